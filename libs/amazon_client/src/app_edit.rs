@@ -7,6 +7,7 @@ use reqwest::{
     // Client,
     Method,
     RequestBuilder,
+    Response,
     Body
 };
 use reqwest_inspect_json::{
@@ -50,39 +51,41 @@ use super::{
 
 ///////////////////////////////////////////////////////
 
-struct EditRequestBuilder<'a>{
-    request_builder: AmazonAppRequestBuilder<'a>,
+struct EditRequestBuilder{
+    request_builder: AmazonAppRequestBuilder,
     edit_id: String
 }
-impl<'a> EditRequestBuilder<'a> {
-    fn new(request_builder: AmazonAppRequestBuilder<'a>, edit_id: String) -> EditRequestBuilder{
+impl EditRequestBuilder {
+    fn new(request_builder: AmazonAppRequestBuilder, edit_id: String) -> EditRequestBuilder{
         EditRequestBuilder{
             request_builder,
             edit_id
         }
     }
-    fn build_request(&self, method: Method, path: &str) -> Result<RequestBuilder, AmazonError> {
+    async fn build_request(&self, method: Method, path: &str) -> Result<RequestBuilder, AmazonError> {
         let path = format!("edits/{}/{}", self.edit_id, path.trim_matches('/'));
         self
             .request_builder
             .build_request(method, &path)
+            .await
     }
 }
 
 ///////////////////////////////////////////////////////
 
-pub struct AppEdit<'a>{
-    request_builder: EditRequestBuilder<'a>
+pub struct AppEdit{
+    request_builder: EditRequestBuilder
 }
-impl<'a> AppEdit<'a> {
-    pub async fn new(request_builder: AmazonAppRequestBuilder<'a>) -> Result<AppEdit<'a>, AmazonError>{
+impl AppEdit {
+    pub async fn new(request_builder: AmazonAppRequestBuilder, discard_existing: bool) -> Result<AppEdit, AmazonError>{
         // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/createEdit_1
 
         // Получаем незавершенное редактирование или стартуем новое
         let edit_info = {
             // Пытаемся получить прошлое редактирование
             let previous_edit = request_builder
-                .build_request(Method::GET, "edits")?
+                .build_request(Method::GET, "edits")
+                .await?
                 .send()
                 .await?
                 .inspect_json::<DataOrErrorResponse<AmazonEditRespone>, AmazonError>(|data|{
@@ -91,6 +94,16 @@ impl<'a> AppEdit<'a> {
                 .await?
                 .into_result()?;
 
+            // Незавершенное редактирование можно удалить, чтобы не подхватить чужие изменения
+            let previous_edit = match previous_edit {
+                AmazonEditRespone::Exists(previous_edit_data) if discard_existing => {
+                    debug!("Previous edit will be discarded: {:#?}", previous_edit_data);
+                    delete_edit(&request_builder, &previous_edit_data.id).await?;
+                    AmazonEditRespone::Empty{}
+                },
+                other => other
+            };
+
             // Пустой ли ответ?
             match previous_edit{
                 AmazonEditRespone::Exists(previous_edit_data) => {
@@ -100,7 +113,8 @@ impl<'a> AppEdit<'a> {
                 AmazonEditRespone::Empty{} => {
                     // Создаем новое редактирование, пустые данные не ожидаем
                     let new_edit = request_builder
-                        .build_request(Method::POST, "edits")?
+                        .build_request(Method::POST, "edits")
+                        .await?
                         .send()
                         .await?
                         .inspect_json::<DataOrErrorResponse<AmazonEditData>, AmazonError>(|data|{
@@ -121,9 +135,14 @@ impl<'a> AppEdit<'a> {
         })      
     }
 
-    async fn get_apks_list(&self) -> Result<Option<Vec<ApkInfoResponse>>, AmazonError> {
+    pub fn get_id(&self) -> &str {
+        &self.request_builder.edit_id
+    }
+
+    pub async fn get_apks_list(&self) -> Result<Option<Vec<ApkInfoResponse>>, AmazonError> {
         let resp = self.request_builder
-            .build_request(Method::GET, "apks")?
+            .build_request(Method::GET, "apks")
+            .await?
             .send()
             .await?;
 
@@ -145,22 +164,12 @@ impl<'a> AppEdit<'a> {
     async fn get_etag_for_apk(&self, info: &ApkInfoResponse) -> Result<String, AmazonError> {
         // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.apks/get_1
         let response = self.request_builder
-            .build_request(Method::GET, &format!("apks/{}", info.id))?
+            .build_request(Method::GET, &format!("apks/{}", info.id))
+            .await?
             .send()
             .await?;
 
-        match response.headers().get("ETag"){
-            Some(header) => {
-                let val = header
-                    .to_str()
-                    .map_err(|_| AmazonError::ETagParseFailed )?
-                    .to_owned();
-                Ok(val)
-            },
-            None => {
-                Err(AmazonError::ETagReceiveFailed)
-            }
-        }
+        get_response_etag(&response)
     }
 
     async fn delete_apk<'b>(&self, info: &'b ApkInfoResponse) -> Result<&'b ApkInfoResponse, AmazonError> {
@@ -171,7 +180,8 @@ impl<'a> AppEdit<'a> {
         debug!("ETag received: {:#?}", etag);
 
         let resp = self.request_builder
-            .build_request(Method::DELETE, &format!("apks/{}", info.id))?
+            .build_request(Method::DELETE, &format!("apks/{}", info.id))
+            .await?
             .header("IF-Match", etag)
             .send()
             .await?;
@@ -209,9 +219,16 @@ impl<'a> AppEdit<'a> {
 
         debug!("Old apks list: {:#?}", old_apks);
 
+        self.remove_apks(old_apks.iter()).await
+    }
+
+    /// Удаляем из редактирования переданные APK
+    pub async fn remove_apks<'b, I>(&self, apks: I) -> Result<(), AmazonError>
+    where
+        I: Iterator<Item = &'b ApkInfoResponse>
+    {
         // Итератор по футурам
-        let delete_futures_iter = old_apks
-            .iter()
+        let delete_futures_iter = apks
             .map(|info|{
                 self.delete_apk(info)
             });
@@ -233,12 +250,12 @@ impl<'a> AppEdit<'a> {
 
         debug!("Uploading started");
 
-        // Имя
-        // let file_name = file_path
-        //     .file_name()
-        //     .ok_or(AmazonError::WrongFilePath)?
-        //     .to_str()
-        //     .ok_or(AmazonError::WrongFilePath)?;
+        // Имя, по нему затем сопоставляются APK при замене
+        let file_name = file_path
+            .file_name()
+            .ok_or(AmazonError::WrongFilePath)?
+            .to_str()
+            .ok_or(AmazonError::WrongFilePath)?;
 
         // Файлик в виде стрима
         let file = File::open(file_path).await?;
@@ -247,10 +264,11 @@ impl<'a> AppEdit<'a> {
         let body = Body::wrap_stream(reader);
 
         let response = self.request_builder
-            .build_request(Method::POST, "apks/upload")?
+            .build_request(Method::POST, "apks/upload")
+            .await?
             .header("Content-Type", "application/octet-stream")
             .header("Content-Length", file_length)
-            // .header("fileName", file_name)
+            .header("fileName", file_name)
             .body(body)
             .send()
             .await?
@@ -265,32 +283,272 @@ impl<'a> AppEdit<'a> {
         Ok(response)
     }
 
-    /*pub async fn validate(&self) -> Result<AmazonEditRespone, AmazonError>{
-        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/validateEdit_1
+    pub async fn replace_apk(&self, info: &ApkInfoResponse, file_path: &Path) -> Result<ApkInfoResponse, AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.apks/replaceApk_1
+        debug!("Replace started: {:#?}", info);
+
+        let etag = self.get_etag_for_apk(info).await?;
+        debug!("ETag received: {:#?}", etag);
+
+        // Файлик в виде стрима
+        let file = File::open(file_path).await?;
+        let file_length = file.metadata().await?.len();
+        let reader = FramedRead::new(file, BytesCodec::new());
+        let body = Body::wrap_stream(reader);
+
         let response = self.request_builder
-            .build_request(Method::POST, "validate")?
+            .build_request(Method::PUT, &format!("apks/{}/replace", info.id))
+            .await?
+            .header("If-Match", etag)
+            .header("Content-Type", "application/octet-stream")
+            .header("Content-Length", file_length)
+            .body(body)
             .send()
             .await?
-            .json::<AmazonEditRespone>()
-            .await?;
+            .inspect_json::<DataOrErrorResponse<ApkInfoResponse>, AmazonError>(|data|{
+                debug!("{}", data);
+            })
+            .await?
+            .into_result()?;
+
+        debug!("Replace finished: {:#?}", response);
 
         Ok(response)
-    }*/
+    }
+
+    /// Нацеливает APK только на переданные устройства Amazon, остальные устройства отключаются
+    pub async fn update_targeting(&self, apk_id: &str, device_ids: &[String]) -> Result<(), AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.apks.targeting/updateTargeting_1
+        let response = self.request_builder
+            .build_request(Method::GET, &format!("apks/{}/targeting", apk_id))
+            .await?
+            .send()
+            .await?;
+
+        let etag = get_response_etag(&response)?;
 
-    /*pub async fn commit_apk<'b>(&self, info: &'b ApkInfoResponse) -> Result<AmazonEditRespone, AmazonError>{
-        debug!("Commit with info: {:#?}", info);
+        let mut targeting = response
+            .inspect_json::<DataOrErrorResponse<ApkTargetingResponse>, AmazonError>(|data|{
+                debug!("Targeting data: {}", data);
+            })
+            .await?
+            .into_result()?;
 
-        let etag = self
-            .get_etag_for_apk(info).await?;
+        // Неизвестное устройство скорее всего опечатка, поэтому не молчим
+        for device_id in device_ids {
+            if !targeting.amazon_devices.iter().any(|device| device.id.eq(device_id)) {
+                return Err(AmazonError::UnknownTargetingDevice{
+                    apk_id: apk_id.to_owned(),
+                    device_id: device_id.clone()
+                });
+            }
+        }
+
+        for device in targeting.amazon_devices.iter_mut() {
+            device.status = if device_ids.contains(&device.id) {
+                "TARGETING".to_owned()
+            }else{
+                "NOT_TARGETING".to_owned()
+            };
+        }
 
         let response = self.request_builder
-            .build_request(Method::POST, "commit")?
+            .build_request(Method::PUT, &format!("apks/{}/targeting", apk_id))
+            .await?
             .header("If-Match", etag)
+            .json(&targeting)
             .send()
             .await?
-            .json::<AmazonEditRespone>()
+            .inspect_json::<DataOrErrorResponse<ApkTargetingResponse>, AmazonError>(|data|{
+                debug!("Targeting update response: {}", data);
+            })
             .await?;
 
+        match response {
+            DataOrErrorResponse::Ok(data) => {
+                debug!("Targeting updated: {:#?}", data);
+                Ok(())
+            },
+            DataOrErrorResponse::Err(err) => {
+                error!("Targeting update failed: {:#?}", err);
+                Err(AmazonError::TargetingUpdateFailed{
+                    apk_id: apk_id.to_owned(),
+                    messages: err.into_messages()
+                })
+            }
+        }
+    }
+
+    /// Получаем листинг для языка вместе с его ETag, нужным для обновления
+    pub async fn get_listing(&self, language: &str) -> Result<(ListingResponse, String), AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.listings/get_3
+        let response = self.request_builder
+            .build_request(Method::GET, &format!("listings/{}", language))
+            .await?
+            .send()
+            .await?;
+
+        if response.status() == http::StatusCode::NOT_FOUND {
+            return Err(AmazonError::ListingNotFound(language.to_owned()));
+        }
+
+        let etag = get_response_etag(&response)?;
+
+        let listing = response
+            .inspect_json::<DataOrErrorResponse<ListingResponse>, AmazonError>(|data|{
+                debug!("Listing data: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        Ok((listing, etag))
+    }
+
+    /// Полностью заменяет листинг для языка, ETag берется из предыдущего получения листинга
+    pub async fn update_listing(&self, listing: &ListingResponse, etag: &str) -> Result<ListingResponse, AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.listings/update_3
+        let response = self.request_builder
+            .build_request(Method::PUT, &format!("listings/{}", listing.language))
+            .await?
+            .header("If-Match", etag)
+            .json(listing)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<ListingResponse>, AmazonError>(|data|{
+                debug!("Listing update response: {}", data);
+            })
+            .await?;
+
+        match response {
+            DataOrErrorResponse::Ok(data) => {
+                debug!("Listing updated: {:#?}", data);
+                Ok(data)
+            },
+            DataOrErrorResponse::Err(err) => {
+                error!("Listing update failed: {:#?}", err);
+                Err(AmazonError::ListingUpdateFailed{
+                    language: listing.language.clone(),
+                    messages: err.into_messages()
+                })
+            }
+        }
+    }
+
+    pub async fn validate(&self) -> Result<AmazonEditData, AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/validateEdit_1
+        let response = self.request_builder
+            .build_request(Method::POST, "validate")
+            .await?
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<AmazonEditData>, AmazonError>(|data|{
+                debug!("Validate response: {}", data);
+            })
+            .await?;
+
+        match response {
+            DataOrErrorResponse::Ok(data) => {
+                debug!("Validation finished: {:#?}", data);
+                Ok(data)
+            },
+            DataOrErrorResponse::Err(err) => {
+                error!("Validation failed: {:#?}", err);
+                Err(AmazonError::ValidationFailed(err.into_messages()))
+            }
+        }
+    }
+
+    pub async fn commit(&self) -> Result<AmazonEditData, AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/commitEdit_1
+        let etag = get_edit_etag(&self.request_builder.request_builder, &self.request_builder.edit_id).await?;
+        debug!("Commit with edit ETag: {}", etag);
+
+        let response = self.request_builder
+            .build_request(Method::POST, "commit")
+            .await?
+            .header("If-Match", etag)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<AmazonEditData>, AmazonError>(|data|{
+                debug!("Commit response: {}", data);
+            })
+            .await?;
+
+        match response {
+            DataOrErrorResponse::Ok(data) => {
+                debug!("Commit finished: {:#?}", data);
+                Ok(data)
+            },
+            DataOrErrorResponse::Err(err) => {
+                error!("Commit failed: {:#?}", err);
+                Err(AmazonError::CommitFailed(err.into_messages()))
+            }
+        }
+    }
+
+    /// Текущее состояние редактирования, после коммита показывает статус отправки
+    pub async fn get_status(&self) -> Result<AmazonEditData, AmazonError>{
+        // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/getEdit_1
+        let response = self.request_builder
+            .request_builder
+            .build_request(Method::GET, &format!("edits/{}", self.request_builder.edit_id))
+            .await?
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<AmazonEditData>, AmazonError>(|data|{
+                debug!("Edit status response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
         Ok(response)
-    }*/
-}
\ No newline at end of file
+    }
+}
+
+///////////////////////////////////////////////////////
+
+async fn get_edit_etag(request_builder: &AmazonAppRequestBuilder, edit_id: &str) -> Result<String, AmazonError> {
+    // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/getEdit_1
+    let response = request_builder
+        .build_request(Method::GET, &format!("edits/{}", edit_id))
+        .await?
+        .send()
+        .await?;
+
+    get_response_etag(&response)
+}
+
+fn get_response_etag(response: &Response) -> Result<String, AmazonError> {
+    match response.headers().get("ETag"){
+        Some(header) => {
+            let val = header
+                .to_str()
+                .map_err(|_| AmazonError::ETagParseFailed )?
+                .to_owned();
+            Ok(val)
+        },
+        None => {
+            Err(AmazonError::ETagReceiveFailed)
+        }
+    }
+}
+
+async fn delete_edit(request_builder: &AmazonAppRequestBuilder, edit_id: &str) -> Result<(), AmazonError> {
+    // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits/deleteEdit_1
+    let etag = get_edit_etag(request_builder, edit_id).await?;
+
+    let resp = request_builder
+        .build_request(Method::DELETE, &format!("edits/{}", edit_id))
+        .await?
+        .header("If-Match", etag)
+        .send()
+        .await?;
+
+    if resp.status().is_success() {
+        debug!("Edit deleted: {}", edit_id);
+        Ok(())
+    }else{
+        error!("Edit delete failed: code={}", resp.status());
+        Err(AmazonError::EditDeleteFailedWithCode(resp.status()))
+    }
+}
@@ -1,11 +1,12 @@
 use super::{
     app_edit::AppEdit, error::AmazonError, request_builder::AmazonAppRequestBuilder,
-    token::AmazonAccessToken,
+    responses::ApkInfoResponse, token::AmazonTokenProvider,
 };
-use log::error;
+use log::{debug, error, info};
 use reqwest::Client;
-use std::path::Path;
+use std::{path::Path, time::Duration};
 use tap::TapFallible;
+use tokio::time::{sleep, Instant};
 
 // Доки
 // https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html
@@ -14,50 +15,249 @@ use tap::TapFallible;
 
 pub struct AmazonUploadTask<'a> {
     pub application_id: &'a str,
+    pub apks: &'a [AmazonApkUpload<'a>],
+    /// Заменять существующие APK на месте с сохранением их таргетинга, несопоставленные старые APK удаляются
+    pub replace_existing: bool,
+    /// Валидировать и коммитить редактирование после выгрузки
+    pub commit: bool,
+    /// Удалять незавершенное редактирование вместо его переиспользования
+    pub discard_existing_edit: bool,
+    /// Изменения листингов, выполняемые в том же редактировании
+    pub listings: &'a [AmazonListingUpdate],
+}
+
+pub struct AmazonApkUpload<'a> {
     pub file_path: &'a Path,
+    /// Устройства Amazon, на которые нацелен APK, пустой список оставляет таргетинг без изменений
+    pub device_ids: &'a [String],
+}
+
+/// Обновление листинга для конкретного языка, пустые поля остаются без изменений
+#[derive(Debug, Default)]
+pub struct AmazonListingUpdate {
+    /// Язык листинга, например `en-US`
+    pub language: String,
+    /// Текст "What's new"
+    pub recent_changes: Option<String>,
+    pub short_description: Option<String>,
+    pub full_description: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct AmazonUploadedApk {
+    pub apk_id: String,
+    pub file_name: String,
+    /// APK заменил существующий вместо выгрузки нового
+    pub replaced: bool,
+}
+
+#[derive(Debug)]
+pub struct AmazonUploadResult {
+    pub edit_id: String,
+    pub apks: Vec<AmazonUploadedApk>,
+    /// Статус отправки после коммита, если он выполнялся
+    pub submission_status: Option<String>,
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////
 
 pub struct AmazonClient {
     http_client: Client,
-    token: AmazonAccessToken,
+    token_provider: AmazonTokenProvider,
 }
 impl AmazonClient {
-    pub fn new(http_client: Client, token: AmazonAccessToken) -> AmazonClient {
-        AmazonClient { http_client, token }
+    pub fn new(http_client: Client, token_provider: AmazonTokenProvider) -> AmazonClient {
+        AmazonClient {
+            http_client,
+            token_provider,
+        }
     }
 
-    async fn build_edit<'a>(&'a self, app_id: &str) -> Result<AppEdit<'a>, AmazonError> {
-        let request_builder =
-            AmazonAppRequestBuilder::new(self.http_client.clone(), &self.token, app_id).tap_err(
-                |err| {
-                    error!("Request builder create failed: {}", err);
-                },
-            )?;
+    async fn build_edit(
+        &self,
+        app_id: &str,
+        discard_existing: bool,
+    ) -> Result<AppEdit, AmazonError> {
+        let request_builder = AmazonAppRequestBuilder::new(
+            self.http_client.clone(),
+            self.token_provider.clone(),
+            app_id,
+        )
+        .tap_err(|err| {
+            error!("Request builder create failed: {}", err);
+        })?;
 
-        let edit = AppEdit::new(request_builder).await?;
+        let edit = AppEdit::new(request_builder, discard_existing).await?;
 
         Ok(edit)
     }
 
-    pub async fn upload(&self, task: AmazonUploadTask<'_>) -> Result<(), AmazonError> {
-        let edit = self.build_edit(task.application_id).await.tap_err(|err| {
-            error!("Edit create failed: {}", err);
-        })?;
+    pub async fn upload(
+        &self,
+        task: AmazonUploadTask<'_>,
+    ) -> Result<AmazonUploadResult, AmazonError> {
+        let edit = self
+            .build_edit(task.application_id, task.discard_existing_edit)
+            .await
+            .tap_err(|err| {
+                error!("Edit create failed: {}", err);
+            })?;
+
+        // При замене старые APK нужны для сопоставления, иначе просто удаляем их
+        let existing_apks = if task.replace_existing {
+            edit.get_apks_list().await?.unwrap_or_default()
+        } else {
+            edit.remove_old_apks().await.tap_err(|err| {
+                error!("Remove old apps failed: {}", err);
+            })?;
+            Vec::new()
+        };
+
+        // Выгружаем по очереди, параллельные изменения одного редактирования Amazon не любит
+        let mut uploaded_apks = Vec::with_capacity(task.apks.len());
+        let mut replaced_ids: Vec<&str> = Vec::new();
+        for apk in task.apks {
+            let file_name = apk
+                .file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or(AmazonError::WrongFilePath)?;
+
+            let replace_target =
+                find_replace_target(&existing_apks, &replaced_ids, file_name, task.apks.len());
+
+            let info = match replace_target {
+                Some(old_info) => {
+                    replaced_ids.push(&old_info.id);
+                    edit.replace_apk(old_info, apk.file_path)
+                        .await
+                        .tap_err(|err| {
+                            error!("Replace failed: {}", err);
+                        })?
+                }
+                None => edit.upload_new_apk(apk.file_path).await.tap_err(|err| {
+                    error!("Upload failed: {}", err);
+                })?,
+            };
+
+            if !apk.device_ids.is_empty() {
+                edit.update_targeting(&info.id, apk.device_ids)
+                    .await
+                    .tap_err(|err| {
+                        error!("Targeting update failed: {}", err);
+                    })?;
+            }
+
+            uploaded_apks.push(AmazonUploadedApk {
+                apk_id: info.id,
+                file_name: file_name.to_owned(),
+                replaced: replace_target.is_some(),
+            });
+        }
+
+        // Незамененные старые APK иначе остались бы в редактировании вместе с новыми
+        if !existing_apks.is_empty() {
+            let stale_apks = existing_apks
+                .iter()
+                .filter(|info| !replaced_ids.contains(&info.id.as_str()));
+            edit.remove_apks(stale_apks).await.tap_err(|err| {
+                error!("Remove stale apks failed: {}", err);
+            })?;
+        }
 
-        edit.remove_old_apks().await.tap_err(|err| {
-            error!("Remove old apps failed: {}", err);
+        for update in task.listings {
+            Self::update_listing(&edit, update).await.tap_err(|err| {
+                error!("Listing update failed: {}", err);
+            })?;
+        }
+
+        // Без коммита изменения остаются в редактировании и публикуются руками из консоли
+        if !task.commit {
+            return Ok(AmazonUploadResult {
+                edit_id: edit.get_id().to_owned(),
+                apks: uploaded_apks,
+                submission_status: None,
+            });
+        }
+
+        edit.validate().await.tap_err(|err| {
+            error!("Edit validation failed: {}", err);
         })?;
 
-        let _info = edit.upload_new_apk(task.file_path).await.tap_err(|err| {
-            error!("Upload failed: {}", err);
+        edit.commit().await.tap_err(|err| {
+            error!("Edit commit failed: {}", err);
         })?;
 
-        // Валидация и коммит вроде как запрещены текущим аккаунтом, делаем лишь выгрузку
-        // edit.validate().await?;
-        // edit.commit_apk(&info).await?;
+        let status = Self::wait_submission_status(&edit).await?;
+
+        Ok(AmazonUploadResult {
+            edit_id: edit.get_id().to_owned(),
+            apks: uploaded_apks,
+            submission_status: Some(status),
+        })
+    }
+
+    async fn update_listing(
+        edit: &AppEdit,
+        update: &AmazonListingUpdate,
+    ) -> Result<(), AmazonError> {
+        let (mut listing, etag) = edit.get_listing(&update.language).await?;
+
+        if let Some(recent_changes) = &update.recent_changes {
+            listing.recent_changes = Some(recent_changes.clone());
+        }
+        if let Some(short_description) = &update.short_description {
+            listing.short_description = Some(short_description.clone());
+        }
+        if let Some(full_description) = &update.full_description {
+            listing.full_description = Some(full_description.clone());
+        }
+
+        edit.update_listing(&listing, &etag).await?;
+        info!("Amazon listing updated: {}", update.language);
 
         Ok(())
     }
+
+    /// Ждем, пока редактирование выйдет из состояния обработки
+    async fn wait_submission_status(edit: &AppEdit) -> Result<String, AmazonError> {
+        const POLL_INTERVAL: Duration = Duration::from_secs(15);
+        const POLL_TIMEOUT: Duration = Duration::from_secs(60 * 10);
+
+        let deadline = Instant::now() + POLL_TIMEOUT;
+        loop {
+            let status = edit.get_status().await?.status;
+            debug!("Amazon edit status: {}", status);
+
+            if !status.eq("IN_PROGRESS") {
+                info!("Amazon submission status: {}", status);
+                return Ok(status);
+            }
+
+            if Instant::now() >= deadline {
+                return Err(AmazonError::SubmissionStatusTimeout(status));
+            }
+
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Ищем APK для замены: сначала по имени файла, а при единственном APK с обеих сторон - его
+fn find_replace_target<'e>(
+    existing_apks: &'e [ApkInfoResponse],
+    replaced_ids: &[&str],
+    file_name: &str,
+    uploads_count: usize,
+) -> Option<&'e ApkInfoResponse> {
+    let by_name = existing_apks
+        .iter()
+        .filter(|info| !replaced_ids.contains(&info.id.as_str()))
+        .find(|info| info.name.eq(file_name));
+
+    match (by_name, existing_apks) {
+        (Some(info), _) => Some(info),
+        (None, [single]) if uploads_count == 1 => Some(single),
+        (None, _) => None,
+    }
 }
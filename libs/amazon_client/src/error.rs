@@ -39,6 +39,14 @@ pub enum AmazonError{
     ApkListFailedWithCode(StatusCode),
     ApkDeleteFailedWithCode{ code: StatusCode, message: Option<String>, desc: Option<String>},
     UploadingFailedWithCode(StatusCode),
+    EditDeleteFailedWithCode(StatusCode),
+    ValidationFailed(Vec<String>),
+    CommitFailed(Vec<String>),
+    SubmissionStatusTimeout(String),
+    ListingNotFound(String),
+    UnknownTargetingDevice{ apk_id: String, device_id: String },
+    TargetingUpdateFailed{ apk_id: String, messages: Vec<String>},
+    ListingUpdateFailed{ language: String, messages: Vec<String>},
     ApiError(ErrorResponseValue),
     Custom(String),
 }
@@ -71,7 +79,15 @@ impl From<ErrorResponseValue> for AmazonError {
 
 impl Display for AmazonError{
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Amazon error: {:#?}", self)
+        match self {
+            // API не умеет добавлять новые языки в листинг, это делается только через консоль
+            AmazonError::ListingNotFound(language) => writeln!(
+                f,
+                "Amazon error: listing for language '{}' not found, add this language in the Amazon Developer Console first",
+                language
+            ),
+            _ => writeln!(f, "Amazon error: {:#?}", self),
+        }
     }
 }
 
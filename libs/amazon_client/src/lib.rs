@@ -9,11 +9,16 @@ mod error;
 pub use self::{
     token::{
         AmazonAccessToken,
+        AmazonTokenProvider,
         request_token
     },
     client::{
         AmazonClient,
-        AmazonUploadTask
+        AmazonUploadTask,
+        AmazonApkUpload,
+        AmazonUploadedApk,
+        AmazonUploadResult,
+        AmazonListingUpdate
     },
     error::{
         AmazonError
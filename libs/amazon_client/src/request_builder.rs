@@ -1,4 +1,4 @@
-use super::{error::AmazonError, token::AmazonAccessToken};
+use super::{error::AmazonError, token::AmazonTokenProvider};
 use log::debug;
 use reqwest::{Client, Method, RequestBuilder, Url};
 
@@ -6,17 +6,18 @@ use reqwest::{Client, Method, RequestBuilder, Url};
 
 //////////////////////////////////////////////////////////////////////////////////////////
 
-pub struct AmazonAppRequestBuilder<'a> {
+#[derive(Clone)]
+pub struct AmazonAppRequestBuilder {
     http_client: Client,
     api_url: Url,
-    token: &'a AmazonAccessToken,
+    token_provider: AmazonTokenProvider,
 }
-impl<'a> AmazonAppRequestBuilder<'a> {
+impl AmazonAppRequestBuilder {
     pub fn new(
         http_client: Client,
-        token: &'a AmazonAccessToken,
+        token_provider: AmazonTokenProvider,
         app_id: &str,
-    ) -> Result<AmazonAppRequestBuilder<'a>, AmazonError> {
+    ) -> Result<AmazonAppRequestBuilder, AmazonError> {
         let base_addr = format!(
             "https://developer.amazon.com/api/appstore/v1/applications/{}/",
             app_id
@@ -33,7 +34,7 @@ impl<'a> AmazonAppRequestBuilder<'a> {
 
         Ok(AmazonAppRequestBuilder {
             http_client,
-            token,
+            token_provider,
             api_url,
         })
     }
@@ -59,17 +60,22 @@ impl<'a> AmazonAppRequestBuilder<'a> {
         builder
     }*/
 
-    pub fn build_request(&self, method: Method, path: &str) -> Result<RequestBuilder, AmazonError> {
+    pub async fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, AmazonError> {
         let AmazonAppRequestBuilder {
             http_client,
-            token,
+            token_provider,
             api_url,
             ..
         } = &self;
 
         let full_url = api_url.join(path)?;
 
-        let token = token.as_str_checked()?;
+        // Получаем токен с перезапросом если надо
+        let token = token_provider.get_access_token().await?;
 
         let builder = http_client
             .request(method, full_url.as_str())
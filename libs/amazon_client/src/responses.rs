@@ -1,5 +1,10 @@
 use serde::{
-    Deserialize
+    Deserialize,
+    Serialize
+};
+use serde_json::{
+    Map,
+    Value
 };
 
 
@@ -32,9 +37,46 @@ pub struct ApkInfoResponse{
     pub name: String
 }
 
+// https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.apks.targeting/getTargeting_1
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApkTargetingResponse{
+    #[serde(default)]
+    pub amazon_devices: Vec<DeviceTargetingInfo>,
+    // Остальные поля сохраняем как есть, так как PUT полностью заменяет таргетинг
+    #[serde(flatten)]
+    pub other: Map<String, Value>
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DeviceTargetingInfo{
+    pub id: String,
+    pub status: String,
+    #[serde(flatten)]
+    pub other: Map<String, Value>
+}
+
 
 // #[serde(rename = "versionCode")]
 
+// https://developer.amazon.com/docs/app-submission-api/appsubapi-endpoints.html#/Edits.listings/get_3
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingResponse{
+    pub language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_changes: Option<String>,
+    // Остальные поля сохраняем как есть, так как PUT полностью заменяет листинг
+    #[serde(flatten)]
+    pub other: Map<String, Value>
+}
+
 //////////////////////////////////////////////////////////////////////
 
 /// Специальный шаблонный тип, чтобы можно было парсить возвращаемые ошибки в ответах
@@ -69,4 +111,17 @@ pub struct ErrorResponseValue{
     pub errors: Option<Vec<ErrorInfo>>,
     // #[serde(flatten)]
     // other: HashMap<String, Value>
-}
\ No newline at end of file
+}
+impl ErrorResponseValue {
+    /// Все сообщения об ошибках из ответа
+    pub fn into_messages(self) -> Vec<String> {
+        self.message
+            .into_iter()
+            .chain(self.errors
+                .into_iter()
+                .flatten()
+                .map(|info| info.error_message)
+                .filter(|message| !message.is_empty()))
+            .collect()
+    }
+}
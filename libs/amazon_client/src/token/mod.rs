@@ -0,0 +1,7 @@
+mod provider;
+mod token_struct;
+
+pub use self::{
+    provider::AmazonTokenProvider,
+    token_struct::{request_token, AmazonAccessToken},
+};
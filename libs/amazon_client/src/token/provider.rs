@@ -0,0 +1,51 @@
+use super::token_struct::request_token;
+use crate::error::AmazonError;
+use reqwest::Client;
+use std::{sync::Arc, time::Duration};
+use token_provider::TokenProvider;
+
+////////////////////////////////////////////////////////////////
+
+/// Провайдер токенов Amazon, обновляет токен заранее перед его истечением
+#[derive(Debug, Clone)]
+pub struct AmazonTokenProvider {
+    inner: TokenProvider<AmazonError>,
+}
+
+impl AmazonTokenProvider {
+    pub fn new(
+        http_client: Client,
+        client_id: String,
+        client_secret: String,
+    ) -> AmazonTokenProvider {
+        Self::new_custom(
+            http_client,
+            client_id,
+            client_secret,
+            Duration::from_secs(60 * 3),
+        )
+    }
+
+    pub fn new_custom(
+        http_client: Client,
+        client_id: String,
+        client_secret: String,
+        token_expire_pre_delay: Duration,
+    ) -> AmazonTokenProvider {
+        let inner = TokenProvider::new("Amazon", token_expire_pre_delay, move || {
+            let http_client = http_client.clone();
+            let client_id = client_id.clone();
+            let client_secret = client_secret.clone();
+            async move {
+                let token = request_token(&http_client, &client_id, &client_secret).await?;
+                Ok(token.into_fetched())
+            }
+        });
+
+        AmazonTokenProvider { inner }
+    }
+
+    pub async fn get_access_token(&self) -> Result<Arc<String>, AmazonError> {
+        self.inner.get_access_token().await
+    }
+}
@@ -1,8 +1,9 @@
-use super::{error::AmazonError, responses::AmazonTokenResponse};
+use crate::{error::AmazonError, responses::AmazonTokenResponse};
 use log::debug;
 use reqwest::Client;
 use serde_json::json;
 use std::time::{Duration, Instant};
+use token_provider::FetchedToken;
 
 #[derive(Debug)]
 pub struct AmazonAccessToken {
@@ -13,6 +14,7 @@ impl AmazonAccessToken {
     pub fn new(value: String, expire_time: Instant) -> AmazonAccessToken {
         AmazonAccessToken { value, expire_time }
     }
+
     pub fn as_str_checked(&self) -> Result<&str, AmazonError> {
         if Instant::now() < self.expire_time {
             Ok(self.value.as_str())
@@ -20,6 +22,14 @@ impl AmazonAccessToken {
             Err(AmazonError::TokenIsExpired)
         }
     }
+
+    /// Оставшееся время жизни токена для общего провайдера
+    pub(crate) fn into_fetched(self) -> FetchedToken {
+        FetchedToken {
+            lifetime: self.expire_time.saturating_duration_since(Instant::now()),
+            value: self.value,
+        }
+    }
 }
 
 pub async fn request_token(
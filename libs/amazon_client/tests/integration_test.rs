@@ -1,7 +1,8 @@
 use amazon_client::{
-    request_token,
     // AmazonAccessToken,
+    AmazonApkUpload,
     AmazonClient,
+    AmazonTokenProvider,
     AmazonUploadTask,
 };
 use log::debug;
@@ -33,22 +34,30 @@ async fn library_integration_test() {
 
     let http_client = Client::new();
 
-    let token = request_token(&http_client, &client_id, &client_secret)
+    let token_provider = AmazonTokenProvider::new(http_client.clone(), client_id, client_secret);
+
+    let token_str = token_provider
+        .get_access_token()
         .await
         .expect("Access token request failed");
 
-    let token_str = token.as_str_checked().expect("Token string get failed");
-
     debug!("Token: {:#?}", token_str);
 
     let file_path = Path::new(
         "/Users/devnul/Downloads/Island2-arm32-amazon-12.16.5-413-06092021_1919-6d8422f5.apk",
     );
 
-    let client = AmazonClient::new(http_client, token);
+    let client = AmazonClient::new(http_client, token_provider);
     let task = AmazonUploadTask {
         application_id: &app_id,
-        file_path,
+        apks: &[AmazonApkUpload {
+            file_path,
+            device_ids: &[],
+        }],
+        replace_existing: false,
+        commit: false,
+        discard_existing_edit: false,
+        listings: &[],
     };
     client.upload(task).await.expect("Uploading failed");
 }
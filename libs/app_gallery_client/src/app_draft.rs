@@ -0,0 +1,264 @@
+use super::{
+    client::AppGalleryPhasedRelease, error::AppGalleryError,
+    request_builder::AppGalleryRequestBuilder, responses::*,
+};
+use chrono::{DateTime, Utc};
+use log::debug;
+use reqwest::{
+    multipart::{Form, Part},
+    Body, Method,
+};
+use reqwest_inspect_json::InspectJson;
+use serde_json::json;
+use std::path::Path;
+use tokio::fs::File;
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+// Главная документация
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-Guides/agcapi-getstarted-0000001111845114
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-Guides/agcapi-upload_and_release-0000001111845092
+
+/// Тип файла пакета приложения в API
+const FILE_TYPE_PACKAGE: u32 = 5;
+
+/// Выпуск на всех пользователей сразу
+const RELEASE_TYPE_FULL: u32 = 1;
+/// Поэтапный выпуск
+const RELEASE_TYPE_PHASED: u32 = 3;
+
+///////////////////////////////////////////////////////
+
+/// Тип файла пакета для запроса урла выгрузки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageType {
+    Apk,
+    Aab,
+}
+impl PackageType {
+    pub fn from_path(file_path: &Path) -> Result<PackageType, AppGalleryError> {
+        let extension = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        match extension.as_deref() {
+            Some("apk") => Ok(PackageType::Apk),
+            Some("aab") => Ok(PackageType::Aab),
+            _ => Err(AppGalleryError::InvalidFileExtention(
+                "Only .apk and .aab files are supported",
+            )),
+        }
+    }
+
+    fn suffix(&self) -> &'static str {
+        match self {
+            PackageType::Apk => "apk",
+            PackageType::Aab => "aab",
+        }
+    }
+}
+
+/// Выгруженный файлик, который еще нужно привязать к черновику версии
+#[derive(Debug)]
+pub struct UploadedFile {
+    pub file_name: String,
+    pub dest_url: String,
+    pub size: u64,
+}
+
+///////////////////////////////////////////////////////
+
+/// Черновик версии приложения, в который выгружается пакет и который затем отправляется на проверку
+pub struct AppDraft {
+    request_builder: AppGalleryRequestBuilder,
+}
+impl AppDraft {
+    pub fn new(request_builder: AppGalleryRequestBuilder) -> AppDraft {
+        AppDraft { request_builder }
+    }
+
+    /// Загружаем файлик пакета на файловый сервер Huawei
+    pub async fn upload_file(
+        &self,
+        file_path: &Path,
+        package_type: PackageType,
+    ) -> Result<UploadedFile, AppGalleryError> {
+        // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-upload-url-0000001158365047
+        let upload_url = self
+            .request_builder
+            .build_request(Method::GET, "upload-url")
+            .await?
+            .query(&[("suffix", package_type.suffix())])
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<UploadUrlResponse>, AppGalleryError>(|data| {
+                debug!("Upload url response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(AppGalleryError::WrongFilePath)?
+            .to_owned();
+
+        // Файлик в виде стрима
+        let file = File::open(file_path).await?;
+        let file_length = file.metadata().await?.len();
+        let reader = FramedRead::new(file, BytesCodec::new());
+        let file_part = Part::stream_with_length(Body::wrap_stream(reader), file_length)
+            .file_name(file_name.clone())
+            .mime_str("application/octet-stream")?;
+
+        // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-upload-file-0000001158245059
+        let form = Form::new()
+            .text("authCode", upload_url.auth_code)
+            .text("fileCount", "1")
+            .text("parseType", "1")
+            .part("file", file_part);
+
+        let response = self
+            .request_builder
+            .get_http_client()
+            .post(&upload_url.upload_url)
+            .header("accept", "application/json")
+            .multipart(form)
+            .send()
+            .await?
+            .inspect_json::<UploadFileResponse, AppGalleryError>(|data| {
+                debug!("Upload file response: {}", data);
+            })
+            .await?;
+
+        if response.result.result_code != "0" {
+            return Err(AppGalleryError::UploadFailed(response.result.result_code));
+        }
+
+        let info = response
+            .result
+            .upload_file_rsp
+            .and_then(|rsp| rsp.file_info_list.into_iter().next())
+            .ok_or(AppGalleryError::UploadResultIsEmpty)?;
+        debug!("File uploaded: {:#?}", info);
+
+        Ok(UploadedFile {
+            file_name,
+            dest_url: info.file_dest_url,
+            size: info.size,
+        })
+    }
+
+    /// Привязываем выгруженный файлик к черновику версии, старый пакет в черновике заменяется
+    pub async fn update_file_info(
+        &self,
+        file: &UploadedFile,
+        phased: bool,
+    ) -> Result<UpdateFileInfoResponse, AppGalleryError> {
+        // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-app-file-info-0000001111685202
+        let release_type = if phased {
+            RELEASE_TYPE_PHASED
+        } else {
+            RELEASE_TYPE_FULL
+        };
+
+        let response = self
+            .request_builder
+            .build_request(Method::PUT, "app-file-info")
+            .await?
+            .query(&[("releaseType", release_type)])
+            .json(&json!({
+                "fileType": FILE_TYPE_PACKAGE,
+                "files": [{
+                    "fileName": file.file_name,
+                    "fileDestUrl": file.dest_url,
+                    "size": file.size
+                }]
+            }))
+            .send()
+            .await?
+            .inspect_json::<UpdateFileInfoResponse, AppGalleryError>(|data| {
+                debug!("Update file info response: {}", data);
+            })
+            .await?;
+
+        if response.ret.code != 0 {
+            return Err(response.ret.into());
+        }
+
+        Ok(response)
+    }
+
+    /// Статус компиляции AAB в APK на стороне Huawei
+    pub async fn get_compile_status(
+        &self,
+        pkg_id: &str,
+    ) -> Result<PackageCompileState, AppGalleryError> {
+        // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-query-aab-compile-status-0000001111845086
+        // Опечатка в пути есть в самом API
+        let response = self
+            .request_builder
+            .build_request(Method::GET, "aab/complile/status")
+            .await?
+            .query(&[("pkgIds", pkg_id)])
+            .send()
+            .await?
+            .inspect_json::<CompileStatusResponse, AppGalleryError>(|data| {
+                debug!("Compile status response: {}", data);
+            })
+            .await?;
+
+        if response.ret.code != 0 {
+            return Err(response.ret.into());
+        }
+
+        response
+            .pkg_state_list
+            .into_iter()
+            .find(|state| state.pkg_id.eq(pkg_id))
+            .ok_or_else(|| {
+                AppGalleryError::Custom(format!("Compile status is missing for package {}", pkg_id))
+            })
+    }
+
+    /// Отправляем черновик на проверку, после нее версия выпускается автоматически
+    pub async fn submit(
+        &self,
+        phased_release: Option<&AppGalleryPhasedRelease>,
+    ) -> Result<(), AppGalleryError> {
+        // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-app-submit-0000001158245061
+        let builder = self
+            .request_builder
+            .build_request(Method::POST, "app-submit")
+            .await?;
+
+        let builder = match phased_release {
+            Some(info) => builder
+                .query(&[("releaseType", RELEASE_TYPE_PHASED)])
+                .json(&json!({
+                    "phasedReleaseStartTime": format_time(&info.start_time),
+                    "phasedReleaseEndTime": format_time(&info.end_time),
+                    "phasedReleasePercent": format!("{:.2}", info.percent),
+                    "phasedReleaseDescription": info.description
+                })),
+            None => builder.query(&[("releaseType", RELEASE_TYPE_FULL)]),
+        };
+
+        builder
+            .send()
+            .await?
+            .inspect_json::<RetResponse, AppGalleryError>(|data| {
+                debug!("Submit response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+}
+
+///////////////////////////////////////////////////////
+
+/// Время в формате `2019-10-09T23:00:00+0000`
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%z").to_string()
+}
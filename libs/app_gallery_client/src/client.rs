@@ -0,0 +1,166 @@
+use super::{
+    app_draft::{AppDraft, PackageType},
+    error::AppGalleryError,
+    request_builder::AppGalleryRequestBuilder,
+    token::AppGalleryTokenProvider,
+};
+use chrono::{DateTime, Utc};
+use log::{debug, error, info};
+use reqwest::Client;
+use std::{path::Path, time::Duration};
+use tap::TapFallible;
+use tokio::time::{sleep, Instant};
+
+// Доки
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-reference-0000001158365045
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AppGalleryUploadTask<'a> {
+    pub application_id: &'a str,
+    /// Файлик .apk или .aab
+    pub file_path: &'a Path,
+    /// Отправлять версию на проверку после выгрузки
+    pub submit: bool,
+    /// Поэтапный выпуск, без него версия выпускается на всех пользователей
+    pub phased_release: Option<&'a AppGalleryPhasedRelease>,
+    /// Сколько ждем компиляции AAB перед отправкой на проверку
+    pub compile_timeout: Duration,
+}
+
+/// Поэтапный выпуск на процент пользователей в течение указанного времени
+#[derive(Debug)]
+pub struct AppGalleryPhasedRelease {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Процент пользователей от 0.01 до 100
+    pub percent: f32,
+    pub description: String,
+}
+
+#[derive(Debug)]
+pub struct AppGalleryUploadResult {
+    pub file_name: String,
+    /// Идентификатор пакета, возвращается лишь для AAB
+    pub pkg_id: Option<String>,
+    pub pkg_versions: Vec<String>,
+    pub submitted: bool,
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AppGalleryClient {
+    http_client: Client,
+    token_provider: AppGalleryTokenProvider,
+}
+impl AppGalleryClient {
+    pub fn new(http_client: Client, token_provider: AppGalleryTokenProvider) -> AppGalleryClient {
+        AppGalleryClient {
+            http_client,
+            token_provider,
+        }
+    }
+
+    fn build_draft(&self, app_id: &str) -> Result<AppDraft, AppGalleryError> {
+        let request_builder = AppGalleryRequestBuilder::new(
+            self.http_client.clone(),
+            self.token_provider.clone(),
+            app_id,
+        )
+        .tap_err(|err| {
+            error!("Request builder create failed: {}", err);
+        })?;
+
+        Ok(AppDraft::new(request_builder))
+    }
+
+    pub async fn upload(
+        &self,
+        task: AppGalleryUploadTask<'_>,
+    ) -> Result<AppGalleryUploadResult, AppGalleryError> {
+        // Проверяем параметры до выгрузки, чтобы не оставлять черновик в непонятном состоянии
+        let package_type = PackageType::from_path(task.file_path)?;
+        if let Some(phased) = task.phased_release {
+            if !(0.01..=100.0).contains(&phased.percent) {
+                return Err(AppGalleryError::InvalidPhasedReleasePercent(phased.percent));
+            }
+        }
+
+        let draft = self.build_draft(task.application_id)?;
+
+        let uploaded = draft
+            .upload_file(task.file_path, package_type)
+            .await
+            .tap_err(|err| {
+                error!("Upload failed: {}", err);
+            })?;
+        info!("App gallery file uploaded: {}", uploaded.file_name);
+
+        let file_info = draft
+            .update_file_info(&uploaded, task.phased_release.is_some())
+            .await
+            .tap_err(|err| {
+                error!("File info update failed: {}", err);
+            })?;
+        debug!("App gallery file info updated: {:?}", file_info);
+
+        // AAB компилируется на стороне Huawei, до окончания компиляции отправить версию нельзя
+        if package_type == PackageType::Aab {
+            // Без идентификатора пакета узнать статус компиляции нельзя
+            let pkg_id = file_info
+                .pkg_id
+                .as_deref()
+                .ok_or(AppGalleryError::AabPackageIdMissing)?;
+            Self::wait_aab_compilation(&draft, pkg_id, task.compile_timeout).await?;
+        }
+
+        if task.submit {
+            draft.submit(task.phased_release).await.tap_err(|err| {
+                error!("Submit failed: {}", err);
+            })?;
+            info!("App gallery version submitted");
+        }
+
+        Ok(AppGalleryUploadResult {
+            file_name: uploaded.file_name,
+            pkg_id: file_info.pkg_id,
+            pkg_versions: file_info.pkg_version,
+            submitted: task.submit,
+        })
+    }
+
+    /// Ждем окончания компиляции AAB
+    async fn wait_aab_compilation(
+        draft: &AppDraft,
+        pkg_id: &str,
+        timeout: Duration,
+    ) -> Result<(), AppGalleryError> {
+        const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let state = draft.get_compile_status(pkg_id).await?;
+            debug!("App gallery AAB compile status: {:?}", state);
+
+            match state.aab_compile_status {
+                2 => {
+                    info!("App gallery AAB compilation finished");
+                    return Ok(());
+                }
+                3 => {
+                    return Err(AppGalleryError::AabCompileFailed {
+                        pkg_id: pkg_id.to_owned(),
+                        reason: state.failed_reason,
+                    });
+                }
+                _ => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(AppGalleryError::AabCompileTimeout(pkg_id.to_owned()));
+            }
+
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::responses::RetInfo;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use url::ParseError;
+
+#[derive(Debug)]
+pub enum AppGalleryError {
+    URLError(ParseError),
+    NetErr(reqwest::Error),
+    JsonParseErr(serde_json::Error),
+    WrongFilePath,
+    InvalidFileExtention(&'static str),
+    FileError(io::Error),
+    InvalidTokenDuration(u64),
+    TokenIsExpired,
+    UploadFailed(String),
+    UploadResultIsEmpty,
+    InvalidPhasedReleasePercent(f32),
+    AabCompileFailed {
+        pkg_id: String,
+        reason: Option<String>,
+    },
+    AabCompileTimeout(String),
+    AabPackageIdMissing,
+    ApiError(RetInfo),
+    Custom(String),
+}
+
+impl From<ParseError> for AppGalleryError {
+    fn from(err: ParseError) -> AppGalleryError {
+        AppGalleryError::URLError(err)
+    }
+}
+impl From<io::Error> for AppGalleryError {
+    fn from(err: io::Error) -> AppGalleryError {
+        AppGalleryError::FileError(err)
+    }
+}
+impl From<reqwest::Error> for AppGalleryError {
+    fn from(err: reqwest::Error) -> AppGalleryError {
+        AppGalleryError::NetErr(err)
+    }
+}
+impl From<serde_json::Error> for AppGalleryError {
+    fn from(err: serde_json::Error) -> AppGalleryError {
+        AppGalleryError::JsonParseErr(err)
+    }
+}
+impl From<RetInfo> for AppGalleryError {
+    fn from(err: RetInfo) -> AppGalleryError {
+        AppGalleryError::ApiError(err)
+    }
+}
+
+impl Display for AppGalleryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "App gallery error: {:#?}", self)
+    }
+}
+
+impl Error for AppGalleryError {}
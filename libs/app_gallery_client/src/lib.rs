@@ -0,0 +1,14 @@
+mod app_draft;
+mod client;
+mod error;
+mod request_builder;
+mod responses;
+mod token;
+
+pub use self::{
+    client::{
+        AppGalleryClient, AppGalleryPhasedRelease, AppGalleryUploadResult, AppGalleryUploadTask,
+    },
+    error::AppGalleryError,
+    token::{request_token, AppGalleryAccessToken, AppGalleryTokenProvider},
+};
@@ -0,0 +1,59 @@
+use super::{error::AppGalleryError, token::AppGalleryTokenProvider};
+use log::debug;
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-overview-0000001111845114
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub struct AppGalleryRequestBuilder {
+    http_client: Client,
+    api_url: Url,
+    token_provider: AppGalleryTokenProvider,
+    app_id: String,
+}
+impl AppGalleryRequestBuilder {
+    pub fn new(
+        http_client: Client,
+        token_provider: AppGalleryTokenProvider,
+        app_id: &str,
+    ) -> Result<AppGalleryRequestBuilder, AppGalleryError> {
+        let api_url = Url::parse("https://connect-api.cloud.huawei.com/api/publish/v2/")?;
+        debug!("Api url: {}", api_url.as_str());
+
+        Ok(AppGalleryRequestBuilder {
+            http_client,
+            api_url,
+            token_provider,
+            app_id: app_id.to_owned(),
+        })
+    }
+
+    pub fn get_http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    /// Идентификатор приложения передается параметром в каждом запросе
+    pub async fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, AppGalleryError> {
+        let mut full_url = self.api_url.join(path)?;
+        full_url
+            .query_pairs_mut()
+            .append_pair("appId", &self.app_id);
+
+        // Получаем токен с перезапросом если надо
+        let token = self.token_provider.get_access_token().await?;
+
+        let builder = self
+            .http_client
+            .request(method, full_url.as_str())
+            .header("client_id", self.token_provider.get_client_id())
+            .bearer_auth(token);
+
+        Ok(builder)
+    }
+}
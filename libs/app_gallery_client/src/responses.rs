@@ -0,0 +1,116 @@
+use serde::Deserialize;
+
+// Huawei на ошибки часто отвечает кодом 200, а саму ошибку кладет в поле `ret`
+
+/// Результат выполнения запроса, код 0 - успех
+#[derive(Deserialize, Debug)]
+pub struct RetInfo {
+    pub code: i64,
+    #[serde(default)]
+    pub msg: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RetResponse {
+    pub ret: RetInfo,
+}
+impl RetResponse {
+    pub fn into_result(self) -> Result<(), RetInfo> {
+        if self.ret.code == 0 {
+            Ok(())
+        } else {
+            Err(self.ret)
+        }
+    }
+}
+
+/// Специальный шаблонный тип, чтобы можно было парсить возвращаемые ошибки в ответах
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DataOrErrorResponse<D> {
+    Ok(D),
+    Err(RetResponse),
+}
+impl<D> DataOrErrorResponse<D> {
+    pub fn into_result(self) -> Result<D, RetInfo> {
+        match self {
+            DataOrErrorResponse::Ok(ok) => Ok(ok),
+            // Данные могли не распарситься и при успешном коде
+            DataOrErrorResponse::Err(err) => Err(err.ret),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-obtain_token-0000001158365043
+#[derive(Deserialize, Debug)]
+pub struct AppGalleryTokenResponse {
+    pub access_token: String,
+    pub expires_in: u64,
+}
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-upload-url-0000001158365047
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadUrlResponse {
+    pub upload_url: String,
+    pub auth_code: String,
+}
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-upload-file-0000001158245059
+#[derive(Deserialize, Debug)]
+pub struct UploadFileResponse {
+    pub result: UploadFileResult,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UploadFileResult {
+    #[serde(rename = "resultCode")]
+    pub result_code: String,
+    #[serde(rename = "UploadFileRsp")]
+    pub upload_file_rsp: Option<UploadFileRsp>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UploadFileRsp {
+    #[serde(rename = "fileInfoList", default)]
+    pub file_info_list: Vec<UploadedFileInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UploadedFileInfo {
+    // Опечатка в самом API
+    #[serde(rename = "fileDestUlr")]
+    pub file_dest_url: String,
+    pub size: u64,
+}
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-app-file-info-0000001111685202
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFileInfoResponse {
+    pub ret: RetInfo,
+    #[serde(default)]
+    pub pkg_version: Vec<String>,
+    /// Возвращается для AAB, нужен для проверки статуса компиляции
+    pub pkg_id: Option<String>,
+}
+
+// https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-query-aab-compile-status-0000001111845086
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompileStatusResponse {
+    pub ret: RetInfo,
+    #[serde(default)]
+    pub pkg_state_list: Vec<PackageCompileState>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageCompileState {
+    pub pkg_id: String,
+    /// 1 - компилируется, 2 - успех, 3 - ошибка
+    pub aab_compile_status: i32,
+    pub failed_reason: Option<String>,
+}
@@ -0,0 +1,7 @@
+mod provider;
+mod token_struct;
+
+pub use self::{
+    provider::AppGalleryTokenProvider,
+    token_struct::{request_token, AppGalleryAccessToken},
+};
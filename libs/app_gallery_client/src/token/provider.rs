@@ -0,0 +1,60 @@
+use super::token_struct::request_token;
+use crate::error::AppGalleryError;
+use reqwest::Client;
+use std::{sync::Arc, time::Duration};
+use token_provider::TokenProvider;
+
+////////////////////////////////////////////////////////////////
+
+/// Провайдер токенов AppGallery, обновляет токен заранее перед его истечением
+#[derive(Debug, Clone)]
+pub struct AppGalleryTokenProvider {
+    client_id: Arc<String>,
+    inner: TokenProvider<AppGalleryError>,
+}
+
+impl AppGalleryTokenProvider {
+    pub fn new(
+        http_client: Client,
+        client_id: String,
+        client_secret: String,
+    ) -> AppGalleryTokenProvider {
+        Self::new_custom(
+            http_client,
+            client_id,
+            client_secret,
+            Duration::from_secs(60 * 3),
+        )
+    }
+
+    pub fn new_custom(
+        http_client: Client,
+        client_id: String,
+        client_secret: String,
+        token_expire_pre_delay: Duration,
+    ) -> AppGalleryTokenProvider {
+        let client_id = Arc::new(client_id);
+
+        let fetch_client_id = client_id.clone();
+        let inner = TokenProvider::new("App gallery", token_expire_pre_delay, move || {
+            let http_client = http_client.clone();
+            let client_id = fetch_client_id.clone();
+            let client_secret = client_secret.clone();
+            async move {
+                let token = request_token(&http_client, &client_id, &client_secret).await?;
+                Ok(token.into_fetched())
+            }
+        });
+
+        AppGalleryTokenProvider { client_id, inner }
+    }
+
+    /// Идентификатор клиента передается в заголовке каждого запроса вместе с токеном
+    pub fn get_client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub async fn get_access_token(&self) -> Result<Arc<String>, AppGalleryError> {
+        self.inner.get_access_token().await
+    }
+}
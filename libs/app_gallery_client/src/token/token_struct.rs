@@ -0,0 +1,68 @@
+use crate::{
+    error::AppGalleryError,
+    responses::{AppGalleryTokenResponse, DataOrErrorResponse},
+};
+use log::debug;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use token_provider::FetchedToken;
+
+#[derive(Debug)]
+pub struct AppGalleryAccessToken {
+    value: String,
+    expire_time: Instant,
+}
+impl AppGalleryAccessToken {
+    pub fn new(value: String, expire_time: Instant) -> AppGalleryAccessToken {
+        AppGalleryAccessToken { value, expire_time }
+    }
+
+    pub fn as_str_checked(&self) -> Result<&str, AppGalleryError> {
+        if Instant::now() < self.expire_time {
+            Ok(self.value.as_str())
+        } else {
+            Err(AppGalleryError::TokenIsExpired)
+        }
+    }
+
+    /// Оставшееся время жизни токена для общего провайдера
+    pub(crate) fn into_fetched(self) -> FetchedToken {
+        FetchedToken {
+            lifetime: self.expire_time.saturating_duration_since(Instant::now()),
+            value: self.value,
+        }
+    }
+}
+
+pub async fn request_token(
+    http_client: &Client,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<AppGalleryAccessToken, AppGalleryError> {
+    // https://developer.huawei.com/consumer/en/doc/development/AppGallery-connect-References/agcapi-obtain_token-0000001158365043
+
+    let response = http_client
+        .post("https://connect-api.cloud.huawei.com/api/oauth2/v1/token")
+        .json(&json!({
+            "grant_type": "client_credentials",
+            "client_id": client_id,
+            "client_secret": client_secret
+        }))
+        .send()
+        .await?
+        .json::<DataOrErrorResponse<AppGalleryTokenResponse>>()
+        .await?
+        .into_result()?;
+
+    debug!("App gallery token expires in: {}", response.expires_in);
+
+    let expire_time = Instant::now()
+        .checked_add(Duration::from_secs(response.expires_in))
+        .ok_or(AppGalleryError::InvalidTokenDuration(response.expires_in))?;
+
+    Ok(AppGalleryAccessToken::new(
+        response.access_token,
+        expire_time,
+    ))
+}
@@ -0,0 +1,55 @@
+use app_gallery_client::{AppGalleryClient, AppGalleryTokenProvider, AppGalleryUploadTask};
+use log::debug;
+use reqwest::Client;
+use std::{path::PathBuf, sync::Once, time::Duration};
+
+fn setup_logs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        if std::env::var("RUST_LOG").is_err() {
+            std::env::set_var("RUST_LOG", "debug");
+        }
+        env_logger::init();
+    })
+}
+
+// Версия не отправляется на проверку, файлик лишь попадает в черновик:
+// APP_GALLERY_CLIENT_ID=... APP_GALLERY_CLIENT_SECRET=... APP_GALLERY_APP_ID=... \
+// APP_GALLERY_TEST_FILE=app.aab cargo test -- --ignored
+#[tokio::test]
+#[ignore]
+async fn library_integration_test() {
+    setup_logs();
+
+    let client_id = std::env::var("APP_GALLERY_CLIENT_ID")
+        .expect("APP_GALLERY_CLIENT_ID environment variable is missing");
+
+    let client_secret = std::env::var("APP_GALLERY_CLIENT_SECRET")
+        .expect("APP_GALLERY_CLIENT_SECRET environment variable is missing");
+
+    let app_id = std::env::var("APP_GALLERY_APP_ID")
+        .expect("APP_GALLERY_APP_ID environment variable is missing");
+
+    let file_path = PathBuf::from(
+        std::env::var("APP_GALLERY_TEST_FILE")
+            .expect("APP_GALLERY_TEST_FILE environment variable is missing"),
+    );
+
+    let http_client = Client::new();
+
+    let token_provider =
+        AppGalleryTokenProvider::new(http_client.clone(), client_id, client_secret);
+
+    let client = AppGalleryClient::new(http_client, token_provider);
+
+    let task = AppGalleryUploadTask {
+        application_id: &app_id,
+        file_path: &file_path,
+        submit: false,
+        phased_release: None,
+        compile_timeout: Duration::from_secs(60 * 30),
+    };
+    let result = client.upload(task).await.expect("Uploading failed");
+
+    debug!("Uploading result: {:#?}", result);
+}
@@ -0,0 +1,568 @@
+use super::{
+    error::AppStoreConnectError,
+    ipa_info::{read_ipa_info, IpaInfo},
+    request_builder::AppStoreConnectRequestBuilder,
+    responses::{
+        AppResource, BuildUploadFileResource, BuildUploadResource, DataOrErrorResponse,
+        UploadOperation,
+    },
+    testflight,
+    token::AppStoreConnectTokenProvider,
+};
+use futures::{stream, StreamExt, TryStreamExt};
+use log::{debug, error, info, warn};
+use reqwest::{Client, Method};
+use reqwest_inspect_json::InspectJson;
+use serde_json::json;
+use std::{io::SeekFrom, path::Path, time::Duration};
+use tap::TapFallible;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+    task::spawn_blocking,
+    time::{sleep, Instant},
+};
+
+// Доки
+// https://developer.apple.com/documentation/appstoreconnectapi/build_uploads
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AppStoreConnectUploadTask<'a> {
+    pub ipa_path: &'a Path,
+    /// Максимальное время ожидания обработки выгруженного билда
+    pub processing_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct AppStoreConnectUploadResult {
+    pub app_id: String,
+    pub ipa_info: IpaInfo,
+    pub build_upload_id: String,
+    /// Идентификатор созданного билда, если App Store Connect его уже вернул
+    pub build_id: Option<String>,
+}
+
+/// Текст "What to Test" для конкретной локали, например "en-US"
+pub struct TestFlightLocalization {
+    pub locale: String,
+    pub what_to_test: String,
+}
+
+pub struct AppStoreConnectTestFlightTask<'a> {
+    pub what_to_test: &'a [TestFlightLocalization],
+    /// Имена бета-групп, в которые добавляем билд
+    pub beta_groups: &'a [String],
+    /// Email отдельных тестировщиков
+    pub testers: &'a [String],
+    pub submit_for_review: bool,
+    /// Максимальное время ожидания обработки билда
+    pub processing_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct AppStoreConnectTestFlightResult {
+    pub build_id: String,
+    /// Ссылка на билд в App Store Connect
+    pub build_url: String,
+    /// Публичные ссылки групп, у которых они включены
+    pub public_links: Vec<String>,
+    /// Состояние бета-ревью, если билд был отправлен
+    pub beta_review_state: Option<String>,
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct AppStoreConnectClient {
+    request_builder: AppStoreConnectRequestBuilder,
+}
+impl AppStoreConnectClient {
+    pub fn new(
+        http_client: Client,
+        token_provider: AppStoreConnectTokenProvider,
+    ) -> AppStoreConnectClient {
+        AppStoreConnectClient {
+            request_builder: AppStoreConnectRequestBuilder::new(http_client, token_provider),
+        }
+    }
+
+    pub async fn upload(
+        &self,
+        task: AppStoreConnectUploadTask<'_>,
+    ) -> Result<AppStoreConnectUploadResult, AppStoreConnectError> {
+        // Версии билда берем прямо из Info.plist
+        let ipa_info = {
+            let ipa_path = task.ipa_path.to_owned();
+            spawn_blocking(move || read_ipa_info(&ipa_path))
+                .await
+                .map_err(|err| AppStoreConnectError::Custom(err.to_string()))?
+                .tap_err(|err| {
+                    error!("Ipa info read failed: {}", err);
+                })?
+        };
+        info!("Ipa info: {:?}", ipa_info);
+
+        let app_id = self.find_app_id(&ipa_info.bundle_id).await.tap_err(|err| {
+            error!("Application search failed: {}", err);
+        })?;
+
+        let build_upload_id =
+            self.create_build_upload(&app_id, &ipa_info)
+                .await
+                .tap_err(|err| {
+                    error!("Build upload create failed: {}", err);
+                })?;
+
+        let file_name = task
+            .ipa_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| AppStoreConnectError::Custom("Invalid ipa file name".to_owned()))?;
+        let file_size = tokio::fs::metadata(task.ipa_path).await?.len();
+
+        // Резервируем файл и получаем операции выгрузки по частям
+        let upload_file = self
+            .reserve_upload_file(&build_upload_id, file_name, file_size)
+            .await
+            .tap_err(|err| {
+                error!("Upload file reserve failed: {}", err);
+            })?;
+
+        self.upload_parts(task.ipa_path, &upload_file.attributes.upload_operations)
+            .await
+            .tap_err(|err| {
+                error!("Parts uploading failed: {}", err);
+            })?;
+
+        self.commit_upload_file(&upload_file.id)
+            .await
+            .tap_err(|err| {
+                error!("Upload file commit failed: {}", err);
+            })?;
+
+        let build_upload = self
+            .wait_build_upload(&build_upload_id, task.processing_timeout)
+            .await
+            .tap_err(|err| {
+                error!("Build processing failed: {}", err);
+            })?;
+
+        let build_id = build_upload
+            .relationships
+            .and_then(|relationships| relationships.build)
+            .and_then(|build| build.data)
+            .map(|data| data.id);
+
+        Ok(AppStoreConnectUploadResult {
+            app_id,
+            ipa_info,
+            build_upload_id,
+            build_id,
+        })
+    }
+
+    async fn find_app_id(&self, bundle_id: &str) -> Result<String, AppStoreConnectError> {
+        // https://developer.apple.com/documentation/appstoreconnectapi/list_apps
+        let apps = self
+            .request_builder
+            .build_request(Method::GET, "apps")
+            .await?
+            .query(&[("filter[bundleId]", bundle_id), ("limit", "1")])
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<Vec<AppResource>>, AppStoreConnectError>(|data| {
+                debug!("Apps response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        apps.into_iter()
+            .next()
+            .map(|app| app.id)
+            .ok_or_else(|| AppStoreConnectError::AppNotFound(bundle_id.to_owned()))
+    }
+
+    async fn create_build_upload(
+        &self,
+        app_id: &str,
+        ipa_info: &IpaInfo,
+    ) -> Result<String, AppStoreConnectError> {
+        // https://developer.apple.com/documentation/appstoreconnectapi/create_a_build_upload
+        let body = json!({
+            "data": {
+                "type": "buildUploads",
+                "attributes": {
+                    "cfBundleShortVersionString": ipa_info.version,
+                    "cfBundleVersion": ipa_info.build_number,
+                    "platform": "IOS"
+                },
+                "relationships": {
+                    "app": {
+                        "data": {
+                            "type": "apps",
+                            "id": app_id
+                        }
+                    }
+                }
+            }
+        });
+
+        let build_upload = self
+            .request_builder
+            .build_request(Method::POST, "buildUploads")
+            .await?
+            .json(&body)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<BuildUploadResource>, AppStoreConnectError>(
+                |data| {
+                    debug!("Build upload create response: {}", data);
+                },
+            )
+            .await?
+            .into_result()?;
+
+        Ok(build_upload.id)
+    }
+
+    async fn reserve_upload_file(
+        &self,
+        build_upload_id: &str,
+        file_name: &str,
+        file_size: u64,
+    ) -> Result<BuildUploadFileResource, AppStoreConnectError> {
+        // https://developer.apple.com/documentation/appstoreconnectapi/create_a_build_upload_file
+        let body = json!({
+            "data": {
+                "type": "buildUploadFiles",
+                "attributes": {
+                    "assetType": "ASSET",
+                    "fileName": file_name,
+                    "fileSize": file_size,
+                    "uti": "com.apple.ipa"
+                },
+                "relationships": {
+                    "buildUpload": {
+                        "data": {
+                            "type": "buildUploads",
+                            "id": build_upload_id
+                        }
+                    }
+                }
+            }
+        });
+
+        let upload_file = self
+            .request_builder
+            .build_request(Method::POST, "buildUploadFiles")
+            .await?
+            .json(&body)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<BuildUploadFileResource>, AppStoreConnectError>(
+                |data| {
+                    debug!("Build upload file reserve response: {}", data);
+                },
+            )
+            .await?
+            .into_result()?;
+
+        debug!(
+            "Upload operations count: {}",
+            upload_file.attributes.upload_operations.len()
+        );
+
+        Ok(upload_file)
+    }
+
+    async fn upload_parts(
+        &self,
+        file_path: &Path,
+        operations: &[UploadOperation],
+    ) -> Result<(), AppStoreConnectError> {
+        const CONCURRENCY: usize = 4;
+
+        stream::iter(operations)
+            .map(|operation| self.upload_part_with_retries(file_path, operation))
+            .buffer_unordered(CONCURRENCY)
+            .try_collect::<Vec<()>>()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn upload_part_with_retries(
+        &self,
+        file_path: &Path,
+        operation: &UploadOperation,
+    ) -> Result<(), AppStoreConnectError> {
+        const RETRY_COUNT: u8 = 3;
+
+        // Часть файла читаем один раз, чтобы не перечитывать при повторах
+        let data = {
+            let mut file = File::open(file_path).await?;
+            file.seek(SeekFrom::Start(operation.offset)).await?;
+            let mut data = vec![0_u8; operation.length as usize];
+            file.read_exact(&mut data).await?;
+            data
+        };
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self.upload_part(operation, data.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt <= RETRY_COUNT => {
+                    warn!(
+                        "Part uploading failed, offset: {}, attempt: {}, err: {}",
+                        operation.offset, attempt, err
+                    );
+                    sleep(Duration::from_secs(5)).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    async fn upload_part(
+        &self,
+        operation: &UploadOperation,
+        data: Vec<u8>,
+    ) -> Result<(), AppStoreConnectError> {
+        let method = Method::from_bytes(operation.method.as_bytes()).map_err(|_| {
+            AppStoreConnectError::Custom(format!("Invalid upload method: {}", operation.method))
+        })?;
+
+        // Авторизация для этих запросов не нужна, все нужное уже есть в адресе и заголовках
+        let mut request = self
+            .request_builder
+            .get_http_client()
+            .request(method, operation.url.as_str());
+        for header in operation.request_headers.iter() {
+            request = request.header(header.name.as_str(), header.value.as_str());
+        }
+
+        let response = request.body(data).send().await?;
+        if !response.status().is_success() {
+            return Err(AppStoreConnectError::UploadOperationFailed {
+                offset: operation.offset,
+                code: response.status(),
+            });
+        }
+
+        debug!(
+            "Part uploaded, offset: {}, length: {}",
+            operation.offset, operation.length
+        );
+
+        Ok(())
+    }
+
+    async fn commit_upload_file(&self, upload_file_id: &str) -> Result<(), AppStoreConnectError> {
+        // https://developer.apple.com/documentation/appstoreconnectapi/modify_a_build_upload_file
+        let body = json!({
+            "data": {
+                "type": "buildUploadFiles",
+                "id": upload_file_id,
+                "attributes": {
+                    "uploaded": true
+                }
+            }
+        });
+
+        self.request_builder
+            .build_request(
+                Method::PATCH,
+                &format!("buildUploadFiles/{}", upload_file_id),
+            )
+            .await?
+            .json(&body)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<BuildUploadFileResource>, AppStoreConnectError>(
+                |data| {
+                    debug!("Build upload file commit response: {}", data);
+                },
+            )
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+
+    /// Ждем, пока App Store Connect закончит обработку выгруженного файла
+    async fn wait_build_upload(
+        &self,
+        build_upload_id: &str,
+        timeout: Duration,
+    ) -> Result<BuildUploadResource, AppStoreConnectError> {
+        // https://developer.apple.com/documentation/appstoreconnectapi/read_build_upload_information
+        const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let build_upload = self
+                .request_builder
+                .build_request(Method::GET, &format!("buildUploads/{}", build_upload_id))
+                .await?
+                .query(&[("include", "build")])
+                .send()
+                .await?
+                .inspect_json::<DataOrErrorResponse<BuildUploadResource>, AppStoreConnectError>(
+                    |data| {
+                        debug!("Build upload status response: {}", data);
+                    },
+                )
+                .await?
+                .into_result()?;
+
+            let state = build_upload
+                .attributes
+                .state
+                .as_ref()
+                .map(|state| state.state.clone())
+                .unwrap_or_default();
+            debug!("Build upload state: {}", state);
+
+            match state.as_str() {
+                "COMPLETE" => {
+                    info!("Build upload processing complete");
+                    return Ok(build_upload);
+                }
+                "FAILED" => {
+                    let messages = build_upload
+                        .attributes
+                        .state
+                        .into_iter()
+                        .flat_map(|state| state.errors)
+                        .map(|detail| {
+                            format!(
+                                "{}: {}",
+                                detail.code.unwrap_or_default(),
+                                detail.description.unwrap_or_default()
+                            )
+                        })
+                        .collect();
+                    return Err(AppStoreConnectError::BuildUploadFailed(messages));
+                }
+                _ => {}
+            }
+
+            if Instant::now() >= deadline {
+                return Err(AppStoreConnectError::ProcessingTimeout(state));
+            }
+
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Дожидаемся обработки билда и раздаем его тестировщикам в TestFlight
+    pub async fn distribute_to_testflight(
+        &self,
+        upload: &AppStoreConnectUploadResult,
+        task: AppStoreConnectTestFlightTask<'_>,
+    ) -> Result<AppStoreConnectTestFlightResult, AppStoreConnectError> {
+        let deadline = Instant::now() + task.processing_timeout;
+
+        // Билд может быть еще не привязан к выгрузке, тогда ищем его по версиям
+        let build_id = match upload.build_id.as_ref() {
+            Some(build_id) => build_id.clone(),
+            None => testflight::wait_build_appear(
+                &self.request_builder,
+                &upload.app_id,
+                &upload.ipa_info,
+                deadline,
+            )
+            .await
+            .tap_err(|err| {
+                error!("Build search failed: {}", err);
+            })?,
+        };
+        info!("TestFlight build id: {}", build_id);
+
+        testflight::wait_build_processing(&self.request_builder, &build_id, deadline)
+            .await
+            .tap_err(|err| {
+                error!("Build processing wait failed: {}", err);
+            })?;
+
+        for localization in task.what_to_test.iter() {
+            testflight::set_what_to_test(
+                &self.request_builder,
+                &build_id,
+                &localization.locale,
+                &localization.what_to_test,
+            )
+            .await
+            .tap_err(|err| {
+                error!(
+                    "What to test update failed for {}: {}",
+                    localization.locale, err
+                );
+            })?;
+        }
+
+        // Группы и тестировщиков сначала ищем все, чтобы не раздать билд частично
+        let mut groups = Vec::with_capacity(task.beta_groups.len());
+        for name in task.beta_groups.iter() {
+            let group = testflight::find_beta_group(&self.request_builder, &upload.app_id, name)
+                .await
+                .tap_err(|err| {
+                    error!("Beta group search failed: {}", err);
+                })?;
+            groups.push(group);
+        }
+
+        let mut testers = Vec::with_capacity(task.testers.len());
+        for email in task.testers.iter() {
+            let tester = testflight::find_beta_tester(&self.request_builder, email)
+                .await
+                .tap_err(|err| {
+                    error!("Beta tester search failed: {}", err);
+                })?;
+            testers.push(tester);
+        }
+
+        if !groups.is_empty() {
+            testflight::add_build_to_groups(&self.request_builder, &build_id, &groups)
+                .await
+                .tap_err(|err| {
+                    error!("Adding build to beta groups failed: {}", err);
+                })?;
+        }
+
+        if !testers.is_empty() {
+            testflight::add_build_to_testers(&self.request_builder, &build_id, &testers)
+                .await
+                .tap_err(|err| {
+                    error!("Adding build to testers failed: {}", err);
+                })?;
+        }
+
+        let beta_review_state = if task.submit_for_review {
+            testflight::submit_for_beta_review(&self.request_builder, &build_id)
+                .await
+                .tap_err(|err| {
+                    error!("Beta review submission failed: {}", err);
+                })?
+        } else {
+            None
+        };
+
+        let public_links = groups
+            .into_iter()
+            .filter(|group| group.attributes.public_link_enabled.unwrap_or(false))
+            .filter_map(|group| group.attributes.public_link)
+            .collect();
+
+        Ok(AppStoreConnectTestFlightResult {
+            build_url: format!(
+                "https://appstoreconnect.apple.com/apps/{}/testflight/ios/{}",
+                upload.app_id, build_id
+            ),
+            build_id,
+            public_links,
+            beta_review_state,
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    io,
+    error::{
+        Error
+    },
+    fmt::{
+        self,
+        Display,
+        Formatter
+    }
+};
+use reqwest::{
+    StatusCode
+};
+use url::{
+    ParseError
+};
+use crate::{
+    responses::{
+        ErrorsResponse
+    }
+};
+
+#[derive(Debug)]
+pub enum AppStoreConnectError{
+    URLError(ParseError),
+    NetErr(reqwest::Error),
+    JsonParseErr(serde_json::Error),
+    FileError(io::Error),
+    ZipError(zip::result::ZipError),
+    PlistError(plist::Error),
+    InvalidPrivateKey(String),
+    TokenSignFailed,
+    InfoPlistIsMissing,
+    InfoPlistValueIsMissing(&'static str),
+    AppNotFound(String),
+    UploadOperationFailed{ offset: u64, code: StatusCode },
+    BuildUploadFailed(Vec<String>),
+    ProcessingTimeout(String),
+    BuildNotFound{ version: String, build_number: String },
+    BuildProcessingFailed(String),
+    BetaGroupNotFound(String),
+    BetaTesterNotFound(String),
+    RequestFailedWithCode(StatusCode),
+    ApiError(ErrorsResponse),
+    Custom(String),
+}
+
+impl From<ParseError> for AppStoreConnectError {
+    fn from(err: ParseError) -> AppStoreConnectError {
+        AppStoreConnectError::URLError(err)
+    }
+}
+impl From<io::Error> for AppStoreConnectError {
+    fn from(err: io::Error) -> AppStoreConnectError {
+        AppStoreConnectError::FileError(err)
+    }
+}
+impl From<reqwest::Error> for AppStoreConnectError {
+    fn from(err: reqwest::Error) -> AppStoreConnectError {
+        AppStoreConnectError::NetErr(err)
+    }
+}
+impl From<serde_json::Error> for AppStoreConnectError {
+    fn from(err: serde_json::Error) -> AppStoreConnectError {
+        AppStoreConnectError::JsonParseErr(err)
+    }
+}
+impl From<zip::result::ZipError> for AppStoreConnectError {
+    fn from(err: zip::result::ZipError) -> AppStoreConnectError {
+        AppStoreConnectError::ZipError(err)
+    }
+}
+impl From<plist::Error> for AppStoreConnectError {
+    fn from(err: plist::Error) -> AppStoreConnectError {
+        AppStoreConnectError::PlistError(err)
+    }
+}
+impl From<ErrorsResponse> for AppStoreConnectError {
+    fn from(err: ErrorsResponse) -> AppStoreConnectError {
+        AppStoreConnectError::ApiError(err)
+    }
+}
+
+impl Display for AppStoreConnectError{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "App Store Connect error: {:#?}", self)
+    }
+}
+
+impl Error for AppStoreConnectError {
+}
@@ -0,0 +1,49 @@
+use crate::error::AppStoreConnectError;
+use std::{fs::File, io::Read, path::Path};
+
+/// Информация о приложении из Info.plist внутри .ipa
+#[derive(Debug, Clone)]
+pub struct IpaInfo {
+    pub bundle_id: String,
+    /// CFBundleShortVersionString
+    pub version: String,
+    /// CFBundleVersion
+    pub build_number: String,
+}
+
+/// Читаем Info.plist из `Payload/<имя>.app/Info.plist`, блокирующая функция
+pub fn read_ipa_info(ipa_path: &Path) -> Result<IpaInfo, AppStoreConnectError> {
+    let mut archive = zip::ZipArchive::new(File::open(ipa_path)?)?;
+
+    // Ищем Info.plist именно самого приложения, а не вложенных фреймворков
+    let plist_name = archive
+        .file_names()
+        .find(|name| {
+            let parts: Vec<&str> = name.split('/').collect();
+            matches!(parts.as_slice(), ["Payload", app, "Info.plist"] if app.ends_with(".app"))
+        })
+        .map(|name| name.to_owned())
+        .ok_or(AppStoreConnectError::InfoPlistIsMissing)?;
+
+    let mut data = Vec::new();
+    archive.by_name(&plist_name)?.read_to_end(&mut data)?;
+
+    // Plist может быть как бинарным, так и текстовым
+    let value = plist::Value::from_reader(std::io::Cursor::new(data))?;
+    let dict = value
+        .as_dictionary()
+        .ok_or(AppStoreConnectError::InfoPlistIsMissing)?;
+
+    let get_string = |key: &'static str| {
+        dict.get(key)
+            .and_then(|value| value.as_string())
+            .map(|value| value.to_owned())
+            .ok_or(AppStoreConnectError::InfoPlistValueIsMissing(key))
+    };
+
+    Ok(IpaInfo {
+        bundle_id: get_string("CFBundleIdentifier")?,
+        version: get_string("CFBundleShortVersionString")?,
+        build_number: get_string("CFBundleVersion")?,
+    })
+}
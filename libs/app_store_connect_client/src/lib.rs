@@ -0,0 +1,29 @@
+mod request_builder;
+mod responses;
+mod client;
+mod testflight;
+mod ipa_info;
+mod token;
+mod error;
+
+pub use self::{
+    token::{
+        AppStoreConnectKey,
+        AppStoreConnectTokenProvider
+    },
+    client::{
+        AppStoreConnectClient,
+        AppStoreConnectUploadTask,
+        AppStoreConnectUploadResult,
+        AppStoreConnectTestFlightTask,
+        AppStoreConnectTestFlightResult,
+        TestFlightLocalization
+    },
+    ipa_info::{
+        IpaInfo,
+        read_ipa_info
+    },
+    error::{
+        AppStoreConnectError
+    }
+};
@@ -0,0 +1,49 @@
+use super::{error::AppStoreConnectError, token::AppStoreConnectTokenProvider};
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub struct AppStoreConnectRequestBuilder {
+    http_client: Client,
+    api_url: Url,
+    token_provider: AppStoreConnectTokenProvider,
+}
+impl AppStoreConnectRequestBuilder {
+    pub fn new(
+        http_client: Client,
+        token_provider: AppStoreConnectTokenProvider,
+    ) -> AppStoreConnectRequestBuilder {
+        let api_url = Url::parse("https://api.appstoreconnect.apple.com/v1/")
+            .expect("App Store Connect base api URL parse failed");
+
+        AppStoreConnectRequestBuilder {
+            http_client,
+            api_url,
+            token_provider,
+        }
+    }
+
+    /// Возвращает сырой клиент без модификаций
+    pub fn get_http_client(&self) -> &Client {
+        &self.http_client
+    }
+
+    pub async fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, AppStoreConnectError> {
+        let full_url = self.api_url.join(path.trim_start_matches('/'))?;
+
+        // Получаем токен с пересозданием если надо
+        let token = self.token_provider.get_access_token().await?;
+
+        let builder = self
+            .http_client
+            .request(method, full_url.as_str())
+            .bearer_auth(token);
+
+        Ok(builder)
+    }
+}
@@ -0,0 +1,189 @@
+use serde::{
+    Deserialize
+};
+
+// Формат ответов JSON:API
+// https://developer.apple.com/documentation/appstoreconnectapi/interpreting_and_handling_errors
+
+#[derive(Deserialize, Debug)]
+pub struct DataResponse<D>{
+    pub data: D
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ResourceIdentifier{
+    pub id: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Relationship{
+    pub data: Option<ResourceIdentifier>
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://developer.apple.com/documentation/appstoreconnectapi/app
+#[derive(Deserialize, Debug)]
+pub struct AppResource{
+    pub id: String
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://developer.apple.com/documentation/appstoreconnectapi/builduploadfile
+#[derive(Deserialize, Debug)]
+pub struct HttpHeader{
+    pub name: String,
+    pub value: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadOperation{
+    pub method: String,
+    pub url: String,
+    pub length: u64,
+    pub offset: u64,
+    #[serde(default)]
+    pub request_headers: Vec<HttpHeader>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildUploadFileAttributes{
+    #[serde(default)]
+    pub upload_operations: Vec<UploadOperation>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildUploadFileResource{
+    pub id: String,
+    pub attributes: BuildUploadFileAttributes
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://developer.apple.com/documentation/appstoreconnectapi/buildupload
+#[derive(Deserialize, Debug)]
+pub struct StateDetail{
+    pub code: Option<String>,
+    pub description: Option<String>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildUploadState{
+    pub state: String,
+    #[serde(default)]
+    pub errors: Vec<StateDetail>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildUploadAttributes{
+    pub state: Option<BuildUploadState>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildUploadRelationships{
+    pub build: Option<Relationship>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildUploadResource{
+    pub id: String,
+    pub attributes: BuildUploadAttributes,
+    pub relationships: Option<BuildUploadRelationships>
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://developer.apple.com/documentation/appstoreconnectapi/build
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildAttributes{
+    pub processing_state: Option<String>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BuildResource{
+    pub id: String,
+    pub attributes: BuildAttributes
+}
+
+// https://developer.apple.com/documentation/appstoreconnectapi/betabuildlocalization
+#[derive(Deserialize, Debug)]
+pub struct BetaBuildLocalizationAttributes{
+    pub locale: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BetaBuildLocalizationResource{
+    pub id: String,
+    pub attributes: BetaBuildLocalizationAttributes
+}
+
+// https://developer.apple.com/documentation/appstoreconnectapi/betagroup
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BetaGroupAttributes{
+    pub name: String,
+    pub public_link_enabled: Option<bool>,
+    pub public_link: Option<String>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BetaGroupResource{
+    pub id: String,
+    pub attributes: BetaGroupAttributes
+}
+
+// https://developer.apple.com/documentation/appstoreconnectapi/betatester
+#[derive(Deserialize, Debug)]
+pub struct BetaTesterResource{
+    pub id: String
+}
+
+// https://developer.apple.com/documentation/appstoreconnectapi/betaappreviewsubmission
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BetaAppReviewSubmissionAttributes{
+    pub beta_review_state: Option<String>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BetaAppReviewSubmissionResource{
+    pub id: String,
+    pub attributes: BetaAppReviewSubmissionAttributes
+}
+
+//////////////////////////////////////////////////////////////////////
+
+/// Специальный шаблонный тип, чтобы можно было парсить возвращаемые ошибки в ответах
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DataOrErrorResponse<D>{
+    Ok(DataResponse<D>),
+    Err(ErrorsResponse)
+}
+impl<D> DataOrErrorResponse<D> {
+    pub fn into_result(self) -> Result<D, ErrorsResponse> {
+        match self {
+            DataOrErrorResponse::Ok(ok) => Ok(ok.data),
+            DataOrErrorResponse::Err(err) => Err(err),
+        }
+    }
+}
+
+/// Тип ошибки, в который мы можем парсить наши данные
+#[derive(Deserialize, Debug)]
+pub struct ErrorInfo{
+    pub status: String,
+    pub code: String,
+    pub title: String,
+    pub detail: Option<String>
+}
+
+/// Тип ошибки, в который мы можем парсить наши данные
+#[derive(Deserialize, Debug)]
+pub struct ErrorsResponse{
+    pub errors: Vec<ErrorInfo>
+}
@@ -0,0 +1,340 @@
+use super::{
+    error::AppStoreConnectError,
+    ipa_info::IpaInfo,
+    request_builder::AppStoreConnectRequestBuilder,
+    responses::{
+        BetaAppReviewSubmissionResource, BetaBuildLocalizationResource, BetaGroupResource,
+        BetaTesterResource, BuildResource, DataOrErrorResponse, ErrorsResponse,
+    },
+};
+use log::{debug, info};
+use reqwest::{Method, RequestBuilder};
+use reqwest_inspect_json::InspectJson;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+// Доки
+// https://developer.apple.com/documentation/appstoreconnectapi/prerelease_versions_and_beta_testers
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Выполняем запрос, в ответ на который приходит пустое тело
+async fn send_no_content(request: RequestBuilder) -> Result<(), AppStoreConnectError> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    // Пробуем распарсить описание ошибки, но оно может и отсутствовать
+    match response.json::<ErrorsResponse>().await {
+        Ok(err) => Err(AppStoreConnectError::ApiError(err)),
+        Err(_) => Err(AppStoreConnectError::RequestFailedWithCode(status)),
+    }
+}
+
+/// Ищем билд по версиям, билд появляется в списке не сразу после выгрузки
+pub(crate) async fn wait_build_appear(
+    request_builder: &AppStoreConnectRequestBuilder,
+    app_id: &str,
+    ipa_info: &IpaInfo,
+    deadline: Instant,
+) -> Result<String, AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/list_builds
+    loop {
+        let builds = request_builder
+            .build_request(Method::GET, "builds")
+            .await?
+            .query(&[
+                ("filter[app]", app_id),
+                ("filter[version]", ipa_info.build_number.as_str()),
+                (
+                    "filter[preReleaseVersion.version]",
+                    ipa_info.version.as_str(),
+                ),
+                ("limit", "1"),
+            ])
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<Vec<BuildResource>>, AppStoreConnectError>(|data| {
+                debug!("Builds response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        if let Some(build) = builds.into_iter().next() {
+            return Ok(build.id);
+        }
+
+        if Instant::now() >= deadline {
+            return Err(AppStoreConnectError::BuildNotFound {
+                version: ipa_info.version.clone(),
+                build_number: ipa_info.build_number.clone(),
+            });
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Ждем, пока билд пройдет обработку и станет доступен для тестирования
+pub(crate) async fn wait_build_processing(
+    request_builder: &AppStoreConnectRequestBuilder,
+    build_id: &str,
+    deadline: Instant,
+) -> Result<(), AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/read_build_information
+    loop {
+        let build = request_builder
+            .build_request(Method::GET, &format!("builds/{}", build_id))
+            .await?
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<BuildResource>, AppStoreConnectError>(|data| {
+                debug!("Build response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        let state = build.attributes.processing_state.unwrap_or_default();
+        debug!("Build processing state: {}", state);
+
+        match state.as_str() {
+            "VALID" => {
+                info!("Build processing finished");
+                return Ok(());
+            }
+            "FAILED" | "INVALID" => {
+                return Err(AppStoreConnectError::BuildProcessingFailed(state));
+            }
+            _ => {}
+        }
+
+        if Instant::now() >= deadline {
+            return Err(AppStoreConnectError::ProcessingTimeout(state));
+        }
+
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Выставляем текст "What to Test" для локали, создавая локализацию при необходимости
+pub(crate) async fn set_what_to_test(
+    request_builder: &AppStoreConnectRequestBuilder,
+    build_id: &str,
+    locale: &str,
+    text: &str,
+) -> Result<(), AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/list_all_beta_build_localizations_of_a_build
+    let localizations = request_builder
+        .build_request(
+            Method::GET,
+            &format!("builds/{}/betaBuildLocalizations", build_id),
+        )
+        .await?
+        .send()
+        .await?
+        .inspect_json::<DataOrErrorResponse<Vec<BetaBuildLocalizationResource>>, AppStoreConnectError>(
+            |data| {
+                debug!("Beta build localizations response: {}", data);
+            },
+        )
+        .await?
+        .into_result()?;
+
+    let existing = localizations
+        .into_iter()
+        .find(|localization| localization.attributes.locale.eq(locale));
+
+    let request = match existing {
+        Some(localization) => {
+            // https://developer.apple.com/documentation/appstoreconnectapi/modify_a_beta_build_localization
+            let body = json!({
+                "data": {
+                    "type": "betaBuildLocalizations",
+                    "id": localization.id,
+                    "attributes": {
+                        "whatsNew": text
+                    }
+                }
+            });
+            request_builder
+                .build_request(
+                    Method::PATCH,
+                    &format!("betaBuildLocalizations/{}", localization.id),
+                )
+                .await?
+                .json(&body)
+        }
+        None => {
+            // https://developer.apple.com/documentation/appstoreconnectapi/create_a_beta_build_localization
+            let body = json!({
+                "data": {
+                    "type": "betaBuildLocalizations",
+                    "attributes": {
+                        "locale": locale,
+                        "whatsNew": text
+                    },
+                    "relationships": {
+                        "build": {
+                            "data": {
+                                "type": "builds",
+                                "id": build_id
+                            }
+                        }
+                    }
+                }
+            });
+            request_builder
+                .build_request(Method::POST, "betaBuildLocalizations")
+                .await?
+                .json(&body)
+        }
+    };
+
+    request
+        .send()
+        .await?
+        .inspect_json::<DataOrErrorResponse<BetaBuildLocalizationResource>, AppStoreConnectError>(
+            |data| {
+                debug!("Beta build localization update response: {}", data);
+            },
+        )
+        .await?
+        .into_result()?;
+
+    Ok(())
+}
+
+pub(crate) async fn find_beta_group(
+    request_builder: &AppStoreConnectRequestBuilder,
+    app_id: &str,
+    name: &str,
+) -> Result<BetaGroupResource, AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/list_beta_groups
+    let groups = request_builder
+        .build_request(Method::GET, "betaGroups")
+        .await?
+        .query(&[("filter[app]", app_id), ("filter[name]", name)])
+        .send()
+        .await?
+        .inspect_json::<DataOrErrorResponse<Vec<BetaGroupResource>>, AppStoreConnectError>(|data| {
+            debug!("Beta groups response: {}", data);
+        })
+        .await?
+        .into_result()?;
+
+    // Фильтр по имени не строгий, поэтому проверяем совпадение сами
+    groups
+        .into_iter()
+        .find(|group| group.attributes.name.eq(name))
+        .ok_or_else(|| AppStoreConnectError::BetaGroupNotFound(name.to_owned()))
+}
+
+pub(crate) async fn add_build_to_groups(
+    request_builder: &AppStoreConnectRequestBuilder,
+    build_id: &str,
+    groups: &[BetaGroupResource],
+) -> Result<(), AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/add_access_for_beta_groups_to_a_build
+    let data: Vec<_> = groups
+        .iter()
+        .map(|group| json!({"type": "betaGroups", "id": group.id}))
+        .collect();
+
+    let request = request_builder
+        .build_request(
+            Method::POST,
+            &format!("builds/{}/relationships/betaGroups", build_id),
+        )
+        .await?
+        .json(&json!({ "data": data }));
+
+    send_no_content(request).await
+}
+
+pub(crate) async fn find_beta_tester(
+    request_builder: &AppStoreConnectRequestBuilder,
+    email: &str,
+) -> Result<BetaTesterResource, AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/list_beta_testers
+    let testers = request_builder
+        .build_request(Method::GET, "betaTesters")
+        .await?
+        .query(&[("filter[email]", email), ("limit", "1")])
+        .send()
+        .await?
+        .inspect_json::<DataOrErrorResponse<Vec<BetaTesterResource>>, AppStoreConnectError>(
+            |data| {
+                debug!("Beta testers response: {}", data);
+            },
+        )
+        .await?
+        .into_result()?;
+
+    testers
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppStoreConnectError::BetaTesterNotFound(email.to_owned()))
+}
+
+pub(crate) async fn add_build_to_testers(
+    request_builder: &AppStoreConnectRequestBuilder,
+    build_id: &str,
+    testers: &[BetaTesterResource],
+) -> Result<(), AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/assign_individual_testers_to_a_build
+    let data: Vec<_> = testers
+        .iter()
+        .map(|tester| json!({"type": "betaTesters", "id": tester.id}))
+        .collect();
+
+    let request = request_builder
+        .build_request(
+            Method::POST,
+            &format!("builds/{}/relationships/individualTesters", build_id),
+        )
+        .await?
+        .json(&json!({ "data": data }));
+
+    send_no_content(request).await
+}
+
+pub(crate) async fn submit_for_beta_review(
+    request_builder: &AppStoreConnectRequestBuilder,
+    build_id: &str,
+) -> Result<Option<String>, AppStoreConnectError> {
+    // https://developer.apple.com/documentation/appstoreconnectapi/submit_an_app_for_beta_review
+    let body = json!({
+        "data": {
+            "type": "betaAppReviewSubmissions",
+            "relationships": {
+                "build": {
+                    "data": {
+                        "type": "builds",
+                        "id": build_id
+                    }
+                }
+            }
+        }
+    });
+
+    let submission = request_builder
+        .build_request(Method::POST, "betaAppReviewSubmissions")
+        .await?
+        .json(&body)
+        .send()
+        .await?
+        .inspect_json::<DataOrErrorResponse<BetaAppReviewSubmissionResource>, AppStoreConnectError>(
+            |data| {
+                debug!("Beta review submission response: {}", data);
+            },
+        )
+        .await?
+        .into_result()?;
+
+    debug!("Beta review submission id: {}", submission.id);
+
+    Ok(submission.attributes.beta_review_state)
+}
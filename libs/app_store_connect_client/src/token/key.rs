@@ -0,0 +1,95 @@
+use crate::error::AppStoreConnectError;
+use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+};
+use serde_json::json;
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// https://developer.apple.com/documentation/appstoreconnectapi/generating_tokens_for_api_requests
+
+/// Ключ App Store Connect API, которым подписываются JWT токены
+pub struct AppStoreConnectKey {
+    issuer_id: String,
+    key_id: String,
+    key_pair: EcdsaKeyPair,
+}
+
+impl Debug for AppStoreConnectKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Приватный ключ в логи не выводим
+        f.debug_struct("AppStoreConnectKey")
+            .field("issuer_id", &self.issuer_id)
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+impl AppStoreConnectKey {
+    /// Создаем ключ из содержимого .p8 файла в формате PEM
+    pub fn from_pem(
+        issuer_id: String,
+        key_id: String,
+        pem: &str,
+    ) -> Result<AppStoreConnectKey, AppStoreConnectError> {
+        // Выкидываем заголовки PEM, оставляя только base64 тело ключа
+        let body: String = pem
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+            .collect();
+
+        let der = base64::decode(&body)
+            .map_err(|err| AppStoreConnectError::InvalidPrivateKey(err.to_string()))?;
+
+        let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &der)
+            .map_err(|err| AppStoreConnectError::InvalidPrivateKey(err.to_string()))?;
+
+        Ok(AppStoreConnectKey {
+            issuer_id,
+            key_id,
+            key_pair,
+        })
+    }
+
+    /// Создаем подписанный ES256 токен с указанным временем жизни
+    pub(crate) fn create_token(&self, lifetime: Duration) -> Result<String, AppStoreConnectError> {
+        let issued_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| AppStoreConnectError::TokenSignFailed)?
+            .as_secs();
+
+        let header = json!({
+            "alg": "ES256",
+            "kid": self.key_id,
+            "typ": "JWT"
+        });
+        let claims = json!({
+            "iss": self.issuer_id,
+            "iat": issued_at,
+            "exp": issued_at + lifetime.as_secs(),
+            "aud": "appstoreconnect-v1"
+        });
+
+        let message = format!(
+            "{}.{}",
+            base64::encode_config(header.to_string(), base64::URL_SAFE_NO_PAD),
+            base64::encode_config(claims.to_string(), base64::URL_SAFE_NO_PAD)
+        );
+
+        // Подпись в формате r||s, как того требует JWS
+        let signature = self
+            .key_pair
+            .sign(&SystemRandom::new(), message.as_bytes())
+            .map_err(|_| AppStoreConnectError::TokenSignFailed)?;
+
+        Ok(format!(
+            "{}.{}",
+            message,
+            base64::encode_config(signature.as_ref(), base64::URL_SAFE_NO_PAD)
+        ))
+    }
+}
@@ -0,0 +1,4 @@
+mod key;
+mod provider;
+
+pub use self::{key::AppStoreConnectKey, provider::AppStoreConnectTokenProvider};
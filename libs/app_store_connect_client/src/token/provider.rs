@@ -0,0 +1,46 @@
+use super::key::AppStoreConnectKey;
+use crate::error::AppStoreConnectError;
+use std::{future::ready, sync::Arc, time::Duration};
+use token_provider::{FetchedToken, TokenProvider};
+
+////////////////////////////////////////////////////////////////
+
+/// Провайдер токенов App Store Connect, создает новый токен заранее перед истечением текущего
+#[derive(Debug, Clone)]
+pub struct AppStoreConnectTokenProvider {
+    inner: TokenProvider<AppStoreConnectError>,
+}
+
+impl AppStoreConnectTokenProvider {
+    pub fn new(key: AppStoreConnectKey) -> AppStoreConnectTokenProvider {
+        // Apple не принимает токены, живущие дольше 20 минут
+        Self::new_custom(
+            key,
+            Duration::from_secs(60 * 20),
+            Duration::from_secs(60 * 3),
+        )
+    }
+
+    pub fn new_custom(
+        key: AppStoreConnectKey,
+        token_lifetime: Duration,
+        token_expire_pre_delay: Duration,
+    ) -> AppStoreConnectTokenProvider {
+        let key = Arc::new(key);
+
+        // Токен подписывается локально, поэтому запрос завершается сразу
+        let inner = TokenProvider::new("App Store Connect", token_expire_pre_delay, move || {
+            let token = key.create_token(token_lifetime).map(|value| FetchedToken {
+                value,
+                lifetime: token_lifetime,
+            });
+            ready(token)
+        });
+
+        AppStoreConnectTokenProvider { inner }
+    }
+
+    pub async fn get_access_token(&self) -> Result<Arc<String>, AppStoreConnectError> {
+        self.inner.get_access_token().await
+    }
+}
@@ -0,0 +1,52 @@
+use app_store_connect_client::{
+    AppStoreConnectClient, AppStoreConnectKey, AppStoreConnectTokenProvider,
+    AppStoreConnectUploadTask,
+};
+use log::debug;
+use reqwest::Client;
+use std::{path::Path, sync::Once, time::Duration};
+
+fn setup_logs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        if std::env::var("RUST_LOG").is_err() {
+            std::env::set_var("RUST_LOG", "debug");
+        }
+        env_logger::init();
+    })
+}
+
+#[tokio::test]
+async fn library_integration_test() {
+    setup_logs();
+
+    let issuer_id = std::env::var("IOS_API_ISSUER_ID")
+        .expect("IOS_API_ISSUER_ID environment variable is missing");
+
+    let key_id =
+        std::env::var("IOS_API_KEY_ID").expect("IOS_API_KEY_ID environment variable is missing");
+
+    let key_file = std::env::var("IOS_API_KEY_FILE")
+        .expect("IOS_API_KEY_FILE environment variable is missing");
+
+    let ipa_file =
+        std::env::var("IOS_IPA_FILE").expect("IOS_IPA_FILE environment variable is missing");
+
+    let pem = std::fs::read_to_string(key_file).expect("Key file read failed");
+    let key = AppStoreConnectKey::from_pem(issuer_id, key_id, &pem).expect("Invalid key");
+    let token_provider = AppStoreConnectTokenProvider::new(key);
+
+    let token = token_provider
+        .get_access_token()
+        .await
+        .expect("Token create failed");
+    debug!("Token: {}", token);
+
+    let client = AppStoreConnectClient::new(Client::new(), token_provider);
+    let task = AppStoreConnectUploadTask {
+        ipa_path: Path::new(&ipa_file),
+        processing_timeout: Duration::from_secs(60 * 30),
+    };
+    let result = client.upload(task).await.expect("Uploading failed");
+    debug!("Result: {:#?}", result);
+}
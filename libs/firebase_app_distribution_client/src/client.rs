@@ -0,0 +1,270 @@
+use super::{
+    error::FirebaseAppDistributionError,
+    responses::{
+        DataOrErrorResponse, ErrorResponse, FirebaseRelease, FirebaseUploadAction,
+        OperationResponse, UploadReleaseResponse,
+    },
+};
+use log::{debug, info};
+use reqwest::{Body, Client, Method, RequestBuilder};
+use reqwest_inspect_json::InspectJson;
+use serde_json::json;
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::fs::File;
+use tokio_util::codec::{BytesCodec, FramedRead};
+use url::Url;
+use yup_oauth2::AccessToken;
+
+// https://firebase.google.com/docs/reference/app-distribution/rest
+
+/// Пауза между запросами статуса обработки сборки
+const OPERATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FirebaseUploadTask<'a> {
+    /// Файлик .apk, .aab или .ipa
+    pub file_path: &'a Path,
+    pub release_notes: Option<&'a str>,
+    pub tester_emails: &'a [String],
+    /// Алиасы групп тестировщиков
+    pub group_aliases: &'a [String],
+    /// Сколько ждем окончания обработки сборки
+    pub processing_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct FirebaseUploadResult {
+    pub action: FirebaseUploadAction,
+    pub release: FirebaseRelease,
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct FirebaseAppDistributionClient {
+    http_client: Client,
+    base_url: Url,
+    token: Arc<AccessToken>,
+    project_number: String,
+    app_id: String,
+}
+impl FirebaseAppDistributionClient {
+    /// Идентификатор приложения имеет вид `1:<номер проекта>:android:<хеш>`,
+    /// номер проекта берем из него
+    pub fn new(
+        http_client: Client,
+        token: AccessToken,
+        app_id: String,
+    ) -> Result<FirebaseAppDistributionClient, FirebaseAppDistributionError> {
+        let project_number = app_id
+            .split(':')
+            .nth(1)
+            .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| FirebaseAppDistributionError::InvalidAppId(app_id.clone()))?
+            .to_owned();
+
+        info!("Firebase app distribution client created");
+
+        Ok(FirebaseAppDistributionClient {
+            http_client,
+            base_url: Url::parse("https://firebaseappdistribution.googleapis.com/")?,
+            token: Arc::new(token),
+            project_number,
+            app_id,
+        })
+    }
+
+    /// Путь указывается относительно корня API вместе с версией
+    fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, FirebaseAppDistributionError> {
+        if self.token.is_expired() {
+            return Err(FirebaseAppDistributionError::TokenIsExpired);
+        }
+        let url = self.base_url.join(path)?;
+        debug!("Firebase request: {} {}", method, url);
+
+        Ok(self
+            .http_client
+            .request(method, url)
+            .bearer_auth(self.token.as_str()))
+    }
+
+    /// Выгружаем файлик, в ответ получаем операцию его обработки
+    async fn upload_binary(
+        &self,
+        file_path: &Path,
+    ) -> Result<OperationResponse, FirebaseAppDistributionError> {
+        // https://firebase.google.com/docs/reference/app-distribution/rest/v1/media/upload
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                FirebaseAppDistributionError::Custom(format!("Invalid file path: {:?}", file_path))
+            })?;
+
+        let file = File::open(file_path).await?;
+        let file_length = file.metadata().await?.len();
+        let body = Body::wrap_stream(FramedRead::new(file, BytesCodec::new()));
+
+        let path = format!(
+            "upload/v1/projects/{}/apps/{}/releases:upload",
+            self.project_number, self.app_id
+        );
+        let operation = self
+            .build_request(Method::POST, &path)?
+            .header("X-Goog-Upload-Protocol", "raw")
+            .header("X-Goog-Upload-File-Name", file_name)
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header(reqwest::header::CONTENT_LENGTH, file_length)
+            .body(body)
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<OperationResponse>, FirebaseAppDistributionError>(
+                |v| {
+                    debug!("{:?}", v);
+                },
+            )
+            .await?
+            .into_result()?;
+
+        Ok(operation)
+    }
+
+    /// Ждем окончания обработки сборки, для .aab это может занимать несколько минут
+    async fn wait_operation(
+        &self,
+        mut operation: OperationResponse,
+        timeout: Duration,
+    ) -> Result<UploadReleaseResponse, FirebaseAppDistributionError> {
+        // https://firebase.google.com/docs/reference/app-distribution/rest/v1/projects.apps.releases.operations/get
+        let start = Instant::now();
+        loop {
+            if operation.done {
+                if let Some(err) = operation.error {
+                    return Err(err.into());
+                }
+                return operation.response.ok_or_else(|| {
+                    FirebaseAppDistributionError::Custom(
+                        "Finished operation has no response".to_owned(),
+                    )
+                });
+            }
+
+            if start.elapsed() > timeout {
+                return Err(FirebaseAppDistributionError::ProcessingTimeout(
+                    operation.name,
+                ));
+            }
+            tokio::time::sleep(OPERATION_POLL_INTERVAL).await;
+
+            operation = self
+                .build_request(Method::GET, &format!("v1/{}", operation.name))?
+                .send()
+                .await?
+                .inspect_json::<DataOrErrorResponse<OperationResponse>, FirebaseAppDistributionError>(
+                    |v| {
+                        debug!("{:?}", v);
+                    },
+                )
+                .await?
+                .into_result()?;
+        }
+    }
+
+    async fn update_release_notes(
+        &self,
+        release: &FirebaseRelease,
+        text: &str,
+    ) -> Result<FirebaseRelease, FirebaseAppDistributionError> {
+        // https://firebase.google.com/docs/reference/app-distribution/rest/v1/projects.apps.releases/patch
+        let release = self
+            .build_request(
+                Method::PATCH,
+                &format!("v1/{}?updateMask=release_notes.text", release.name),
+            )?
+            .json(&json!({
+                "name": release.name,
+                "releaseNotes": {
+                    "text": text
+                }
+            }))
+            .send()
+            .await?
+            .inspect_json::<DataOrErrorResponse<FirebaseRelease>, FirebaseAppDistributionError>(
+                |v| {
+                    debug!("{:?}", v);
+                },
+            )
+            .await?
+            .into_result()?;
+
+        Ok(release)
+    }
+
+    async fn distribute(
+        &self,
+        release: &FirebaseRelease,
+        tester_emails: &[String],
+        group_aliases: &[String],
+    ) -> Result<(), FirebaseAppDistributionError> {
+        // https://firebase.google.com/docs/reference/app-distribution/rest/v1/projects.apps.releases/distribute
+        let response = self
+            .build_request(Method::POST, &format!("v1/{}:distribute", release.name))?
+            .json(&json!({
+                "testerEmails": tester_emails,
+                "groupAliases": group_aliases
+            }))
+            .send()
+            .await?;
+
+        // В случае успеха возвращается пустой объект, так что смотрим лишь на статус
+        if !response.status().is_success() {
+            let err = response.json::<ErrorResponse>().await?;
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn upload(
+        &self,
+        task: FirebaseUploadTask<'_>,
+    ) -> Result<FirebaseUploadResult, FirebaseAppDistributionError> {
+        info!("Firebase app distribution uploading started");
+
+        // Выгрузка
+        let operation = self.upload_binary(task.file_path).await?;
+        debug!("Firebase upload operation: {}", operation.name);
+
+        // Ожидание обработки
+        let UploadReleaseResponse {
+            result: action,
+            release,
+        } = self
+            .wait_operation(operation, task.processing_timeout)
+            .await?;
+        info!("Firebase release {}: {}", action, release.name);
+
+        // Описание релиза
+        let release = match task.release_notes {
+            Some(text) => self.update_release_notes(&release, text).await?,
+            None => release,
+        };
+
+        // Раздача тестировщикам
+        if !task.tester_emails.is_empty() || !task.group_aliases.is_empty() {
+            self.distribute(&release, task.tester_emails, task.group_aliases)
+                .await?;
+            info!("Firebase release distributed");
+        }
+
+        Ok(FirebaseUploadResult { action, release })
+    }
+}
@@ -0,0 +1,54 @@
+use super::responses::{ErrorResponse, OperationError};
+use quick_error::quick_error;
+use std::io;
+use url::ParseError;
+
+quick_error! {
+    #[derive(Debug)]
+    pub enum FirebaseAppDistributionError {
+        URLError(err: ParseError){
+            from()
+            display("{}", err)
+        }
+
+        NetErr(err: reqwest::Error){
+            from()
+            display("{}", err)
+        }
+
+        JsonParseErr(err: serde_json::Error){
+            from()
+            display("{}", err)
+        }
+
+        FileError(err: io::Error){
+            from()
+            display("{}", err)
+        }
+
+        InvalidAppId(app_id: String){
+            display("Invalid firebase app id '{}', expected format is 1:<project number>:<platform>:<hash>", app_id)
+        }
+
+        TokenIsExpired{
+        }
+
+        ResponseError(err: ErrorResponse){
+            from()
+            display("{:?}", err)
+        }
+
+        OperationFailed(err: OperationError){
+            from()
+            display("Release processing failed: {:?}", err)
+        }
+
+        ProcessingTimeout(operation: String){
+            display("Release processing timeout, operation: {}", operation)
+        }
+
+        Custom(err: String){
+            display("{}", err)
+        }
+    }
+}
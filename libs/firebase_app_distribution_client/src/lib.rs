@@ -0,0 +1,9 @@
+mod client;
+mod error;
+mod responses;
+
+pub use self::{
+    client::{FirebaseAppDistributionClient, FirebaseUploadResult, FirebaseUploadTask},
+    error::FirebaseAppDistributionError,
+    responses::{FirebaseRelease, FirebaseUploadAction},
+};
@@ -0,0 +1,134 @@
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Deserialize, Debug)]
+pub struct ErrorResponseValue {
+    pub message: String,
+    pub status: String,
+}
+#[derive(Deserialize, Debug)]
+pub struct ErrorResponse {
+    pub error: ErrorResponseValue,
+}
+
+//////////////////////////////////////////////////////////////////////
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum DataOrErrorResponse<D> {
+    Ok(D),
+    Err(ErrorResponse),
+}
+impl<D> DataOrErrorResponse<D> {
+    pub fn into_result(self) -> Result<D, ErrorResponse> {
+        match self {
+            DataOrErrorResponse::Ok(ok) => Ok(ok),
+            DataOrErrorResponse::Err(err) => Err(err),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://firebase.google.com/docs/reference/app-distribution/rest/v1/projects.apps.releases#Release
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FirebaseRelease {
+    /// Имя вида `projects/<номер>/apps/<id>/releases/<id>`
+    pub name: String,
+    pub display_version: Option<String>,
+    pub build_version: Option<String>,
+    pub create_time: Option<String>,
+    /// Ссылка на релиз в консоли Firebase
+    pub firebase_console_uri: Option<String>,
+    /// Ссылка для тестировщиков, по ней можно установить сборку
+    pub testing_uri: Option<String>,
+}
+
+/// Что произошло с релизом после выгрузки, повторная выгрузка того же файла релиз не создает
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirebaseUploadAction {
+    #[serde(rename = "RELEASE_CREATED")]
+    Created,
+    #[serde(rename = "RELEASE_UPDATED")]
+    Updated,
+    #[serde(rename = "RELEASE_UNMODIFIED")]
+    Unmodified,
+    #[serde(other)]
+    Unspecified,
+}
+impl Display for FirebaseUploadAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FirebaseUploadAction::Created => write!(f, "created"),
+            FirebaseUploadAction::Updated => write!(f, "updated"),
+            FirebaseUploadAction::Unmodified => write!(f, "unmodified"),
+            FirebaseUploadAction::Unspecified => write!(f, "unspecified"),
+        }
+    }
+}
+
+// https://firebase.google.com/docs/reference/app-distribution/rest/v1/UploadReleaseResponse
+#[derive(Deserialize, Debug)]
+pub struct UploadReleaseResponse {
+    pub result: FirebaseUploadAction,
+    pub release: FirebaseRelease,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OperationError {
+    pub code: Option<i64>,
+    pub message: Option<String>,
+}
+
+// https://firebase.google.com/docs/reference/app-distribution/rest/v1/projects.apps.releases.operations#Operation
+#[derive(Deserialize, Debug)]
+pub struct OperationResponse {
+    pub name: String,
+    #[serde(default)]
+    pub done: bool,
+    pub response: Option<UploadReleaseResponse>,
+    pub error: Option<OperationError>,
+}
+
+//////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_parse() {
+        let json = r#"{
+            "name": "projects/123/apps/1:123:android:abc/releases/-/operations/op",
+            "done": true,
+            "response": {
+                "@type": "type.googleapis.com/google.firebase.appdistro.v1.UploadReleaseResponse",
+                "result": "RELEASE_CREATED",
+                "release": {
+                    "name": "projects/123/apps/1:123:android:abc/releases/rel",
+                    "displayVersion": "1.2.3",
+                    "buildVersion": "45",
+                    "firebaseConsoleUri": "https://console.firebase.google.com/project/test",
+                    "testingUri": "https://appdistribution.firebase.google.com/testerapps/rel"
+                }
+            }
+        }"#;
+        let operation = serde_json::from_str::<DataOrErrorResponse<OperationResponse>>(json)
+            .expect("Parse failed")
+            .into_result()
+            .expect("Error response");
+        assert!(operation.done);
+        let response = operation.response.expect("Response is missing");
+        assert_eq!(response.result, FirebaseUploadAction::Created);
+        assert_eq!(response.release.build_version.as_deref(), Some("45"));
+
+        let json = r#"{"name": "op", "response": {"result": "SOMETHING_NEW", "release": {"name": "rel"}}}"#;
+        let operation = serde_json::from_str::<OperationResponse>(json).expect("Parse failed");
+        assert!(!operation.done);
+        assert_eq!(
+            operation.response.expect("Response is missing").result,
+            FirebaseUploadAction::Unspecified
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use firebase_app_distribution_client::{FirebaseAppDistributionClient, FirebaseUploadTask};
+use log::info;
+use reqwest::Client;
+use std::{env, path::PathBuf, sync::Once, time::Duration};
+
+fn setup_logs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        if std::env::var("RUST_LOG").is_err() {
+            std::env::set_var("RUST_LOG", "debug");
+        }
+        env_logger::init();
+    })
+}
+
+// Запуск с реальным приложением:
+// FIREBASE_APP_ID="1:<номер проекта>:android:<хеш>" FIREBASE_TEST_FILE="app.apk" cargo test -- --ignored
+#[tokio::test]
+#[ignore]
+async fn library_integration_test() {
+    setup_logs();
+
+    let app_id = env::var("FIREBASE_APP_ID").expect("Missing env variable");
+    let file_path = PathBuf::from(env::var("FIREBASE_TEST_FILE").expect("Missing env variable"));
+
+    let key = yup_oauth2::read_service_account_key("env/firebase_credentials.json")
+        .await
+        .expect("Auth info parse failed");
+
+    let auth = yup_oauth2::ServiceAccountAuthenticator::builder(key)
+        .build()
+        .await
+        .expect("Authentification failed");
+
+    let token = auth
+        .token(&["https://www.googleapis.com/auth/cloud-platform"])
+        .await
+        .expect("Token receive failed");
+
+    let client = FirebaseAppDistributionClient::new(Client::new(), token, app_id)
+        .expect("Client create failed");
+
+    let task = FirebaseUploadTask {
+        file_path: &file_path,
+        release_notes: Some("Integration test build"),
+        tester_emails: &[],
+        group_aliases: &[],
+        processing_timeout: Duration::from_secs(10 * 60),
+    };
+    let result = client.upload(task).await.expect("Firebase upload failed");
+
+    info!("Firebase upload result: {:?}", result);
+    assert!(result.release.firebase_console_uri.is_some());
+}
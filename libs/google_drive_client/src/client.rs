@@ -1,8 +1,10 @@
 use super::{
+    conflict::{GoogleDriveConflictPolicy, GoogleDriveUploadAction},
     error::GoogleDriveError,
-    file::{DomainFileOwner, EmailFileOwner, GoogleDriveFile},
-    folder::GoogleDriveFolder,
-    helpers::get_files_list_with_query,
+    file::GoogleDriveFile,
+    folder::{GoogleDriveFolder, FOLDER_MIME_TYPE},
+    helpers::file_stream_body,
+    permission::GoogleDrivePermission,
     request_builder::GoogleDriveRequestBuilder,
     responses::*,
 };
@@ -10,13 +12,11 @@ use into_result::IntoResult;
 use log::{debug, info, trace};
 use reqwest::{
     multipart::{Form, Part},
-    Body, Client, Method,
+    Client, Method,
 };
 use reqwest_inspect_json::InspectJson;
 use serde_json::json;
 use std::path::{Path, PathBuf};
-use tokio::fs::File;
-use tokio_util::codec::{BytesCodec, FramedRead};
 use yup_oauth2::AccessToken;
 
 //////////////////////////////////////////////////////////////////////////////////////////
@@ -25,15 +25,19 @@ use yup_oauth2::AccessToken;
 pub struct GoogleDriveUploadTask<'a> {
     pub file_path: PathBuf,
     pub parent_folder: &'a GoogleDriveFolder,
-    pub owner_email: Option<&'a str>,
-    pub owner_domain: Option<&'a str>,
+    /// Разрешения, которые выдаются на каждый выгруженный файл
+    pub permissions: &'a [GoogleDrivePermission],
+    pub send_notification_email: bool,
+    pub on_conflict: Option<GoogleDriveConflictPolicy>,
 }
 
 #[derive(Debug)]
 pub struct GoogleDriveUploadResult {
+    pub file_id: String,
     pub file_name: String,
     pub web_view_link: String,
     pub web_content_link: String,
+    pub action: GoogleDriveUploadAction,
 }
 
 //////////////////////////////////////////////////////////////////////////////////////////
@@ -55,31 +59,14 @@ impl GoogleDriveClient {
         &self,
         parent_folder: &GoogleDriveFolder,
         file_path: &Path,
+        file_name: &str,
     ) -> Result<GoogleDriveFile, GoogleDriveError> {
         // https://developers.google.com/drive/api/v3/reference/files/create
         // https://developers.google.com/drive/api/v3/manage-uploads
 
-        let file_name = file_path
-            .file_name()
-            .ok_or(GoogleDriveError::WrongFilePath)?
-            .to_str()
-            .ok_or(GoogleDriveError::WrongFilePath)?;
         debug!("File name: {}", file_name);
 
-        // let mut total_uploaded = 0;
-        // let file_name_stream = file_name.to_owned();
-
-        let file = File::open(file_path).await?;
-        let file_length = file.metadata().await?.len();
-        let reader = FramedRead::new(file, BytesCodec::new())
-            /*.map(move |v| {
-                if let Ok(ref v) = v{
-                    total_uploaded += v.len();
-                    info!("Uploaded {}: {}/{}", file_name_stream, total_uploaded, file_length);
-                }
-                v
-            })*/;
-        let body = Body::wrap_stream(reader);
+        let (body, file_length) = file_stream_body(file_path).await?;
 
         // Первой секцией идет метаинформация в формате json
         let meta = json!({
@@ -133,42 +120,48 @@ impl GoogleDriveClient {
         &self,
         folder_id: &str,
     ) -> Result<Option<GoogleDriveFolder>, GoogleDriveError> {
-        // TODO: Указание конкретного ID не работает почему-то
-        // let query = format!("(mimeType = 'application/vnd.google-apps.folder') and \
-        //                      (files.id = '{}')", folder_id);
-
-        let query = "(mimeType = 'application/vnd.google-apps.folder')";
-        let mut found_list = get_files_list_with_query(&self.request_builder, query, None).await?;
-
-        while !found_list.files.is_empty() {
-            let found = found_list
-                .files
-                .into_iter()
-                .find(|info| info.id.eq(folder_id));
-
-            // Нашли - возврат
-            if let Some(info) = found {
-                return Ok(Some(GoogleDriveFolder::new(
-                    self.request_builder.clone(),
-                    info,
-                )));
-            }
+        // https://developers.google.com/drive/api/v3/reference/files/get
+        let response = self
+            .request_builder
+            .build_request(Method::GET, &format!("drive/v3/files/{}", folder_id))?
+            .query(&[
+                ("supportsAllDrives", "true"),
+                ("supportsTeamDrives", "true"),
+                (
+                    "fields",
+                    "id,parents,owners,name,size,mimeType,webContentLink,webViewLink",
+                ),
+            ])
+            .send()
+            .await?
+            .inspect_json::<FilesUploadResponse, GoogleDriveError>(|data| {
+                trace!("Folder info response: {}", data);
+            })
+            .await?;
 
-            // Иначе заново запрашиваем новую страницу
-            if found_list.next_page_token.is_some() {
-                found_list = get_files_list_with_query(
-                    &self.request_builder,
-                    query,
-                    found_list.next_page_token,
-                )
-                .await?;
-            } else {
-                info!("Folder search finished, next token is empty");
-                break;
+        let info = match response {
+            FilesUploadResponse::Ok(info) => info,
+            // Папки с таким ID нет или у сервисного аккаунта нет к ней доступа
+            FilesUploadResponse::Error(err) if err.error.code == 404 => {
+                info!("Folder with id {} is not found", folder_id);
+                return Ok(None);
+            }
+            FilesUploadResponse::Error(err) => {
+                return Err(err.into());
             }
+        };
+
+        if !info.mime_type.eq(FOLDER_MIME_TYPE) {
+            return Err(GoogleDriveError::Custom(format!(
+                "Google drive item {} is not a folder: {}",
+                folder_id, info.mime_type
+            )));
         }
 
-        Ok(None)
+        Ok(Some(GoogleDriveFolder::new(
+            self.request_builder.clone(),
+            info,
+        )))
     }
 
     pub async fn upload(
@@ -177,36 +170,131 @@ impl GoogleDriveClient {
     ) -> Result<GoogleDriveUploadResult, GoogleDriveError> {
         info!("Before upload");
 
-        // Выгрузка файлика
-        let upload_res = self
-            .upload_file(task.parent_folder, &task.file_path)
-            .await?;
+        let file_name = task
+            .file_path
+            .file_name()
+            .ok_or(GoogleDriveError::WrongFilePath)?
+            .to_str()
+            .ok_or(GoogleDriveError::WrongFilePath)?;
+
+        // Уже существующие файлы с таким же именем
+        let existing_files = match task.on_conflict {
+            Some(_) => task.parent_folder.find_files_for_name(file_name).await?,
+            None => Vec::new(),
+        };
+
+        // Выгрузка файлика с учетом политики конфликтов
+        let (upload_res, action) = match (task.on_conflict, existing_files.is_empty()) {
+            (None, _) | (Some(_), true) => {
+                let file = self
+                    .upload_file(task.parent_folder, &task.file_path, file_name)
+                    .await?;
+                (file, GoogleDriveUploadAction::Uploaded)
+            }
+            (Some(GoogleDriveConflictPolicy::Skip), false) => {
+                info!("File {} already exists, skip uploading", file_name);
+                let existing = existing_files
+                    .into_iter()
+                    .next()
+                    .expect("Existing files list can't be empty");
+                return Self::make_result(existing, GoogleDriveUploadAction::Skipped);
+            }
+            (Some(GoogleDriveConflictPolicy::NewRevision), false) => {
+                info!("File {} already exists, upload new revision", file_name);
+                let existing = existing_files
+                    .into_iter()
+                    .next()
+                    .expect("Existing files list can't be empty");
+                let file = existing.upload_new_revision(&task.file_path).await?;
+                (file, GoogleDriveUploadAction::NewRevision)
+            }
+            (Some(GoogleDriveConflictPolicy::Replace), false) => {
+                info!("File {} already exists, replace it", file_name);
+                for existing in existing_files.iter() {
+                    existing.trash().await?;
+                }
+                let file = self
+                    .upload_file(task.parent_folder, &task.file_path, file_name)
+                    .await?;
+                (file, GoogleDriveUploadAction::Replaced)
+            }
+            (Some(GoogleDriveConflictPolicy::Rename), false) => {
+                let new_name = self
+                    .find_free_file_name(task.parent_folder, file_name)
+                    .await?;
+                info!("File {} already exists, upload as {}", file_name, new_name);
+                let file = self
+                    .upload_file(task.parent_folder, &task.file_path, &new_name)
+                    .await?;
+                (
+                    file,
+                    GoogleDriveUploadAction::Renamed {
+                        original_name: file_name.to_owned(),
+                    },
+                )
+            }
+        };
 
         debug!("Upload res: {:?}", upload_res.get_info());
 
-        // Смена владельца
-        if let Some(email) = task.owner_email {
-            upload_res.update_owner(EmailFileOwner::new(email)).await?;
-        } else if let Some(domain) = task.owner_domain {
+        // Выдача доступа
+        for permission in task.permissions {
             upload_res
-                .update_owner(DomainFileOwner::new(domain))
+                .add_permission(permission, task.send_notification_email)
                 .await?;
-        } else {
-            return Err(GoogleDriveError::EmptyNewOwner);
         }
 
-        // Результат
+        Self::make_result(upload_res, action)
+    }
+
+    /// Подбирает имя вида `name (1).ext`, которого еще нет в папке
+    async fn find_free_file_name(
+        &self,
+        parent_folder: &GoogleDriveFolder,
+        file_name: &str,
+    ) -> Result<String, GoogleDriveError> {
+        let path = Path::new(file_name);
+        let stem = path
+            .file_stem()
+            .and_then(|v| v.to_str())
+            .unwrap_or(file_name);
+        let extension = path.extension().and_then(|v| v.to_str());
+
+        let mut index = 1;
+        loop {
+            let candidate = match extension {
+                Some(ext) => format!("{} ({}).{}", stem, index, ext),
+                None => format!("{} ({})", stem, index),
+            };
+            if parent_folder
+                .find_files_for_name(&candidate)
+                .await?
+                .is_empty()
+            {
+                return Ok(candidate);
+            }
+            index += 1;
+        }
+    }
+
+    fn make_result(
+        file: GoogleDriveFile,
+        action: GoogleDriveUploadAction,
+    ) -> Result<GoogleDriveUploadResult, GoogleDriveError> {
         let FilesUploadResponseOk {
+            id,
             name,
             web_view_link,
             web_content_link,
             ..
-        } = upload_res.into();
+        } = file.into();
         match web_content_link {
             Some(web_content_link) => Ok(GoogleDriveUploadResult {
+                file_id: id,
                 file_name: name,
                 web_content_link,
                 web_view_link,
+                action,
             }),
             _ => Err(GoogleDriveError::Custom(
                 "Web view link is missing".to_owned(),
@@ -0,0 +1,58 @@
+use super::error::GoogleDriveError;
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Что делать, если в целевой папке уже есть файл с таким же именем
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoogleDriveConflictPolicy {
+    /// Старый файл отправляется в корзину, новый выгружается заново
+    Replace,
+    /// Выгружается новая ревизия уже существующего файла
+    NewRevision,
+    /// Выгрузка пропускается, в результате возвращается существующий файл
+    Skip,
+    /// К имени нового файла добавляется суффикс вида ` (1)`
+    Rename,
+}
+impl FromStr for GoogleDriveConflictPolicy {
+    type Err = GoogleDriveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "replace" => Ok(GoogleDriveConflictPolicy::Replace),
+            "new-revision" => Ok(GoogleDriveConflictPolicy::NewRevision),
+            "skip" => Ok(GoogleDriveConflictPolicy::Skip),
+            "rename" => Ok(GoogleDriveConflictPolicy::Rename),
+            _ => Err(GoogleDriveError::InvalidConflictPolicy(s.to_owned())),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Что в итоге произошло с файлом при выгрузке
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoogleDriveUploadAction {
+    Uploaded,
+    Replaced,
+    NewRevision,
+    Skipped,
+    Renamed { original_name: String },
+}
+impl Display for GoogleDriveUploadAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GoogleDriveUploadAction::Uploaded => write!(f, "uploaded"),
+            GoogleDriveUploadAction::Replaced => write!(f, "replaced existing file"),
+            GoogleDriveUploadAction::NewRevision => write!(f, "new revision of existing file"),
+            GoogleDriveUploadAction::Skipped => write!(f, "skipped, file already exists"),
+            GoogleDriveUploadAction::Renamed { original_name } => {
+                write!(f, "renamed, '{}' already exists", original_name)
+            }
+        }
+    }
+}
@@ -43,7 +43,16 @@ quick_error! {
         TokenIsExpired{
         }
 
-        EmptyNewOwner{
+        InvalidGrantee(value: String){
+            display("Invalid grantee '{}', must be one of: user:<email>, group:<email>, domain:<domain>, anyone", value)
+        }
+
+        InvalidRole(value: String){
+            display("Invalid role '{}', must be one of: reader, commenter, writer", value)
+        }
+
+        InvalidConflictPolicy(value: String){
+            display("Invalid conflict policy '{}', must be one of: replace, new-revision, skip, rename", value)
         }
 
         ErrorResponse(err: ResponseErrorValue){
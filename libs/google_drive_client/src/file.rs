@@ -1,19 +1,34 @@
 use reqwest::{
     Method
 };
+use reqwest_inspect_json::{
+    InspectJson
+};
+use into_result::{
+    IntoResult
+};
 use log::{
-    debug
+    trace
 };
-use serde_json::{
-    json,
-    Value
+use std::{
+    path::{
+        Path
+    }
 };
 use super::{
     request_builder::{
         GoogleDriveRequestBuilder
     },
+    helpers::{
+        create_permission,
+        file_stream_body,
+        trash_item
+    },
+    permission::{
+        GoogleDrivePermission
+    },
     responses::{
-        // FilesUploadResponse,
+        FilesUploadResponse,
         FilesUploadResponseOk,
         // FilesUploadResponseErr
     },
@@ -22,57 +37,6 @@ use super::{
     }
 };
 
-pub trait FileOwner{
-    fn get_params(&self) -> Value;
-}
-
-///////////////////////////////////////////////////////////////
-
-pub struct DomainFileOwner<'a>{
-    domain: &'a str
-}
-impl<'a> DomainFileOwner<'a> {
-    pub fn new(domain: &'a str) -> Self{
-        Self{
-            domain
-        }
-    }
-}
-impl<'a> FileOwner for DomainFileOwner<'a>{
-    fn get_params(&self) -> Value {
-        json!({
-            "role": "writer",
-            "type": "domain",
-            "domain": self.domain
-        })
-    }
-}
-
-///////////////////////////////////////////////////////////////
-
-pub struct EmailFileOwner<'a>{
-    email: &'a str
-}
-impl<'a> EmailFileOwner<'a> {
-    pub fn new(email: &'a str) -> Self{
-        Self{
-            email
-        }
-    }
-}
-impl<'a> FileOwner for EmailFileOwner<'a>{
-    fn get_params(&self) -> Value {
-        json!({
-            "role": "writer",
-            // "role": "owner",
-            "type": "user",
-            "emailAddress": self.email
-        })
-    }
-}
-
-///////////////////////////////////////////////////////////////
-
 pub struct GoogleDriveFile{
     client: GoogleDriveRequestBuilder,
     info: FilesUploadResponseOk
@@ -94,31 +58,41 @@ impl GoogleDriveFile {
         &self.info
     }
 
-    pub async fn update_owner<O>(&self, owner: O) -> Result<(), GoogleDriveError> 
-    where 
-        O: FileOwner {
-        // https://developers.google.com/drive/api/v3/reference/permissions/create
+    /// Выдает разрешение на доступ к файлу
+    pub async fn add_permission(&self, permission: &GoogleDrivePermission, send_notification_email: bool) -> Result<(), GoogleDriveError> {
+        create_permission(&self.client, &self.info.id, permission, send_notification_email).await
+    }
+
+    /// Выгружает содержимое файлика как новую ревизию этого файла
+    pub async fn upload_new_revision(self, file_path: &Path) -> Result<GoogleDriveFile, GoogleDriveError> {
+        // https://developers.google.com/drive/api/v3/reference/files/update
+        // https://developers.google.com/drive/api/v3/manage-uploads#simple
 
-        let body = owner.get_params();
+        let (body, file_length) = file_stream_body(file_path).await?;
 
-        let text = self.client
-            .build_request(Method::POST, "drive/v3/files/fileId/permissions")?
+        let info = self.client
+            .build_request(Method::PATCH, &format!("upload/drive/v3/files/{}", self.info.id))?
             .query(&[
-                ("fileId", self.info.id.as_str()),
-                // ("transferOwnership", "true"),
+                ("uploadType", "media"),
                 ("supportsAllDrives", "true"),
-                // ("fields", "id,emailAddress,domain")
+                ("supportsTeamDrives", "true"),
+                ("fields", "id,parents,owners,name,size,mimeType,webContentLink,webViewLink"),
             ])
-            .json(&body)
+            .header(reqwest::header::CONTENT_LENGTH, file_length)
+            .body(body)
             .send()
             .await?
-            .text()
-            .await?;
+            .inspect_json::<FilesUploadResponse, GoogleDriveError>(|data| {
+                trace!("File revision response: {}", data);
+            })
+            .await?
+            .into_result()?;
 
-        debug!("Owner update resp: {}", text);
-            // .json::<FilePermissionResponse>()
-            // .await?;
+        Ok(GoogleDriveFile::new(self.client, info))
+    }
 
-        Ok(())
+    /// Отправляет файл в корзину
+    pub async fn trash(&self) -> Result<(), GoogleDriveError> {
+        trash_item(&self.client, &self.info.id).await
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,18 @@
 use super::{
     error::GoogleDriveError,
-    helpers::get_files_list_with_query,
+    file::GoogleDriveFile,
+    helpers::{
+        create_permission, delete_item, escape_query_value, get_files_list_with_query, trash_item,
+    },
+    permission::GoogleDrivePermission,
     request_builder::GoogleDriveRequestBuilder,
     responses::{FilesUploadResponse, FilesUploadResponseOk},
+    retention::{GoogleDriveRemovedItem, GoogleDriveRetentionPolicy, GoogleDriveRetentionResult},
 };
+use chrono::{DateTime, Duration, Utc};
+use futures::future::{BoxFuture, FutureExt};
 use into_result::IntoResult;
-use log::debug;
+use log::{debug, info};
 use reqwest::{
     multipart::{Form, Part},
     Method,
@@ -15,6 +22,57 @@ use serde_json::json;
 
 //////////////////////////////////////////////////////////////////////////////
 
+pub(crate) const FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+
+//////////////////////////////////////////////////////////////////////////////
+
+/// Все элементы папки, кроме удаленных в корзину
+async fn list_children(
+    request_builder: &GoogleDriveRequestBuilder,
+    folder_id: &str,
+) -> Result<Vec<FilesUploadResponseOk>, GoogleDriveError> {
+    let query = format!("('{}' in parents) and (trashed = false)", folder_id);
+
+    let mut result = Vec::new();
+    let mut page_token = None;
+    loop {
+        let files_list = get_files_list_with_query(request_builder, &query, page_token).await?;
+        result.extend(files_list.files);
+        match files_list.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(result)
+}
+
+/// Суммарный размер элемента, для папок считается рекурсивно
+fn calculate_size<'a>(
+    request_builder: &'a GoogleDriveRequestBuilder,
+    info: &'a FilesUploadResponseOk,
+) -> BoxFuture<'a, Result<u64, GoogleDriveError>> {
+    async move {
+        if !info.mime_type.eq(FOLDER_MIME_TYPE) {
+            let size = info
+                .size
+                .as_deref()
+                .and_then(|size| size.parse::<u64>().ok())
+                .unwrap_or(0);
+            return Ok(size);
+        }
+
+        let mut total = 0;
+        for child in list_children(request_builder, &info.id).await? {
+            total += calculate_size(request_builder, &child).await?;
+        }
+        Ok(total)
+    }
+    .boxed()
+}
+
+//////////////////////////////////////////////////////////////////////////////
+
 pub struct GoogleDriveFolder {
     request_builder: GoogleDriveRequestBuilder,
     info: FilesUploadResponseOk,
@@ -39,16 +97,33 @@ impl GoogleDriveFolder {
         &self.info
     }
 
+    /// Выдает разрешение на доступ к папке, оно наследуется всем содержимым
+    pub async fn add_permission(
+        &self,
+        permission: &GoogleDrivePermission,
+        send_notification_email: bool,
+    ) -> Result<(), GoogleDriveError> {
+        create_permission(
+            &self.request_builder,
+            &self.info.id,
+            permission,
+            send_notification_email,
+        )
+        .await
+    }
+
     async fn find_sub_folder_id_for_name(
         &self,
         subfolder_name: &str,
     ) -> Result<Option<GoogleDriveFolder>, GoogleDriveError> {
         // https://developers.google.com/drive/api/v3/search-files
         let query = format!(
-            "(mimeType = 'application/vnd.google-apps.folder') and \
+            "(mimeType = '{}') and \
                              ('{}' in parents) and \
                              (name = '{}')",
-            self.info.id, subfolder_name
+            FOLDER_MIME_TYPE,
+            self.info.id,
+            escape_query_value(subfolder_name)
         );
 
         let mut files_list = get_files_list_with_query(&self.request_builder, &query, None).await?;
@@ -62,7 +137,7 @@ impl GoogleDriveFolder {
                     None => return false,
                 };
 
-                val.mime_type.eq("application/vnd.google-apps.folder")
+                val.mime_type.eq(FOLDER_MIME_TYPE)
                     && val.name.eq(subfolder_name)
                     && parents
                         .iter()
@@ -93,6 +168,45 @@ impl GoogleDriveFolder {
         Ok(None)
     }
 
+    /// Ищет в папке все файлы (не папки) с указанным именем, кроме удаленных в корзину
+    pub async fn find_files_for_name(
+        &self,
+        file_name: &str,
+    ) -> Result<Vec<GoogleDriveFile>, GoogleDriveError> {
+        // https://developers.google.com/drive/api/v3/search-files
+        let query = format!(
+            "(mimeType != '{}') and \
+                             ('{}' in parents) and \
+                             (name = '{}') and \
+                             (trashed = false)",
+            FOLDER_MIME_TYPE,
+            self.info.id,
+            escape_query_value(file_name)
+        );
+
+        let mut result = Vec::new();
+        let mut page_token = None;
+        loop {
+            let files_list =
+                get_files_list_with_query(&self.request_builder, &query, page_token).await?;
+
+            result.extend(
+                files_list
+                    .files
+                    .into_iter()
+                    .filter(|val| val.name.eq(file_name))
+                    .map(|val| GoogleDriveFile::new(self.request_builder.clone(), val)),
+            );
+
+            match files_list.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(result)
+    }
+
     async fn create_sub_folder(
         &self,
         subfolder_name: &str,
@@ -104,7 +218,7 @@ impl GoogleDriveFolder {
             "parents": [
                 parent_id
             ],
-            "mimeType": "application/vnd.google-apps.folder"
+            "mimeType": FOLDER_MIME_TYPE
         })
         .to_string();
 
@@ -154,4 +268,114 @@ impl GoogleDriveFolder {
             return self.create_sub_folder(subfolder_name).await;
         }
     }
+
+    /// Создает вложенные папки по пути вида `release/2026-10/build-123`,
+    /// каждый уровень создается только если его еще нет
+    pub async fn create_subfolders_path_if_needed(
+        &self,
+        subfolders_path: &str,
+    ) -> Result<GoogleDriveFolder, GoogleDriveError> {
+        let mut names = subfolders_path
+            .split('/')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty());
+
+        let first_name = names.next().ok_or_else(|| {
+            GoogleDriveError::Custom(format!("Empty subfolders path: '{}'", subfolders_path))
+        })?;
+
+        let mut folder = self.create_subfolder_if_needed(first_name).await?;
+        for name in names {
+            folder = folder.create_subfolder_if_needed(name).await?;
+        }
+
+        Ok(folder)
+    }
+
+    /// Удаляет старые элементы папки согласно политике хранения,
+    /// элементы с идентификаторами из `keep_ids` никогда не удаляются
+    pub async fn remove_old_items(
+        &self,
+        policy: &GoogleDriveRetentionPolicy,
+        keep_ids: &[&str],
+    ) -> Result<GoogleDriveRetentionResult, GoogleDriveError> {
+        // Подходящие под фильтры элементы
+        let (current, other): (Vec<_>, Vec<_>) =
+            list_children(&self.request_builder, &self.info.id)
+                .await?
+                .into_iter()
+                .filter(|info| policy.is_name_matched(&info.name))
+                .partition(|info| keep_ids.contains(&info.id.as_str()));
+
+        // Самые новые в начале
+        let mut items: Vec<(Option<DateTime<Utc>>, FilesUploadResponseOk)> = other
+            .into_iter()
+            .map(|info| {
+                let created = info
+                    .created_time
+                    .as_deref()
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                    .map(|time| time.with_timezone(&Utc));
+                (created, info)
+            })
+            .collect();
+        // Элементы без даты создания считаем самыми новыми, чтобы случайно их не удалить
+        items.sort_by(|a, b| match (a.0, b.0) {
+            (Some(a), Some(b)) => b.cmp(&a),
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        // Текущие сборки занимают часть лимита на количество
+        let keep_newest = policy
+            .keep_newest
+            .map(|count| count.saturating_sub(current.len()));
+        let created_before = policy
+            .max_age_days
+            .map(|days| Utc::now() - Duration::days(days));
+
+        let mut removed = Vec::new();
+        let mut reclaimed_bytes = 0;
+        for (index, (created, info)) in items.into_iter().enumerate() {
+            let too_many = keep_newest.map(|count| index >= count).unwrap_or(false);
+            let too_old = match (created, created_before) {
+                (Some(created), Some(created_before)) => created < created_before,
+                _ => false,
+            };
+            if !too_many && !too_old {
+                continue;
+            }
+
+            let size = calculate_size(&self.request_builder, &info).await?;
+
+            if policy.dry_run {
+                info!(
+                    "Retention dry run, would remove: {} ({} bytes)",
+                    info.name, size
+                );
+            } else if policy.delete_permanently {
+                delete_item(&self.request_builder, &info.id).await?;
+                info!("Retention removed: {} ({} bytes)", info.name, size);
+            } else {
+                trash_item(&self.request_builder, &info.id).await?;
+                info!("Retention trashed: {} ({} bytes)", info.name, size);
+            }
+
+            reclaimed_bytes += size;
+            removed.push(GoogleDriveRemovedItem {
+                is_folder: info.mime_type.eq(FOLDER_MIME_TYPE),
+                id: info.id,
+                name: info.name,
+                created_time: info.created_time,
+                size,
+            });
+        }
+
+        Ok(GoogleDriveRetentionResult {
+            removed,
+            reclaimed_bytes,
+            dry_run: policy.dry_run,
+        })
+    }
 }
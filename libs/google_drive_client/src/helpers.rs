@@ -1,8 +1,26 @@
 use log::{
+    debug,
     trace
 };
 use reqwest::{
     Method,
+    Body
+};
+use std::{
+    path::{
+        Path
+    }
+};
+use tokio::{
+    fs::{
+        File
+    }
+};
+use tokio_util::{
+    codec::{
+        BytesCodec,
+        FramedRead
+    }
 };
 use reqwest_inspect_json::{
     InspectJson
@@ -14,11 +32,28 @@ use super::{
     responses::{
         *
     },
+    permission::{
+        GoogleDrivePermission
+    },
     error::{
         GoogleDriveError
     }
 };
 
+/// Потоковое тело запроса из файлика вместе с его размером
+pub async fn file_stream_body(file_path: &Path) -> Result<(Body, u64), GoogleDriveError> {
+    let file = File::open(file_path).await?;
+    let file_length = file.metadata().await?.len();
+    let reader = FramedRead::new(file, BytesCodec::new());
+    Ok((Body::wrap_stream(reader), file_length))
+}
+
+/// Экранирование строки для использования внутри запроса поиска
+/// https://developers.google.com/drive/api/v3/ref-search-terms
+pub fn escape_query_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
 pub async fn get_files_list_with_query(request_builder: &GoogleDriveRequestBuilder, query: &str, page_token: Option<String>) -> Result<FilesListResponse, GoogleDriveError> {
     // https://developers.google.com/drive/api/v3/reference/files/list
     // https://developers.google.com/drive/api/v3/search-files
@@ -32,7 +67,7 @@ pub async fn get_files_list_with_query(request_builder: &GoogleDriveRequestBuild
             ("supportsAllDrives", "true"),
             ("includeTeamDriveItems", "true"),
             ("supportsTeamDrives", "true"),
-            ("fields", "nextPageToken,files(id,mimeType,name,webContentLink,webViewLink,parents)"),
+            ("fields", "nextPageToken,files(id,mimeType,name,webContentLink,webViewLink,parents,createdTime,size)"),
             ("pageToken", &page_token),
             ("q", query)
         ])
@@ -44,4 +79,137 @@ pub async fn get_files_list_with_query(request_builder: &GoogleDriveRequestBuild
     trace!("Files list response: {:?}", info);
 
     Ok(info)
-}
\ No newline at end of file
+}
+
+pub async fn create_permission(request_builder: &GoogleDriveRequestBuilder, item_id: &str, permission: &GoogleDrivePermission, send_notification_email: bool) -> Result<(), GoogleDriveError> {
+    // https://developers.google.com/drive/api/v3/reference/permissions/create
+    let send_notification_email = if send_notification_email { "true" } else { "false" };
+
+    let mut query = vec![
+        ("supportsAllDrives", "true"),
+        ("fields", "id"),
+    ];
+    // Параметр разрешен лишь для пользователей и групп
+    if permission.grantee.supports_notification() {
+        query.push(("sendNotificationEmail", send_notification_email));
+    }
+
+    let info = request_builder
+        .build_request(Method::POST, &format!("drive/v3/files/{}/permissions", item_id))?
+        .query(&query)
+        .json(&permission.get_params())
+        .send()
+        .await?
+        .inspect_json::<FilePermissionResponse, GoogleDriveError>(|d| { trace!("Permission resp: {}", d) })
+        .await?;
+
+    match info {
+        FilePermissionResponse::Ok(info) => {
+            debug!("Permission {} created for {}", info.id, item_id);
+            Ok(())
+        },
+        FilePermissionResponse::Error(err) => {
+            Err(err.into())
+        }
+    }
+}
+
+/// Отправляет файл или папку в корзину
+pub async fn trash_item(request_builder: &GoogleDriveRequestBuilder, item_id: &str) -> Result<(), GoogleDriveError> {
+    // https://developers.google.com/drive/api/v3/reference/files/update
+    let response = request_builder
+        .build_request(Method::PATCH, &format!("drive/v3/files/{}", item_id))?
+        .query(&[
+            ("supportsAllDrives", "true"),
+            ("supportsTeamDrives", "true"),
+            ("fields", "id,parents,owners,name,size,mimeType,webContentLink,webViewLink"),
+        ])
+        .json(&serde_json::json!({
+            "trashed": true
+        }))
+        .send()
+        .await?
+        .inspect_json::<FilesUploadResponse, GoogleDriveError>(|d| { trace!("Trash resp: {}", d) })
+        .await?;
+
+    match response {
+        FilesUploadResponse::Ok(info) => {
+            debug!("Item trashed: {}", info.name);
+            Ok(())
+        },
+        FilesUploadResponse::Error(err) => {
+            Err(err.into())
+        }
+    }
+}
+
+/// Удаляет файл или папку окончательно, минуя корзину
+pub async fn delete_item(request_builder: &GoogleDriveRequestBuilder, item_id: &str) -> Result<(), GoogleDriveError> {
+    // https://developers.google.com/drive/api/v3/reference/files/delete
+    let response = request_builder
+        .build_request(Method::DELETE, &format!("drive/v3/files/{}", item_id))?
+        .query(&[
+            ("supportsAllDrives", "true"),
+            ("supportsTeamDrives", "true"),
+        ])
+        .send()
+        .await?;
+
+    // В случае успеха тело ответа пустое
+    if response.status().is_success() {
+        debug!("Item deleted: {}", item_id);
+        return Ok(());
+    }
+
+    let err = response.json::<ResponseErr>().await?;
+    Err(err.into())
+}
+
+/// Проверка имени на соответствие шаблону, поддерживаются `*` и `?`
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Классический жадный алгоритм с откатом к последней звездочке
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        // Звездочка
+        assert!(wildcard_match("*.apk", "app-release.apk"));
+        assert!(wildcard_match("build_*_*.zip", "build_123_ios.zip"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("*.apk", "app-release.aab"));
+
+        // Вопросительный знак
+        assert!(wildcard_match("v1.?.apk", "v1.2.apk"));
+        assert!(!wildcard_match("v1.?.apk", "v1.22.apk"));
+
+        // Точное совпадение
+        assert!(wildcard_match("app.apk", "app.apk"));
+        assert!(!wildcard_match("app.apk", "app.apk.old"));
+        assert!(!wildcard_match("app.apk", "my_app.apk"));
+    }
+}
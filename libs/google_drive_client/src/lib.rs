@@ -5,6 +5,9 @@ mod client;
 mod file;
 mod folder;
 mod error;
+mod conflict;
+mod permission;
+mod retention;
 
 pub use self::{
     client::{
@@ -14,5 +17,22 @@ pub use self::{
     },
     error::{
         GoogleDriveError
+    },
+    folder::{
+        GoogleDriveFolder
+    },
+    conflict::{
+        GoogleDriveConflictPolicy,
+        GoogleDriveUploadAction
+    },
+    permission::{
+        GoogleDriveGrantee,
+        GoogleDrivePermission,
+        GoogleDriveRole
+    },
+    retention::{
+        GoogleDriveRemovedItem,
+        GoogleDriveRetentionPolicy,
+        GoogleDriveRetentionResult
     }
 };
\ No newline at end of file
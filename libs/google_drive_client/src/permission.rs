@@ -0,0 +1,108 @@
+use super::error::GoogleDriveError;
+use serde_json::{json, Value};
+use std::str::FromStr;
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Кому выдается доступ
+/// https://developers.google.com/drive/api/v3/reference/permissions#resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GoogleDriveGrantee {
+    User(String),
+    Group(String),
+    Domain(String),
+    Anyone,
+}
+impl GoogleDriveGrantee {
+    /// Оповещения по почте можно отправлять лишь пользователям и группам
+    pub(crate) fn supports_notification(&self) -> bool {
+        matches!(self, GoogleDriveGrantee::User(_) | GoogleDriveGrantee::Group(_))
+    }
+}
+impl FromStr for GoogleDriveGrantee {
+    type Err = GoogleDriveError;
+
+    /// Формат: `user:email`, `group:email`, `domain:name` или `anyone`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("user", email)) if !email.is_empty() => {
+                Ok(GoogleDriveGrantee::User(email.to_owned()))
+            }
+            Some(("group", email)) if !email.is_empty() => {
+                Ok(GoogleDriveGrantee::Group(email.to_owned()))
+            }
+            Some(("domain", domain)) if !domain.is_empty() => {
+                Ok(GoogleDriveGrantee::Domain(domain.to_owned()))
+            }
+            None if s == "anyone" => Ok(GoogleDriveGrantee::Anyone),
+            _ => Err(GoogleDriveError::InvalidGrantee(s.to_owned())),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoogleDriveRole {
+    Reader,
+    Commenter,
+    Writer,
+}
+impl GoogleDriveRole {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GoogleDriveRole::Reader => "reader",
+            GoogleDriveRole::Commenter => "commenter",
+            GoogleDriveRole::Writer => "writer",
+        }
+    }
+}
+impl FromStr for GoogleDriveRole {
+    type Err = GoogleDriveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reader" => Ok(GoogleDriveRole::Reader),
+            "commenter" => Ok(GoogleDriveRole::Commenter),
+            "writer" => Ok(GoogleDriveRole::Writer),
+            _ => Err(GoogleDriveError::InvalidRole(s.to_owned())),
+        }
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Выдаваемое разрешение на файл или папку
+#[derive(Debug, Clone)]
+pub struct GoogleDrivePermission {
+    pub grantee: GoogleDriveGrantee,
+    pub role: GoogleDriveRole,
+    /// Время окончания доступа в формате RFC 3339, только для пользователей и групп
+    pub expiration_time: Option<String>,
+}
+impl GoogleDrivePermission {
+    pub(crate) fn get_params(&self) -> Value {
+        let mut params = match &self.grantee {
+            GoogleDriveGrantee::User(email) => json!({
+                "type": "user",
+                "emailAddress": email
+            }),
+            GoogleDriveGrantee::Group(email) => json!({
+                "type": "group",
+                "emailAddress": email
+            }),
+            GoogleDriveGrantee::Domain(domain) => json!({
+                "type": "domain",
+                "domain": domain
+            }),
+            GoogleDriveGrantee::Anyone => json!({
+                "type": "anyone"
+            }),
+        };
+        params["role"] = Value::from(self.role.as_str());
+        if let Some(expiration_time) = &self.expiration_time {
+            params["expirationTime"] = Value::from(expiration_time.as_str());
+        }
+        params
+    }
+}
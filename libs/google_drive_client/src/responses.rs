@@ -35,7 +35,11 @@ pub struct FilesUploadResponseOk{
     #[serde(rename = "webViewLink")]
     pub web_view_link: String,
     #[serde(rename = "webContentLink")]
-    pub web_content_link: Option<String>
+    pub web_content_link: Option<String>,
+    #[serde(rename = "createdTime")]
+    pub created_time: Option<String>,
+    // Google отдает int64 строкой, у папок размера нет
+    pub size: Option<String>
 }
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
@@ -56,13 +60,19 @@ impl into_result::IntoResult<FilesUploadResponseOk, ResponseErr> for FilesUpload
 
 // https://developers.google.com/drive/api/v3/reference/files#resource
 #[derive(Deserialize, Debug)]
-pub struct FilePermissionResponse{
+pub struct FilePermissionResponseOk{
     pub id: String,
     // pub domain: String,
     
     // #[serde(rename = "emailAddress")]
     // pub email_address: String,
 }
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum FilePermissionResponse{
+    Ok(FilePermissionResponseOk),
+    Error(ResponseErr)
+}
 
 ///////////////////////////////////////////////////////////////////////////////////////////
 
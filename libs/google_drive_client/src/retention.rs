@@ -0,0 +1,45 @@
+use super::helpers::wildcard_match;
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Политика удаления старых сборок из папки
+#[derive(Debug, Clone, Default)]
+pub struct GoogleDriveRetentionPolicy {
+    /// Удалять элементы старше указанного количества дней
+    pub max_age_days: Option<i64>,
+    /// Оставлять лишь указанное количество самых новых элементов
+    pub keep_newest: Option<usize>,
+    /// Шаблоны имен с `*` и `?`, пустой список означает все элементы
+    pub name_patterns: Vec<String>,
+    /// Удалять окончательно вместо перемещения в корзину
+    pub delete_permanently: bool,
+    /// Только сформировать список без удаления
+    pub dry_run: bool,
+}
+impl GoogleDriveRetentionPolicy {
+    pub(crate) fn is_name_matched(&self, name: &str) -> bool {
+        self.name_patterns.is_empty()
+            || self
+                .name_patterns
+                .iter()
+                .any(|pattern| wildcard_match(pattern, name))
+    }
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub struct GoogleDriveRemovedItem {
+    pub id: String,
+    pub name: String,
+    pub is_folder: bool,
+    pub created_time: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Debug)]
+pub struct GoogleDriveRetentionResult {
+    pub removed: Vec<GoogleDriveRemovedItem>,
+    pub reclaimed_bytes: u64,
+    pub dry_run: bool,
+}
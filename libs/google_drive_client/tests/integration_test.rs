@@ -1,4 +1,7 @@
-use google_drive_client::{GoogleDriveClient, GoogleDriveUploadTask};
+use google_drive_client::{
+    GoogleDriveClient, GoogleDriveGrantee, GoogleDrivePermission, GoogleDriveRole,
+    GoogleDriveUploadTask,
+};
 use reqwest::Client;
 use std::{path::PathBuf, sync::Once};
 use log::{debug, info};
@@ -57,11 +60,18 @@ async fn library_integration_test() {
 
     let file_path = PathBuf::from("/Users/devnul/Downloads/jdk-15.0.1_osx-x64_bin.dmg");
 
+    let permissions = [GoogleDrivePermission {
+        grantee: GoogleDriveGrantee::User("devnulpavel@gmail.com".to_owned()),
+        role: GoogleDriveRole::Writer,
+        expiration_time: None,
+    }];
+
     let task = GoogleDriveUploadTask {
         file_path,
         parent_folder: &sub_folder,
-        owner_email: Some("devnulpavel@gmail.com"),
-        owner_domain: None,
+        permissions: &permissions,
+        send_notification_email: true,
+        on_conflict: None,
     };
     info!("Google drive task created");
 
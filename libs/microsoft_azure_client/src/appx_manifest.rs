@@ -0,0 +1,348 @@
+use crate::{error::MicrosoftAzureError, helpers::check_file_extention};
+use log::debug;
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+
+// Описание манифестов:
+// https://docs.microsoft.com/en-us/uwp/schemas/appxpackage/uapmanifestschema/element-identity
+// https://docs.microsoft.com/en-us/uwp/schemas/bundlemanifestschema/element-package
+
+const PACKAGE_MANIFEST: &str = "AppxManifest.xml";
+const BUNDLE_MANIFEST: &str = "AppxMetadata/AppxBundleManifest.xml";
+const KNOWN_ARCHITECTURES: &[&str] = &["x86", "x64", "arm", "arm64", "neutral"];
+
+/// Информация о пакете из его манифеста
+#[derive(Debug, Clone)]
+pub struct AppxPackageInfo {
+    pub file_name: String,
+    /// Имя идентичности пакета, одинаковое для всех пакетов приложения
+    pub name: String,
+    /// Версия вида "1.2.3.0"
+    pub version: String,
+    /// Архитектуры пакета, у бандла их может быть несколько
+    pub architectures: Vec<String>,
+}
+
+/// Требования к пакетам, проверяемые перед выгрузкой
+#[derive(Debug, Clone, Default)]
+pub struct PackageRequirements {
+    /// Ожидаемая версия пакетов
+    pub version: Option<String>,
+    /// Архитектуры, которые обязательно должны быть среди пакетов
+    pub architectures: Option<Vec<String>>,
+}
+
+/// Читаем манифест пакета .appx/.msix, бандла .appxbundle/.msixbundle
+/// или вложенного в .appxupload/.msixupload пакета, блокирующая функция
+pub fn read_package_info(path: &Path) -> Result<AppxPackageInfo, MicrosoftAzureError> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| MicrosoftAzureError::NoFile(path.to_owned()))?
+        .to_owned();
+
+    let (name, version, architectures) = read_archive_identity(File::open(path)?, &file_name)?;
+    let info = AppxPackageInfo {
+        file_name,
+        name,
+        version,
+        architectures,
+    };
+    debug!("Microsoft Azure: package info {:?}", info);
+
+    Ok(info)
+}
+
+/// Является ли файлик пакетом, который можно выгрузить в магазин
+pub(crate) fn is_package_file(path: &Path) -> bool {
+    [
+        "appx",
+        "msix",
+        "appxbundle",
+        "msixbundle",
+        "appxupload",
+        "msixupload",
+    ]
+    .iter()
+    .any(|ext| check_file_extention(path, ext))
+}
+
+fn read_archive_identity<R: Read + Seek>(
+    reader: R,
+    file_name: &str,
+) -> Result<(String, String, Vec<String>), MicrosoftAzureError> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+
+    // Обычный пакет
+    if let Some(xml) = read_entry(&mut archive, PACKAGE_MANIFEST)? {
+        let document = roxmltree::Document::parse(&xml)?;
+        let identity = find_identity(&document, file_name)?;
+        let architecture = identity
+            .attribute("ProcessorArchitecture")
+            .unwrap_or("neutral")
+            .to_lowercase();
+        return Ok((
+            get_attribute(&identity, "Name", file_name)?,
+            get_attribute(&identity, "Version", file_name)?,
+            vec![architecture],
+        ));
+    }
+
+    // Бандл, архитектуры берем из пакетов приложения внутри
+    if let Some(xml) = read_entry(&mut archive, BUNDLE_MANIFEST)? {
+        let document = roxmltree::Document::parse(&xml)?;
+        let identity = find_identity(&document, file_name)?;
+        let architectures = document
+            .descendants()
+            .filter(|node| node.has_tag_name("Package"))
+            .filter(|node| node.attribute("Type").unwrap_or("application") == "application")
+            .filter_map(|node| node.attribute("Architecture"))
+            .map(|architecture| architecture.to_lowercase())
+            .collect();
+        return Ok((
+            get_attribute(&identity, "Name", file_name)?,
+            get_attribute(&identity, "Version", file_name)?,
+            architectures,
+        ));
+    }
+
+    // Файлик выгрузки, внутри лежит сам пакет или бандл, а также символы
+    let inner_name = archive
+        .file_names()
+        .find(|name| {
+            let path = Path::new(name);
+            is_package_file(path)
+                && !check_file_extention(path, "appxupload")
+                && !check_file_extention(path, "msixupload")
+        })
+        .map(|name| name.to_owned())
+        .ok_or_else(|| MicrosoftAzureError::AppxManifestIsMissing(file_name.to_owned()))?;
+
+    // Вложенный архив может быть большим, поэтому распаковываем во временный файлик
+    let mut inner_file = tempfile::tempfile()?;
+    std::io::copy(&mut archive.by_name(&inner_name)?, &mut inner_file)?;
+    inner_file.rewind()?;
+
+    read_archive_identity(inner_file, file_name)
+}
+
+fn read_entry<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Option<String>, MicrosoftAzureError> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut xml = String::new();
+    entry.read_to_string(&mut xml)?;
+    Ok(Some(xml))
+}
+
+fn find_identity<'a, 'input>(
+    document: &'a roxmltree::Document<'input>,
+    file_name: &str,
+) -> Result<roxmltree::Node<'a, 'input>, MicrosoftAzureError> {
+    // Identity должна быть прямым потомком корня, у вложенных элементов своих Identity нет
+    document
+        .root_element()
+        .children()
+        .find(|node| node.has_tag_name("Identity"))
+        .ok_or_else(|| MicrosoftAzureError::InvalidAppxManifest {
+            file_name: file_name.to_owned(),
+            info: "Identity element is missing".to_owned(),
+        })
+}
+
+fn get_attribute(
+    node: &roxmltree::Node,
+    name: &str,
+    file_name: &str,
+) -> Result<String, MicrosoftAzureError> {
+    node.attribute(name)
+        .map(|value| value.to_owned())
+        .ok_or_else(|| MicrosoftAzureError::InvalidAppxManifest {
+            file_name: file_name.to_owned(),
+            info: format!("Identity attribute {} is missing", name),
+        })
+}
+
+/// Версия пакета для магазина: 4 числа, последнее зарезервировано и должно быть нулем
+fn check_version(info: &AppxPackageInfo) -> Result<(), String> {
+    let parts = info
+        .version
+        .split('.')
+        .map(|part| part.parse::<u16>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| format!("{}: invalid version {}", info.file_name, info.version))?;
+
+    match parts.as_slice() {
+        [_, _, _, 0] => Ok(()),
+        [_, _, _, _] => Err(format!(
+            "{}: revision number of version {} must be 0",
+            info.file_name, info.version
+        )),
+        _ => Err(format!(
+            "{}: version {} must have 4 parts",
+            info.file_name, info.version
+        )),
+    }
+}
+
+/// Проверяем, что пакеты относятся к одному приложению и соответствуют требованиям
+pub fn validate_packages(
+    packages: &[AppxPackageInfo],
+    requirements: &PackageRequirements,
+) -> Result<(), MicrosoftAzureError> {
+    let fail = |info: String| Err(MicrosoftAzureError::PackageValidationFailed(info));
+
+    let first = match packages.first() {
+        Some(first) => first,
+        None => return Err(MicrosoftAzureError::NoAppxFilesInZip),
+    };
+
+    let mut seen = HashSet::new();
+    for package in packages.iter() {
+        if let Err(info) = check_version(package) {
+            return fail(info);
+        }
+        if package.name != first.name {
+            return fail(format!(
+                "{}: package name {} differs from {} in {}",
+                package.file_name, package.name, first.name, first.file_name
+            ));
+        }
+        if let Some(version) = &requirements.version {
+            if package.version.ne(version) {
+                return fail(format!(
+                    "{}: version {} differs from expected {}",
+                    package.file_name, package.version, version
+                ));
+            }
+        }
+        if package.architectures.is_empty() {
+            return fail(format!("{}: no architectures found", package.file_name));
+        }
+        for architecture in package.architectures.iter() {
+            if !KNOWN_ARCHITECTURES.contains(&architecture.as_str()) {
+                return fail(format!(
+                    "{}: unknown architecture {}",
+                    package.file_name, architecture
+                ));
+            }
+            // Магазин не примет два пакета с одной версией и архитектурой
+            if !seen.insert((package.version.as_str(), architecture.as_str())) {
+                return fail(format!(
+                    "{}: duplicate package for version {} and architecture {}",
+                    package.file_name, package.version, architecture
+                ));
+            }
+        }
+    }
+
+    if let Some(required) = &requirements.architectures {
+        let missing: Vec<&str> = required
+            .iter()
+            .map(|architecture| architecture.as_str())
+            .filter(|architecture| {
+                !seen
+                    .iter()
+                    .any(|(_, existing)| existing.eq_ignore_ascii_case(architecture))
+            })
+            .collect();
+        if !missing.is_empty() {
+            return fail(format!("missing architectures: {}", missing.join(", ")));
+        }
+    }
+
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{write::FileOptions, ZipWriter};
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries.iter() {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_read_and_validate_packages() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // .msixupload с пакетом внутри
+        let manifest = br#"<?xml version="1.0" encoding="utf-8"?>
+            <Package xmlns="http://schemas.microsoft.com/appx/manifest/foundation/windows10">
+              <Identity Name="Company.Game" Publisher="CN=Company" Version="1.2.3.0" ProcessorArchitecture="x64" />
+            </Package>"#;
+        let msix_path = dir.path().join("Game_x64.msix");
+        write_zip(&msix_path, &[(PACKAGE_MANIFEST, manifest)]);
+        let upload_path = dir.path().join("Game_x64.msixupload");
+        write_zip(
+            &upload_path,
+            &[("Game_x64.msix", &std::fs::read(&msix_path).unwrap())],
+        );
+
+        // Бандл с двумя архитектурами
+        let bundle_manifest = br#"<?xml version="1.0" encoding="utf-8"?>
+            <Bundle xmlns="http://schemas.microsoft.com/appx/2013/bundle">
+              <Identity Name="Company.Game" Publisher="CN=Company" Version="1.2.3.0" />
+              <Packages>
+                <Package Type="application" Version="1.2.3.0" Architecture="x86" FileName="Game_x86.msix" />
+                <Package Type="application" Version="1.2.3.0" Architecture="arm64" FileName="Game_arm64.msix" />
+                <Package Type="resource" Version="1.2.3.0" ResourceId="split.scale-150" FileName="Game_scale-150.msix" />
+              </Packages>
+            </Bundle>"#;
+        let bundle_path = dir.path().join("Game.msixbundle");
+        write_zip(&bundle_path, &[(BUNDLE_MANIFEST, bundle_manifest)]);
+
+        let upload = read_package_info(&upload_path).unwrap();
+        assert_eq!(upload.file_name, "Game_x64.msixupload");
+        assert_eq!(upload.version, "1.2.3.0");
+        assert_eq!(upload.architectures, vec!["x64"]);
+
+        let bundle = read_package_info(&bundle_path).unwrap();
+        assert_eq!(bundle.architectures, vec!["x86", "arm64"]);
+
+        let packages = vec![upload.clone(), bundle];
+        let requirements = PackageRequirements {
+            version: Some("1.2.3.0".to_owned()),
+            architectures: Some(vec!["x64".to_owned(), "ARM64".to_owned()]),
+        };
+        validate_packages(&packages, &requirements).unwrap();
+
+        let wrong_version = PackageRequirements {
+            version: Some("1.2.4.0".to_owned()),
+            ..Default::default()
+        };
+        assert!(validate_packages(&packages, &wrong_version).is_err());
+
+        let missing_architecture = PackageRequirements {
+            architectures: Some(vec!["arm".to_owned()]),
+            ..Default::default()
+        };
+        assert!(validate_packages(&packages, &missing_architecture).is_err());
+
+        // Одна и та же архитектура дважды и ненулевая ревизия
+        assert!(validate_packages(&[upload.clone(), upload.clone()], &Default::default()).is_err());
+        let revision = AppxPackageInfo {
+            version: "1.2.3.1".to_owned(),
+            ..upload
+        };
+        assert!(validate_packages(&[revision], &Default::default()).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    blob_uploader::{perform_blob_file_uploading, BlobProperties, BlobUploadOptions},
+    error::MicrosoftAzureError,
+};
+use log::debug;
+use reqwest::Client;
+use std::path::Path;
+use url::Url;
+
+/// Клиент для выгрузки файликов в контейнер Azure Blob Storage по SAS урлу контейнера.
+/// Урл вида `https://<account>.blob.core.windows.net/<container>?<sas>`,
+/// для Azurite `http://127.0.0.1:10000/devstoreaccount1/<container>?<sas>`
+pub struct BlobStorageClient {
+    http_client: Client,
+    container_url: Url,
+    upload_options: BlobUploadOptions,
+}
+
+impl BlobStorageClient {
+    pub fn new(
+        http_client: Client,
+        container_sas_url: &str,
+    ) -> Result<BlobStorageClient, MicrosoftAzureError> {
+        let container_url = Url::parse(container_sas_url)?;
+        if container_url.cannot_be_a_base() {
+            return Err(MicrosoftAzureError::RequestBuilderFail(
+                "Container SAS URL can't be a base URL",
+            ));
+        }
+
+        Ok(BlobStorageClient {
+            http_client,
+            container_url,
+            upload_options: Default::default(),
+        })
+    }
+
+    /// Меняем размер блоков и количество потоков выгрузки
+    pub fn with_upload_options(mut self, options: BlobUploadOptions) -> BlobStorageClient {
+        self.upload_options = options;
+        self
+    }
+
+    /// Урл блоба вместе с SAS параметрами контейнера
+    fn blob_sas_url(&self, blob_name: &str) -> Result<Url, MicrosoftAzureError> {
+        let mut url = self.container_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| MicrosoftAzureError::UnvalidUrlSegments)?
+            .pop_if_empty()
+            .extend(blob_name.split('/').filter(|segment| !segment.is_empty()));
+        Ok(url)
+    }
+
+    /// Выгружаем файлик в блоб с указанным именем, имя может содержать путь через `/`.
+    /// Возвращается урл блоба без SAS параметров, чтобы его можно было спокойно показывать
+    pub async fn upload_file(
+        &self,
+        file_path: &Path,
+        blob_name: &str,
+        properties: &BlobProperties,
+    ) -> Result<Url, MicrosoftAzureError> {
+        if !file_path.exists() {
+            return Err(MicrosoftAzureError::NoFile(file_path.to_owned()));
+        }
+
+        let sas_url = self.blob_sas_url(blob_name)?;
+        debug!(
+            "Azure blob storage: uploading {:?} to {}",
+            file_path, blob_name
+        );
+
+        perform_blob_file_uploading(
+            &self.http_client,
+            &sas_url,
+            file_path,
+            &self.upload_options,
+            properties,
+        )
+        .await?;
+
+        let mut blob_url = sas_url;
+        blob_url.set_query(None);
+        Ok(blob_url)
+    }
+}
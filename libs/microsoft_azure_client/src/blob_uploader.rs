@@ -1,24 +1,111 @@
 use crate::error::MicrosoftAzureError;
 use bytes::Bytes;
-use log::{debug, error, warn};
-use reqwest::Client;
-use std::{fmt::Display, path::Path, usize};
+use log::{debug, error, info, warn};
+use reqwest::{Client, StatusCode};
+use std::{collections::HashMap, fmt::Display, path::Path, usize};
 use tokio::{fs::File, io::AsyncReadExt, sync::mpsc, time};
 use url::Url;
 
-/// Сколько испольузем потоков выгрузки?
-const UPLOAD_THREADS_COUNT: usize = 8;
-/// Размер отдельного отгружаемого блока данных
-const BUFFER_MAX_SIZE: i64 = 1024 * 1024 * 8; // 8Mb - ограничение для отдельного куска
+/// Максимальный размер блока по документации Put Block
+const BLOCK_MAX_SIZE: usize = 1024 * 1024 * 4000;
+
+/// Параметры выгрузки файлика блоками
+#[derive(Debug, Clone)]
+pub struct BlobUploadOptions {
+    /// Размер отдельного отгружаемого блока данных
+    pub block_size: usize,
+    /// Сколько испольузем потоков выгрузки
+    pub concurrency: usize,
+}
+
+impl Default for BlobUploadOptions {
+    fn default() -> Self {
+        BlobUploadOptions {
+            block_size: 1024 * 1024 * 8,
+            concurrency: 8,
+        }
+    }
+}
+
+/// Свойства блоба, выставляемые при коммите списка блоков
+#[derive(Debug, Clone, Default)]
+pub struct BlobProperties {
+    pub content_type: Option<String>,
+    pub cache_control: Option<String>,
+    /// Пользовательские метаданные, имена должны быть валидными идентификаторами C#
+    pub metadata: Vec<(String, String)>,
+}
 
 // Задача выгрузки
 #[derive(Debug)]
 struct UploadTask {
     data: Bytes,
     block_id: String,
+    /// Контрольная сумма блока в base64
+    md5: String,
     index: usize,
 }
 
+/// Id блока строится из смещения и хеша данных, поэтому при повторном запуске совпадает с уже выгруженным
+/// только для тех же самых байтов, а не для блока другого файлика на том же месте.
+/// Id должен быть в base64 и одной длины для всех блоков: 8 байт смещения + 8 байт хеша
+fn make_block_id(offset: u64, md5: &md5::Digest) -> String {
+    let mut id = [0_u8; 16];
+    id[..8].copy_from_slice(&offset.to_be_bytes());
+    id[8..].copy_from_slice(&md5.0[..8]);
+    base64::encode(id)
+}
+
+/// Получаем список незакоммиченных блоков, выгруженных ранее: id -> размер
+async fn get_uncommitted_blocks(
+    http_client: &Client,
+    url: &Url,
+) -> Result<HashMap<String, u64>, MicrosoftAzureError> {
+    // https://docs.microsoft.com/en-us/rest/api/storageservices/get-block-list
+    let mut block_list_url = url.clone();
+    block_list_url
+        .query_pairs_mut()
+        .append_pair("comp", "blocklist")
+        .append_pair("blocklisttype", "uncommitted");
+
+    let response = http_client.get(block_list_url).send().await?;
+
+    // Ни блоба, ни блоков еще нет, либо SAS не дает прав на чтение списка блоков:
+    // в обоих случаях просто выгружаем файлик с нуля
+    let status = response.status();
+    if !status.is_success() {
+        if status != StatusCode::NOT_FOUND {
+            warn!(
+                "Microsoft Azure: block list request failed with status {}, upload from scratch",
+                status
+            );
+        }
+        return Ok(HashMap::new());
+    }
+    let xml = response.text().await?;
+
+    let document = roxmltree::Document::parse(&xml)?;
+    let blocks = document
+        .descendants()
+        .filter(|node| node.has_tag_name("UncommittedBlocks"))
+        .flat_map(|node| node.children())
+        .filter(|node| node.has_tag_name("Block"))
+        .filter_map(|block| {
+            let child_text = |name: &str| {
+                block
+                    .children()
+                    .find(|node| node.has_tag_name(name))
+                    .and_then(|node| node.text())
+            };
+            let name = child_text("Name")?.to_owned();
+            let size = child_text("Size")?.parse::<u64>().ok()?;
+            Some((name, size))
+        })
+        .collect();
+
+    Ok(blocks)
+}
+
 // Результат выгрузки
 #[derive(Debug)]
 struct UploadResult {
@@ -54,6 +141,7 @@ async fn upload_worker(
         let UploadTask {
             data,
             block_id,
+            md5,
             index,
         } = task;
 
@@ -73,6 +161,7 @@ async fn upload_worker(
                 http_client
                     .put(url.clone())
                     .header(reqwest::header::CONTENT_LENGTH, data.len())
+                    .header("Content-MD5", &md5)
                     .body(data.clone())
                     .send()
                     .await?
@@ -117,10 +206,11 @@ async fn upload_worker(
 fn spawn_uploaders(
     http_client: &Client,
     url: &Url,
+    concurrency: usize,
     task_receiver: async_channel::Receiver<UploadTask>,
     result_sender: mpsc::Sender<Result<UploadResult, MicrosoftAzureError>>,
 ) {
-    for _ in 0..UPLOAD_THREADS_COUNT {
+    for _ in 0..concurrency {
         // Создаем клоны для воркера
         let http_client = http_client.clone();
         let url = url.clone();
@@ -142,6 +232,7 @@ async fn commit_blocks(
     http_client: &Client,
     url: &Url,
     blocks: Vec<UploadResult>,
+    properties: &BlobProperties,
 ) -> Result<(), MicrosoftAzureError> {
     // Формируем XML со списком блоков
     let data = {
@@ -164,13 +255,21 @@ async fn commit_blocks(
         list_commit_url
     };
 
+    // Свойства блоба передаются заголовками
+    // https://docs.microsoft.com/en-us/rest/api/storageservices/put-block-list#request-headers
+    let mut request = http_client.put(list_commit_url);
+    if let Some(content_type) = &properties.content_type {
+        request = request.header("x-ms-blob-content-type", content_type);
+    }
+    if let Some(cache_control) = &properties.cache_control {
+        request = request.header("x-ms-blob-cache-control", cache_control);
+    }
+    for (name, value) in properties.metadata.iter() {
+        request = request.header(format!("x-ms-meta-{}", name).as_str(), value);
+    }
+
     // Делаем запрос с коммитом
-    http_client
-        .put(list_commit_url)
-        .body(data)
-        .send()
-        .await?
-        .error_for_status()?;
+    request.body(data).send().await?.error_for_status()?;
     Ok(())
 }
 
@@ -182,16 +281,41 @@ fn usize_to_displayable<T: humansize::FileSize>(
         .map_err(MicrosoftAzureError::HumanSizeError)
 }
 
-/// Выполнение выгрузки непосредственно файлика с билдом
+/// Выполнение выгрузки непосредственно файлика с билдом,
+/// уже выгруженные ранее по этому же урлу блоки повторно не отправляются
 pub async fn perform_blob_file_uploading(
     http_client: &Client,
     url: &Url,
     file_path: &Path,
+    options: &BlobUploadOptions,
+    properties: &BlobProperties,
 ) -> Result<(), MicrosoftAzureError> {
     debug!("Microsoft Azure: file uploading start");
 
-    // Первым этапом идет выставление режима AppendBlob для выгрузки
-    enable_block_mode(http_client, url).await?;
+    if options.block_size == 0 || options.block_size > BLOCK_MAX_SIZE {
+        return Err(MicrosoftAzureError::UploadingError(format!(
+            "Invalid block size: {}",
+            options.block_size
+        )));
+    }
+    if options.concurrency == 0 {
+        return Err(MicrosoftAzureError::UploadingError(
+            "Upload concurrency must be greater than zero".to_owned(),
+        ));
+    }
+
+    // Блоки, выгруженные прошлой попыткой
+    let uploaded_blocks = get_uncommitted_blocks(http_client, url).await?;
+    if uploaded_blocks.is_empty() {
+        // Первым этапом идет выставление режима AppendBlob для выгрузки,
+        // при продолжении выгрузки не вызываем, так как это удалит незакоммиченные блоки
+        enable_block_mode(http_client, url).await?;
+    } else {
+        info!(
+            "Microsoft Azure: resume uploading, {} uncommitted blocks found",
+            uploaded_blocks.len()
+        );
+    }
 
     // Подготавливаем файлик для потоковой выгрузки
     let mut source_file = File::open(file_path).await?;
@@ -201,12 +325,18 @@ pub async fn perform_blob_file_uploading(
 
     // Создаем каналы для задач и результатов
     let (task_sender, task_receiver) =
-        async_channel::bounded::<UploadTask>(UPLOAD_THREADS_COUNT * 2);
+        async_channel::bounded::<UploadTask>(options.concurrency * 2);
     let (result_sender, mut result_receiver) =
-        mpsc::channel::<Result<UploadResult, MicrosoftAzureError>>(UPLOAD_THREADS_COUNT * 8);
+        mpsc::channel::<Result<UploadResult, MicrosoftAzureError>>(options.concurrency * 8);
 
     // Создаем воркеры для отгрузки
-    spawn_uploaders(http_client, url, task_receiver, result_sender);
+    spawn_uploaders(
+        http_client,
+        url,
+        options.concurrency,
+        task_receiver,
+        result_sender,
+    );
 
     // Массив с результатами
     let mut blocks = Vec::<UploadResult>::new();
@@ -216,11 +346,13 @@ pub async fn perform_blob_file_uploading(
     let mut index = 0;
     loop {
         // Размер буффера
-        let buffer_size_limit = std::cmp::min(BUFFER_MAX_SIZE, data_left);
+        let buffer_size_limit = std::cmp::min(options.block_size as i64, data_left);
         if buffer_size_limit <= 0 {
             break;
         }
 
+        let offset = source_file_length - data_left as u64;
+
         // TODO: Убрать создание нового буффера каждый раз,
         // Вроде бы как Hyper позволяет использовать slice для выгрузки
         let mut buffer = vec![0_u8; buffer_size_limit as usize];
@@ -234,11 +366,24 @@ pub async fn perform_blob_file_uploading(
         // Обрезаем буффер на нужный размер
         buffer.truncate(read_size);
 
+        // Контрольная сумма нужна и для id блока, и для проверки на стороне сервера
+        let md5_digest = md5::compute(&buffer);
+        let block_id = make_block_id(offset, &md5_digest);
+
+        // Такой блок уже есть на сервере, просто не отправляем его данные
+        if uploaded_blocks.get(&block_id) == Some(&(read_size as u64)) {
+            debug!("Block {} is already uploaded, skip", block_id);
+            blocks.push(UploadResult { block_id, index });
+            index += 1;
+            continue;
+        }
+
         // Отправляем задачу выгрузки
         task_sender
             .send(UploadTask {
                 data: Bytes::from(buffer),
-                block_id: format!("{:04}", index),
+                block_id,
+                md5: base64::encode(md5_digest.0),
                 index,
             })
             .await
@@ -300,7 +445,31 @@ pub async fn perform_blob_file_uploading(
 
     // Непосредственно выгрузка списка в правильном порядке
     // https://docs.microsoft.com/en-us/rest/api/storageservices/put-block-list
-    commit_blocks(http_client, url, blocks).await?;
+    commit_blocks(http_client, url, blocks, properties).await?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_id() {
+        // Одинаковое место в разных файликах дает разные id
+        let first = make_block_id(8 * 1024 * 1024, &md5::compute(b"first build"));
+        let second = make_block_id(8 * 1024 * 1024, &md5::compute(b"second build"));
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            make_block_id(8 * 1024 * 1024, &md5::compute(b"first build"))
+        );
+
+        // Длина id не зависит от смещения
+        assert_eq!(first.len(), make_block_id(0, &md5::compute(b"")).len());
+        assert_eq!(
+            first.len(),
+            make_block_id(u64::MAX, &md5::compute(b"")).len()
+        );
+    }
+}
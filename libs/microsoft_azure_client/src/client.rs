@@ -1,6 +1,14 @@
 use crate::{
-    error::MicrosoftAzureError, flight_submission::FlightSubmission,
-    production_submission::ProductionSubmission, request_builder::RequestBuilder,
+    blob_uploader::BlobUploadOptions,
+    error::MicrosoftAzureError,
+    flight_submission::{list_flights, FlightSubmission},
+    listing_update::ListingUpdate,
+    package_rollout::manage_published_rollout,
+    production_submission::ProductionSubmission,
+    publish_options::{PackageRolloutAction, ProductionPublishOptions},
+    request_builder::RequestBuilder,
+    responses::{FlightInfoResponse, PackageRollout, SubmissionStatusResponse},
+    submission_helpers::{CertificationWait, PendingSubmissionPolicy},
     token::TokenProvider,
 };
 use log::debug;
@@ -9,6 +17,7 @@ use std::path::Path;
 
 pub struct MicrosoftAzureClient {
     request_builder: RequestBuilder,
+    blob_upload_options: BlobUploadOptions,
 }
 
 impl MicrosoftAzureClient {
@@ -27,7 +36,16 @@ impl MicrosoftAzureClient {
         // Уже с провайдером токенов создаем билдер запросов
         let request_builder = RequestBuilder::new(http_client, token_provider, application_id);
 
-        Ok(MicrosoftAzureClient { request_builder })
+        Ok(MicrosoftAzureClient {
+            request_builder,
+            blob_upload_options: Default::default(),
+        })
+    }
+
+    /// Меняем размер блоков и количество потоков выгрузки файлика
+    pub fn with_blob_upload_options(mut self, options: BlobUploadOptions) -> MicrosoftAzureClient {
+        self.blob_upload_options = options;
+        self
     }
 
     /// Непосредственно выгружаем архив с билдом
@@ -36,23 +54,37 @@ impl MicrosoftAzureClient {
         zip_upload_file_path: &Path,
         groups: Vec<String>,
         test_flight_name: String,
-    ) -> Result<(), MicrosoftAzureError> {
+        rank_higher_than: Option<String>,
+        pending_policy: PendingSubmissionPolicy,
+        certification_wait: Option<&CertificationWait>,
+    ) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
         // https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-flights
         // https://docs.microsoft.com/en-us/windows/uwp/monetize/python-code-examples-for-the-windows-store-submission-api
 
         // Создаем новый Submission для данного приложения
         debug!("Microsoft Azure: flight submission create try");
-        let mut submission =
-            FlightSubmission::start_new(self.request_builder.clone(), groups, test_flight_name)
-                .await?;
+        let mut submission = FlightSubmission::start_new(
+            self.request_builder.clone(),
+            groups,
+            test_flight_name,
+            rank_higher_than,
+            pending_policy,
+        )
+        .await?;
         debug!("Microsoft Azure: flight submission created");
 
         // Выполняем выгрузку файлика
         debug!("Microsoft Azure: File uploading start");
-        submission.upload_build(zip_upload_file_path).await?;
+        let status = submission
+            .upload_build(
+                zip_upload_file_path,
+                &self.blob_upload_options,
+                certification_wait,
+            )
+            .await?;
         debug!("Microsoft Azure: File uploading finished");
 
-        Ok(())
+        Ok(status)
     }
 
     /// Непосредственно выгружаем архив с билдом
@@ -60,22 +92,47 @@ impl MicrosoftAzureClient {
         &self,
         zip_upload_file_path: &Path,
         submission_name: String,
-    ) -> Result<(), MicrosoftAzureError> {
+        publish_options: &ProductionPublishOptions,
+        listings: &[ListingUpdate],
+        pending_policy: PendingSubmissionPolicy,
+        certification_wait: Option<&CertificationWait>,
+    ) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
         // https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-flights
         // https://docs.microsoft.com/en-us/windows/uwp/monetize/python-code-examples-for-the-windows-store-submission-api
 
         // Создаем новый Submission для данного приложения
         debug!("Microsoft Azure: production submission create try");
-        let mut submission = ProductionSubmission::start_new(self.request_builder.clone()).await?;
+        let mut submission =
+            ProductionSubmission::start_new(self.request_builder.clone(), pending_policy).await?;
         debug!("Microsoft Azure: production submission created");
 
         // Выполняем выгрузку файлика
         debug!("Microsoft Azure: File uploading start");
-        submission
-            .upload_build(zip_upload_file_path, submission_name)
+        let status = submission
+            .upload_build(
+                zip_upload_file_path,
+                submission_name,
+                publish_options,
+                listings,
+                &self.blob_upload_options,
+                certification_wait,
+            )
             .await?;
         debug!("Microsoft Azure: File uploading finished");
 
-        Ok(())
+        Ok(status)
+    }
+
+    /// Получаем список всех flight приложения
+    pub async fn list_flights(&self) -> Result<Vec<FlightInfoResponse>, MicrosoftAzureError> {
+        list_flights(&self.request_builder).await
+    }
+
+    /// Управляем постепенной раскаткой последней опубликованной сабмиссии
+    pub async fn manage_published_rollout(
+        &self,
+        action: &PackageRolloutAction,
+    ) -> Result<PackageRollout, MicrosoftAzureError> {
+        manage_published_rollout(&self.request_builder, action).await
     }
 }
@@ -81,6 +81,27 @@ quick_error! {
             display("No file for store in provided .zip archive")
         }
 
+        /// В пакете не нашлось манифеста
+        AppxManifestIsMissing(file_name: String){
+            display("AppxManifest is missing in package: {}", file_name)
+        }
+
+        /// Манифест пакета без нужных данных
+        InvalidAppxManifest{file_name: String, info: String}{
+            display("Invalid AppxManifest in package {}: {}", file_name, info)
+        }
+
+        /// Ошибка парсинга XML манифеста
+        XmlParseError(err: roxmltree::Error){
+            from()
+            display("{}", err)
+        }
+
+        /// Пакеты не прошли проверку перед выгрузкой
+        PackageValidationFailed(info: String){
+            display("Package validation failed: {}", info)
+        }
+
         /// Получили какой-то кривой статус коммита
         InvalidCommitStatus(status: String){
             display("Unknown commit status: {}", status)
@@ -91,6 +112,31 @@ quick_error! {
             display("{:?}", response_data)
         }
 
+        /// Сабмиссия не прошла сертификацию или публикацию
+        CertificationFailed(response_data: SubmissionStatusResponse){
+            display("Submission certification failed. {}", response_data)
+        }
+
+        /// Существующий flight с таким именем настроен иначе, а изменить его через API нельзя
+        FlightSettingsMismatch(flight_name: String, info: String){
+            display("Existing flight '{}' differs from requested: {}", flight_name, info)
+        }
+
+        /// Уже есть незавершенная сабмиссия, а политика запрещает ее трогать
+        PendingSubmissionExists(submission_id: String){
+            display("Pending submission already exists: {}", submission_id)
+        }
+
+        /// У приложения нет опубликованной сабмиссии
+        NoPublishedSubmission{
+            display("Application has no published submission")
+        }
+
+        /// Не дождались окончания сертификации
+        CertificationTimeout(response_data: SubmissionStatusResponse){
+            display("Submission certification wait timeout. {}", response_data)
+        }
+
         // /// Проблема с Mutex в корутине выгрузки
         // MutexError{
 
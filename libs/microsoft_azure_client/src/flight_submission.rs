@@ -1,13 +1,16 @@
 use crate::{
-    blob_uploader::perform_blob_file_uploading,
+    blob_uploader::{perform_blob_file_uploading, BlobUploadOptions},
     error::MicrosoftAzureError,
     helpers::find_appx_filenames_in_zip,
     request_builder::RequestBuilder,
     responses::{
         DataOrErrorResponse, FlightCreateResponse, FlightInfoResponse,
-        FlightSubmissionsCreateResponse,
+        FlightSubmissionsCreateResponse, FlightsListResponse, SubmissionStatusResponse,
+    },
+    submission_helpers::{
+        commit_changes, delete_submission, wait_certification_finished, wait_commit_finished,
+        CertificationWait, PendingSubmissionPolicy,
     },
-    submission_helpers::{commit_changes, wait_commit_finished},
 };
 use log::debug;
 use serde_json::json;
@@ -20,41 +23,119 @@ pub struct FlightSubmission {
     data: FlightSubmissionsCreateResponse,
 }
 
-impl FlightSubmission {
-    /// Инициализируем новый экземпляр выливки
-    pub async fn start_new(
-        request_builder: RequestBuilder,
-        groups: Vec<String>,
-        test_flight_name: String,
-    ) -> Result<FlightSubmission, MicrosoftAzureError> {
-        // Выполняем запрос создания нового сабмишена
-        // https://docs.microsoft.com/en-us/windows/uwp/monetize/create-a-flight
-        let new_flight_info = request_builder
+/// Получаем список всех flight для приложения
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/get-flights-for-an-app`
+pub async fn list_flights(
+    request_builder: &RequestBuilder,
+) -> Result<Vec<FlightInfoResponse>, MicrosoftAzureError> {
+    const PAGE_SIZE: usize = 100;
+
+    let mut flights = Vec::new();
+    loop {
+        let page = request_builder
             .clone()
-            .method(reqwest::Method::POST)
-            .join_path("flights".to_owned())
+            .method(reqwest::Method::GET)
+            .join_path("listflights".to_owned())
             .build()
             .await?
-            .json(&json!({
-                "groupIds": groups,
-                "friendlyName": test_flight_name,
-                // "rankHigherThan": null
-            }))
+            .query(&[("top", PAGE_SIZE), ("skip", flights.len())])
             .send()
             .await?
             .text()
             .await?
-            .parse_json_with_data_err::<DataOrErrorResponse<FlightCreateResponse>>()?
+            .parse_json_with_data_err::<DataOrErrorResponse<FlightsListResponse>>()?
             .into_result()?;
-        debug!(
-            "Microsoft Azure, new flight response: {:#?}",
-            new_flight_info
-        );
 
-        // Создаем новый реквест билдер на основании старого, но уже с полученным submission id
-        let request_builder = request_builder
-            .clone()
-            .flight_id(new_flight_info.flight_id.clone());
+        let received = page.value.len();
+        flights.extend(page.value);
+
+        if received == 0 || flights.len() >= page.total_count {
+            break;
+        }
+    }
+    debug!("Microsoft Azure, flights list: {:#?}", flights);
+
+    Ok(flights)
+}
+
+impl FlightSubmission {
+    /// Инициализируем новый экземпляр выливки
+    pub async fn start_new(
+        request_builder: RequestBuilder,
+        groups: Vec<String>,
+        test_flight_name: String,
+        rank_higher_than: Option<String>,
+        pending_policy: PendingSubmissionPolicy,
+    ) -> Result<FlightSubmission, MicrosoftAzureError> {
+        // Ищем существующий flight с таким же именем, чтобы не плодить новые
+        let existing_flight = list_flights(&request_builder)
+            .await?
+            .into_iter()
+            .find(|flight| flight.friendly_name.eq(&test_flight_name));
+
+        let flight_id = match existing_flight {
+            Some(flight) => {
+                debug!(
+                    "Microsoft Azure, existing flight found: {}",
+                    flight.flight_id
+                );
+                // API не позволяет изменить flight, поэтому молча выливать с другими
+                // группами или приоритетом нельзя, пусть лучше поменяют имя или удалят flight
+                let mut existing_groups = flight.group_ids.clone();
+                existing_groups.sort();
+                let mut requested_groups = groups.clone();
+                requested_groups.sort();
+                if existing_groups != requested_groups {
+                    return Err(MicrosoftAzureError::FlightSettingsMismatch(
+                        test_flight_name,
+                        format!("groups {:?}, requested {:?}", flight.group_ids, groups),
+                    ));
+                }
+                if rank_higher_than.is_some() && flight.rank_higher_than != rank_higher_than {
+                    return Err(MicrosoftAzureError::FlightSettingsMismatch(
+                        test_flight_name,
+                        format!(
+                            "rank higher than {:?}, requested {:?}",
+                            flight.rank_higher_than, rank_higher_than
+                        ),
+                    ));
+                }
+                flight.flight_id
+            }
+            None => {
+                // Выполняем запрос создания нового flight
+                // https://docs.microsoft.com/en-us/windows/uwp/monetize/create-a-flight
+                let mut body = json!({
+                    "groupIds": groups,
+                    "friendlyName": test_flight_name,
+                });
+                if let Some(rank_higher_than) = rank_higher_than {
+                    body["rankHigherThan"] = json!(rank_higher_than);
+                }
+
+                let new_flight_info = request_builder
+                    .clone()
+                    .method(reqwest::Method::POST)
+                    .join_path("flights".to_owned())
+                    .build()
+                    .await?
+                    .json(&body)
+                    .send()
+                    .await?
+                    .text()
+                    .await?
+                    .parse_json_with_data_err::<DataOrErrorResponse<FlightCreateResponse>>()?
+                    .into_result()?;
+                debug!(
+                    "Microsoft Azure, new flight response: {:#?}",
+                    new_flight_info
+                );
+                new_flight_info.flight_id
+            }
+        };
+
+        // Создаем новый реквест билдер на основании старого, но уже с полученным flight id
+        let request_builder = request_builder.clone().flight_id(flight_id);
 
         // Получим информацию для данного flightId
         let flight_info = request_builder
@@ -70,23 +151,22 @@ impl FlightSubmission {
             .into_result()?;
         debug!("Microsoft Azure, flight info: {:#?}", flight_info);
 
-        // Если есть какие-то ожидающие сабмиссии
-        let new_submission_info = if let Some(pending) = flight_info.pending_flight_submission {
-            // Удаляем сабмиссии
-            /*let resp = request_builder
-                .clone()
-                .submission_id(pending.id)
-                .method(reqwest::Method::DELETE)
-                .build()
-                .await?
-                .send()
-                .await?;
-            if !resp.status().is_success() {
-                let err = resp.json::<ErrorResponseValue>().await?;
-                return Err(MicrosoftAzureError::RestApiResponseError(SpanTrace::capture(), err));
+        // Если есть какие-то ожидающие сабмиссии, то поступаем с ними согласно политике
+        let pending = match (flight_info.pending_flight_submission, pending_policy) {
+            (Some(pending), PendingSubmissionPolicy::Reuse) => Some(pending),
+            (Some(pending), PendingSubmissionPolicy::Delete) => {
+                // https://docs.microsoft.com/en-us/windows/uwp/monetize/delete-a-flight-submission
+                delete_submission(&request_builder.clone().submission_id(pending.id)).await?;
+                debug!("Microsoft Azure, flight submission delete success");
+                None
+            }
+            (Some(pending), PendingSubmissionPolicy::Fail) => {
+                return Err(MicrosoftAzureError::PendingSubmissionExists(pending.id));
             }
-            debug!("Microsoft Azure, flight submission delete success");*/
+            (None, _) => None,
+        };
 
+        let new_submission_info = if let Some(pending) = pending {
             // Данные по имеющейся сабмиссии
             let info = request_builder
                 .clone()
@@ -139,7 +219,12 @@ impl FlightSubmission {
     }
 
     /// Выгружаем наш билд
-    pub async fn upload_build(&mut self, zip_file_path: &Path) -> Result<(), MicrosoftAzureError> {
+    pub async fn upload_build(
+        &mut self,
+        zip_file_path: &Path,
+        upload_options: &BlobUploadOptions,
+        certification_wait: Option<&CertificationWait>,
+    ) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
         // Может быть нет фалика по этому пути
         if !zip_file_path.exists() {
             return Err(MicrosoftAzureError::NoFile(zip_file_path.to_owned()));
@@ -188,14 +273,25 @@ impl FlightSubmission {
         let append_data_url = reqwest::Url::parse(&self.data.file_upload_url)?;
 
         // Выполняем непосредственно выгрузку на сервер нашего архива
-        perform_blob_file_uploading(&http_client, &append_data_url, zip_file_path).await?;
+        perform_blob_file_uploading(
+            &http_client,
+            &append_data_url,
+            zip_file_path,
+            upload_options,
+            &Default::default(),
+        )
+        .await?;
 
         // Пытаемся закоммитить
         commit_changes(&self.request_builder).await?;
 
         // Ждем завершения коммита
-        wait_commit_finished(&self.request_builder).await?;
+        let status = wait_commit_finished(&self.request_builder).await?;
 
-        Ok(())
+        // Если надо, ждем еще и прохождения сертификации
+        match certification_wait {
+            Some(wait) => wait_certification_finished(&self.request_builder, wait).await,
+            None => Ok(status),
+        }
     }
 }
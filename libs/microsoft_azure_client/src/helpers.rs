@@ -1,6 +1,6 @@
-use crate::error::MicrosoftAzureError;
-use std::path::Path;
+use crate::{appx_manifest::is_package_file, error::MicrosoftAzureError};
 use log::debug;
+use std::path::Path;
 
 /// Данная функция проверяет, что расширение файлика совпадает с указанным
 pub fn check_file_extention(path: &Path, required_extention: &str) -> bool {
@@ -16,7 +16,7 @@ pub fn check_file_extention(path: &Path, required_extention: &str) -> bool {
         .is_some()
 }
 
-/// Ищем внутри архива файлики .appx / .appxupload
+/// Ищем внутри архива файлики пакетов: .appx / .msix, бандлы и .appxupload / .msixupload
 pub fn find_appx_filenames_in_zip(
     zip_file_path: &Path,
 ) -> Result<Vec<String>, MicrosoftAzureError> {
@@ -29,17 +29,11 @@ pub fn find_appx_filenames_in_zip(
     let filenames_in_zip: Vec<_> = zip
         .file_names()
         .filter(|full_path_str| {
-            let file_name = std::path::Path::new(full_path_str)
-                .file_name()
-                .and_then(|f| f.to_str());
-            if let Some(file_name) = file_name {
-                !file_name.starts_with('.')
-                    && (file_name.ends_with(".appx")
-                        || file_name.ends_with(".appxupload")
-                        || file_name.ends_with(".msixupload")
-                        || file_name.ends_with(".msix"))
-            } else {
-                false
+            let path = Path::new(full_path_str);
+            match path.file_name().and_then(|f| f.to_str()) {
+                // Символы .appxsym отдельно не регистрируются, поэтому их здесь нет
+                Some(file_name) => !file_name.starts_with('.') && is_package_file(path),
+                None => false,
             }
         })
         .map(|v| v.to_owned())
@@ -1,12 +1,35 @@
+mod appx_manifest;
+mod blob_storage;
+mod blob_uploader;
 mod client;
+mod error;
+mod flight_submission;
+mod helpers;
+mod listing_update;
+mod package_archive;
+mod package_rollout;
+mod production_submission;
+mod publish_options;
 mod request_builder;
 mod responses;
-mod token;
 mod submission_helpers;
-mod flight_submission;
-mod production_submission;
-mod error;
-mod helpers;
-mod blob_uploader;
+mod token;
 
-pub use self::client::MicrosoftAzureClient;
+pub use self::{
+    appx_manifest::{read_package_info, validate_packages, AppxPackageInfo, PackageRequirements},
+    blob_storage::BlobStorageClient,
+    blob_uploader::{BlobProperties, BlobUploadOptions},
+    client::MicrosoftAzureClient,
+    error::MicrosoftAzureError,
+    listing_update::{ListingImage, ListingUpdate},
+    package_archive::{build_upload_archive, UploadArchive},
+    publish_options::{
+        MandatoryUpdate, PackageRolloutAction, ProductionPublishOptions, PublishMode,
+    },
+    responses::{
+        FlightInfoResponse, FlightInfoSubmission, PackageRollout,
+        SubmissionCreateSertificationReport, SubmissionCreateStatusDetailInfo,
+        SubmissionStatusDetails, SubmissionStatusResponse,
+    },
+    submission_helpers::{CertificationWait, PendingSubmissionPolicy},
+};
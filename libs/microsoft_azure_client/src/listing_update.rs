@@ -0,0 +1,93 @@
+use crate::{
+    error::MicrosoftAzureError,
+    responses::{BaseListing, SubmissionCommonData, SubmissionListing, SubmissionListingImage},
+};
+use std::path::PathBuf;
+
+/// Новая картинка листинга, сам файлик должен лежать в корне выгружаемого архива
+#[derive(Debug, Clone)]
+pub struct ListingImage {
+    /// Тип картинки, например "Screenshot" или "StoreLogo300x300"
+    pub image_type: String,
+    pub file_path: PathBuf,
+}
+
+/// Изменения описания приложения для конкретного языка,
+/// незаданные поля остаются такими же, как в предыдущей сабмиссии,
+/// для нового языка создается новый листинг
+#[derive(Debug, Clone, Default)]
+pub struct ListingUpdate {
+    /// Язык листинга, например "en-us"
+    pub language: String,
+    /// Текст "What's new in this version"
+    pub release_notes: Option<String>,
+    pub description: Option<String>,
+    pub features: Option<Vec<String>>,
+    /// Картинки добавляются к уже существующим
+    pub images: Vec<ListingImage>,
+}
+
+/// Применяем изменения к листингам новой сабмиссии
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#listing-object`
+pub(crate) fn apply_listing_updates(
+    data: &mut SubmissionCommonData,
+    updates: &[ListingUpdate],
+) -> Result<(), MicrosoftAzureError> {
+    if updates.is_empty() {
+        return Ok(());
+    }
+
+    let listings = data.listings.get_or_insert_with(Default::default);
+
+    for update in updates.iter() {
+        // Языки в ответе могут быть в другом регистре
+        let existing_language = listings
+            .keys()
+            .find(|language| language.eq_ignore_ascii_case(&update.language))
+            .cloned();
+
+        // Листинга для нового языка еще нет, создаем пустой
+        let listing = listings
+            .entry(existing_language.unwrap_or_else(|| update.language.clone()))
+            .or_insert_with(|| SubmissionListing {
+                base_listing: BaseListing {
+                    release_notes: None,
+                    description: None,
+                    features: None,
+                    images: None,
+                    other_fields: Default::default(),
+                },
+                other_fields: Default::default(),
+            });
+
+        let base = &mut listing.base_listing;
+        if let Some(release_notes) = &update.release_notes {
+            base.release_notes = Some(release_notes.clone());
+        }
+        if let Some(description) = &update.description {
+            base.description = Some(description.clone());
+        }
+        if let Some(features) = &update.features {
+            base.features = Some(features.clone());
+        }
+        for image in update.images.iter() {
+            let file_name = image
+                .file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| MicrosoftAzureError::NoFile(image.file_path.clone()))?;
+
+            // Без ссылки из листинга магазин просто игнорирует файлик в архиве
+            base.images
+                .get_or_insert_with(Vec::new)
+                .push(SubmissionListingImage {
+                    file_name: file_name.to_owned(),
+                    file_status: "PendingUpload".to_owned(),
+                    image_type: image.image_type.clone(),
+                    other_fields: Default::default(),
+                });
+        }
+    }
+
+    Ok(())
+}
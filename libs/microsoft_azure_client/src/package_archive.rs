@@ -0,0 +1,218 @@
+use crate::{
+    appx_manifest::{
+        is_package_file, read_package_info, validate_packages, AppxPackageInfo, PackageRequirements,
+    },
+    error::MicrosoftAzureError,
+};
+use log::{debug, info};
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+/// Архив для выгрузки, собранный во временной директории,
+/// директория удаляется вместе с объектом
+pub struct UploadArchive {
+    _temp_dir: TempDir,
+    zip_path: PathBuf,
+    pub packages: Vec<AppxPackageInfo>,
+}
+
+impl UploadArchive {
+    pub fn path(&self) -> &Path {
+        &self.zip_path
+    }
+}
+
+/// Собираем .zip для выгрузки из файликов пакетов или директорий с ними,
+/// в директориях пакеты ищутся без рекурсии, картинки листинга кладутся в корень архива
+pub async fn build_upload_archive(
+    inputs: Vec<PathBuf>,
+    listing_images: Vec<PathBuf>,
+    requirements: PackageRequirements,
+) -> Result<UploadArchive, MicrosoftAzureError> {
+    tokio::task::spawn_blocking(move || {
+        build_upload_archive_blocking(&inputs, &listing_images, &requirements)
+    })
+    .await
+    .map_err(|err| MicrosoftAzureError::UploadingError(err.to_string()))?
+}
+
+fn collect_package_files(inputs: &[PathBuf]) -> Result<Vec<PathBuf>, MicrosoftAzureError> {
+    let mut files = Vec::new();
+    for input in inputs.iter() {
+        if input.is_dir() {
+            let mut dir_files = std::fs::read_dir(input)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            dir_files.retain(|path| path.is_file() && is_package_file(path));
+            dir_files.sort();
+            files.extend(dir_files);
+        } else if input.is_file() {
+            if !is_package_file(input) {
+                return Err(MicrosoftAzureError::InvalidUploadFileExtention);
+            }
+            files.push(input.clone());
+        } else {
+            return Err(MicrosoftAzureError::NoFile(input.clone()));
+        }
+    }
+    Ok(files)
+}
+
+fn build_upload_archive_blocking(
+    inputs: &[PathBuf],
+    listing_images: &[PathBuf],
+    requirements: &PackageRequirements,
+) -> Result<UploadArchive, MicrosoftAzureError> {
+    let package_files = collect_package_files(inputs)?;
+    debug!("Microsoft Azure: package files {:?}", package_files);
+
+    // Проверяем метаданные еще до сборки архива
+    let packages = package_files
+        .iter()
+        .map(|path| read_package_info(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    validate_packages(&packages, requirements)?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let zip_path = temp_dir.path().join("upload.zip");
+    let mut writer = ZipWriter::new(File::create(&zip_path)?);
+
+    // Пакеты и так сжаты, поэтому просто складываем
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(true);
+
+    // Имя в корне архива -> канонический путь к файлику
+    let mut names: HashMap<String, PathBuf> = HashMap::new();
+    for path in package_files.iter().chain(listing_images.iter()) {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| MicrosoftAzureError::NoFile(path.clone()))?;
+        let canonical_path = path.canonicalize()?;
+
+        // Одна и та же картинка может использоваться в нескольких языках, кладем ее один раз,
+        // а вот разные файлики с одинаковыми именами в корне архива различить нельзя
+        match names.get(name) {
+            Some(existing) if existing == &canonical_path => continue,
+            Some(existing) => {
+                return Err(MicrosoftAzureError::PackageValidationFailed(format!(
+                    "duplicate file name in archive: {} ({} and {})",
+                    name,
+                    existing.display(),
+                    canonical_path.display()
+                )));
+            }
+            None => {
+                names.insert(name.to_owned(), canonical_path);
+            }
+        }
+
+        writer.start_file(name, options)?;
+        std::io::copy(&mut File::open(path)?, &mut writer)?;
+    }
+    writer.finish()?;
+
+    info!(
+        "Microsoft Azure: upload archive created {} with packages {:?}",
+        zip_path.display(),
+        packages
+    );
+
+    Ok(UploadArchive {
+        _temp_dir: temp_dir,
+        zip_path,
+        packages,
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::find_appx_filenames_in_zip;
+    use std::io::Write;
+
+    /// Минимальный .appxbundle с одним пакетом внутри
+    fn write_test_bundle(dir: &Path) -> PathBuf {
+        let bundle_manifest = br#"<?xml version="1.0" encoding="utf-8"?>
+            <Bundle xmlns="http://schemas.microsoft.com/appx/2013/bundle">
+              <Identity Name="Company.Game" Publisher="CN=Company" Version="1.2.3.0" />
+              <Packages>
+                <Package Type="application" Version="1.2.3.0" Architecture="x64" FileName="Game_x64.appx" />
+              </Packages>
+            </Bundle>"#;
+        let bundle_path = dir.join("Game.appxbundle");
+        let mut writer = ZipWriter::new(File::create(&bundle_path).unwrap());
+        writer
+            .start_file(
+                "AppxMetadata/AppxBundleManifest.xml",
+                FileOptions::default(),
+            )
+            .unwrap();
+        writer.write_all(bundle_manifest).unwrap();
+        writer.finish().unwrap();
+
+        bundle_path
+    }
+
+    #[test]
+    fn test_bundle_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle_path = write_test_bundle(dir.path());
+
+        let image_path = dir.path().join("screenshot.png");
+        std::fs::write(&image_path, b"png").unwrap();
+
+        // Картинка попадает в архив, но пакетом не считается
+        let archive =
+            build_upload_archive_blocking(&[bundle_path], &[image_path], &Default::default())
+                .unwrap();
+        assert_eq!(archive.packages.len(), 1);
+        assert_eq!(
+            find_appx_filenames_in_zip(archive.path()).unwrap(),
+            vec!["Game.appxbundle".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_shared_listing_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle_path = write_test_bundle(dir.path());
+
+        let image_path = dir.path().join("screenshot.png");
+        std::fs::write(&image_path, b"png").unwrap();
+        let other_dir = dir.path().join("other");
+        std::fs::create_dir(&other_dir).unwrap();
+        let other_image_path = other_dir.join("screenshot.png");
+        std::fs::write(&other_image_path, b"png").unwrap();
+
+        // Одна и та же картинка для нескольких языков кладется в архив один раз
+        let same_images = [image_path.clone(), other_dir.join("../screenshot.png")];
+        let archive = build_upload_archive_blocking(
+            std::slice::from_ref(&bundle_path),
+            &same_images,
+            &Default::default(),
+        )
+        .unwrap();
+        let zip = zip::ZipArchive::new(File::open(archive.path()).unwrap()).unwrap();
+        let mut file_names = zip.file_names().collect::<Vec<_>>();
+        file_names.sort_unstable();
+        assert_eq!(file_names, vec!["Game.appxbundle", "screenshot.png"]);
+
+        // Разные файлики с одним именем - ошибка
+        let different_images = [image_path, other_image_path];
+        let result =
+            build_upload_archive_blocking(&[bundle_path], &different_images, &Default::default());
+        assert!(matches!(
+            result,
+            Err(MicrosoftAzureError::PackageValidationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    error::MicrosoftAzureError,
+    publish_options::PackageRolloutAction,
+    request_builder::RequestBuilder,
+    responses::{DataOrErrorResponse, PackageRollout},
+    submission_helpers::get_application_info,
+};
+use log::debug;
+use serde_json_string_parse::ParseJson;
+
+/// Получаем идентификатор последней опубликованной сабмиссии
+async fn get_published_submission_id(
+    request_builder: &RequestBuilder,
+) -> Result<String, MicrosoftAzureError> {
+    get_application_info(request_builder)
+        .await?
+        .last_published_application_submission
+        .map(|submission| submission.id)
+        .ok_or(MicrosoftAzureError::NoPublishedSubmission)
+}
+
+/// Выполняем действие с раскаткой опубликованной сабмиссии
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#manage-a-gradual-package-rollout`
+pub async fn manage_published_rollout(
+    request_builder: &RequestBuilder,
+    action: &PackageRolloutAction,
+) -> Result<PackageRollout, MicrosoftAzureError> {
+    let submission_id = get_published_submission_id(request_builder).await?;
+    debug!(
+        "Microsoft Azure: rollout action {:?} for submission {}",
+        action, submission_id
+    );
+
+    let request_builder = request_builder.clone().submission_id(submission_id);
+
+    let request = match action {
+        PackageRolloutAction::Update(percentage) => request_builder
+            .method(reqwest::Method::POST)
+            .submission_command("updatepackagerolloutpercentage".to_owned())
+            .build()
+            .await?
+            .query(&[("percentage", percentage)]),
+        PackageRolloutAction::Halt => {
+            request_builder
+                .method(reqwest::Method::POST)
+                .submission_command("haltpackagerollout".to_owned())
+                .build()
+                .await?
+        }
+        PackageRolloutAction::Finalize => {
+            request_builder
+                .method(reqwest::Method::POST)
+                .submission_command("finalizepackagerollout".to_owned())
+                .build()
+                .await?
+        }
+    };
+
+    let rollout = request
+        .header(reqwest::header::CONTENT_LENGTH, "0")
+        .send()
+        .await?
+        .text()
+        .await?
+        .parse_json_with_data_err::<DataOrErrorResponse<PackageRollout>>()?
+        .into_result()?;
+    debug!("Microsoft Azure: rollout response {:#?}", rollout);
+
+    Ok(rollout)
+}
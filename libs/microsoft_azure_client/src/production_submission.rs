@@ -1,15 +1,20 @@
 use crate::responses::AppPackage;
 use crate::{
-    blob_uploader::perform_blob_file_uploading,
+    blob_uploader::{perform_blob_file_uploading, BlobUploadOptions},
     error::MicrosoftAzureError,
     helpers::find_appx_filenames_in_zip,
+    listing_update::{apply_listing_updates, ListingUpdate},
+    publish_options::ProductionPublishOptions,
     request_builder::RequestBuilder,
-    responses::{DataOrErrorResponse, SubmissionCreateResponse},
-    submission_helpers::{commit_changes, wait_commit_finished},
+    responses::{DataOrErrorResponse, SubmissionCreateResponse, SubmissionStatusResponse},
+    submission_helpers::{
+        commit_changes, delete_submission, get_application_info, wait_certification_finished,
+        wait_commit_finished, CertificationWait, PendingSubmissionPolicy,
+    },
 };
+use log::debug;
 use serde_json_string_parse::ParseJson;
 use std::path::Path;
-use log::debug;
 
 /// Внутренняя структура по работе с submission
 pub struct ProductionSubmission {
@@ -21,7 +26,48 @@ impl ProductionSubmission {
     /// Инициализируем новый экземпляр выливки
     pub async fn start_new(
         request_builder: RequestBuilder,
+        pending_policy: PendingSubmissionPolicy,
     ) -> Result<ProductionSubmission, MicrosoftAzureError> {
+        // Новая сабмиссия не создастся, если уже есть незавершенная
+        let app_info = get_application_info(&request_builder).await?;
+        if let Some(pending) = app_info.pending_application_submission {
+            debug!(
+                "Microsoft Azure: pending submission {} found, policy {:?}",
+                pending.id, pending_policy
+            );
+
+            let pending_request_builder = request_builder.clone().submission_id(pending.id.clone());
+            match pending_policy {
+                PendingSubmissionPolicy::Reuse => {
+                    // https://docs.microsoft.com/en-us/windows/uwp/monetize/get-an-app-submission
+                    let data = pending_request_builder
+                        .clone()
+                        .method(reqwest::Method::GET)
+                        .build()
+                        .await?
+                        .send()
+                        .await?
+                        .text()
+                        .await?
+                        .parse_json_with_data_err::<DataOrErrorResponse<SubmissionCreateResponse>>(
+                        )?
+                        .into_result()?;
+                    debug!("Microsoft Azure, pending submission response: {:#?}", data);
+
+                    return Ok(ProductionSubmission {
+                        request_builder: pending_request_builder,
+                        data,
+                    });
+                }
+                PendingSubmissionPolicy::Delete => {
+                    delete_submission(&pending_request_builder).await?;
+                }
+                PendingSubmissionPolicy::Fail => {
+                    return Err(MicrosoftAzureError::PendingSubmissionExists(pending.id));
+                }
+            }
+        }
+
         // Выполняем запрос создания нового сабмишена
         // https://docs.microsoft.com/en-us/windows/uwp/monetize/create-a-flight
         let data = request_builder
@@ -29,14 +75,11 @@ impl ProductionSubmission {
             .method(reqwest::Method::POST)
             .join_path("submissions".to_owned())
             .build()
-            
             .await?
             .header(reqwest::header::CONTENT_LENGTH, "0")
             .send()
-            
             .await?
             .text()
-            
             .await?
             .parse_json_with_data_err::<DataOrErrorResponse<SubmissionCreateResponse>>()?
             .into_result()?;
@@ -55,7 +98,11 @@ impl ProductionSubmission {
         &mut self,
         zip_file_path: &Path,
         submission_name: String,
-    ) -> Result<(), MicrosoftAzureError> {
+        publish_options: &ProductionPublishOptions,
+        listings: &[ListingUpdate],
+        upload_options: &BlobUploadOptions,
+        certification_wait: Option<&CertificationWait>,
+    ) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
         // Может быть нет фалика по этому пути
         if !zip_file_path.exists() {
             return Err(MicrosoftAzureError::NoFile(zip_file_path.to_owned()));
@@ -69,7 +116,10 @@ impl ProductionSubmission {
 
         // Выставляем параметры активации и имя выгрузки
         new_params.friendly_name = Some(submission_name);
-        new_params.target_publish_mode = "Manual".to_owned();
+        publish_options.apply(&mut new_params);
+
+        // Обновляем только указанные поля листингов
+        apply_listing_updates(&mut new_params, listings)?;
 
         // У старых пакетов помечаем статус необходимости удаления
         new_params.application_packages.iter_mut().for_each(|val| {
@@ -91,11 +141,9 @@ impl ProductionSubmission {
             .clone()
             .method(reqwest::Method::PUT)
             .build()
-            
             .await?
             .json(&new_params)
             .send()
-            
             .await?
             .text()
             .await?
@@ -110,20 +158,25 @@ impl ProductionSubmission {
         let append_data_url = reqwest::Url::parse(&self.data.file_upload_url)?;
 
         // Выполняем непосредственно выгрузку на сервер нашего архива
-        perform_blob_file_uploading(&http_client, &append_data_url, zip_file_path)
-            
-            .await?;
+        perform_blob_file_uploading(
+            &http_client,
+            &append_data_url,
+            zip_file_path,
+            upload_options,
+            &Default::default(),
+        )
+        .await?;
 
         // Пытаемся закоммитить
-        commit_changes(&self.request_builder)
-            
-            .await?;
+        commit_changes(&self.request_builder).await?;
 
         // Ждем завершения коммита
-        wait_commit_finished(&self.request_builder)
-            
-            .await?;
+        let status = wait_commit_finished(&self.request_builder).await?;
 
-        Ok(())
+        // Если надо, ждем еще и прохождения сертификации
+        match certification_wait {
+            Some(wait) => wait_certification_finished(&self.request_builder, wait).await,
+            None => Ok(status),
+        }
     }
 }
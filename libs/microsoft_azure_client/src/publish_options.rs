@@ -0,0 +1,87 @@
+use crate::responses::{PackageDeliveryOptions, PackageRollout, SubmissionCommonData};
+use std::collections::HashMap;
+
+/// Режим публикации после прохождения сертификации
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#app-submission-object`
+#[derive(Debug, Clone, Default)]
+pub enum PublishMode {
+    Immediate,
+    #[default]
+    Manual,
+    /// Дата в формате ISO 8601
+    SpecificDate(String),
+}
+
+/// Обязательное обновление, дата начала действия в формате ISO 8601
+#[derive(Debug, Clone, Default)]
+pub struct MandatoryUpdate {
+    pub effective_date: Option<String>,
+}
+
+/// Параметры публикации продакшен сабмиссии,
+/// незаданные параметры раскатки остаются такими же, как в предыдущей сабмиссии
+#[derive(Debug, Clone, Default)]
+pub struct ProductionPublishOptions {
+    pub publish_mode: PublishMode,
+    /// Процент пользователей для постепенной раскатки
+    pub rollout_percentage: Option<f32>,
+    pub mandatory_update: Option<MandatoryUpdate>,
+}
+
+impl ProductionPublishOptions {
+    /// Применяем параметры к данным новой сабмиссии
+    pub(crate) fn apply(&self, data: &mut SubmissionCommonData) {
+        match &self.publish_mode {
+            PublishMode::Immediate => {
+                data.target_publish_mode = "Immediate".to_owned();
+            }
+            PublishMode::Manual => {
+                data.target_publish_mode = "Manual".to_owned();
+            }
+            PublishMode::SpecificDate(date) => {
+                data.target_publish_mode = "SpecificDate".to_owned();
+                data.target_publish_date = Some(date.clone());
+            }
+        }
+
+        if self.rollout_percentage.is_none() && self.mandatory_update.is_none() {
+            return;
+        }
+
+        let delivery =
+            data.package_delivery_options
+                .get_or_insert_with(|| PackageDeliveryOptions {
+                    package_rollout: None,
+                    is_mandatory_update: false,
+                    mandatory_update_effective_date: None,
+                    other_fields: HashMap::new(),
+                });
+
+        if let Some(percentage) = self.rollout_percentage {
+            // Статус раскатки и запасную сабмиссию выставляет сервер
+            delivery.package_rollout = Some(PackageRollout {
+                is_package_rollout: true,
+                package_rollout_percentage: percentage,
+                package_rollout_status: None,
+                fallback_submission_id: None,
+                other_fields: HashMap::new(),
+            });
+        }
+
+        if let Some(mandatory_update) = &self.mandatory_update {
+            delivery.is_mandatory_update = true;
+            delivery.mandatory_update_effective_date = mandatory_update.effective_date.clone();
+        }
+    }
+}
+
+/// Действие с раскаткой уже опубликованной сабмиссии
+#[derive(Debug, Clone)]
+pub enum PackageRolloutAction {
+    /// Новый процент пользователей
+    Update(f32),
+    /// Остановить раскатку, пользователи получат запасную сабмиссию
+    Halt,
+    /// Раскатить на всех пользователей
+    Finalize,
+}
@@ -3,7 +3,11 @@ use serde::{
     Deserialize, Serialize,
 };
 use serde_json::value::Value;
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
 
 /// Вспомогательная функция для serde, чтобы конвертировать строки в u64 во время парсинга
 fn deserealize_from_str<'de, T, D>(deserializer: D) -> Result<T, D::Error>
@@ -84,10 +88,10 @@ pub struct SubmissionCreateStatusDetailInfo {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SubmissionCreateSertificationReport {
-    date: String,
+    pub date: String,
 
     #[serde(rename = "reportUrl")]
-    report_url: String,
+    pub report_url: String,
 
     #[serde(flatten)]
     pub other_fields: HashMap<String, Value>,
@@ -106,6 +110,31 @@ pub struct SubmissionStatusDetails {
     pub other_fields: HashMap<String, Value>,
 }
 
+/// Человекочитаемое описание ошибок, предупреждений и отчетов сертификации
+impl Display for SubmissionStatusDetails {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.errors.is_empty() {
+            write!(f, "\nErrors:")?;
+            for info in self.errors.iter() {
+                write!(f, "\n- {}: {}", info.code, info.details)?;
+            }
+        }
+        if !self.warnings.is_empty() {
+            write!(f, "\nWarnings:")?;
+            for info in self.warnings.iter() {
+                write!(f, "\n- {}: {}", info.code, info.details)?;
+            }
+        }
+        if !self.certification_reports.is_empty() {
+            write!(f, "\nCertification reports:")?;
+            for report in self.certification_reports.iter() {
+                write!(f, "\n- {}: {}", report.date, report.report_url)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 //////////////////////////////////////////////////////////////////////
 
 /// Данная структура представляет собой ответ после инициализации
@@ -157,7 +186,17 @@ pub struct FlightInfoResponse {
     pub group_ids: Vec<String>,
 
     #[serde(rename = "rankHigherThan")]
-    pub rank_higher_than: String,
+    pub rank_higher_than: Option<String>,
+}
+
+/// Страница списка flight для приложения
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/get-flights-for-an-app`
+#[derive(Deserialize, Debug, Clone)]
+pub struct FlightsListResponse {
+    pub value: Vec<FlightInfoResponse>,
+
+    #[serde(rename = "totalCount")]
+    pub total_count: usize,
 }
 
 //////////////////////////////////////////////////////////////////////
@@ -239,6 +278,97 @@ pub struct AppPackage {
     // pub target_device_families: Option<Vec<String>>,
 }
 
+/// Параметры постепенной раскатки пакетов
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#package-rollout-object`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PackageRollout {
+    #[serde(rename = "isPackageRollout")]
+    pub is_package_rollout: bool,
+
+    #[serde(rename = "packageRolloutPercentage")]
+    pub package_rollout_percentage: f32,
+
+    #[serde(
+        rename = "packageRolloutStatus",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub package_rollout_status: Option<String>,
+
+    #[serde(
+        rename = "fallbackSubmissionId",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fallback_submission_id: Option<String>,
+
+    #[serde(flatten)]
+    pub other_fields: HashMap<String, Value>,
+}
+
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#package-delivery-options-object`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PackageDeliveryOptions {
+    #[serde(rename = "packageRollout", skip_serializing_if = "Option::is_none")]
+    pub package_rollout: Option<PackageRollout>,
+
+    #[serde(rename = "isMandatoryUpdate", default)]
+    pub is_mandatory_update: bool,
+
+    #[serde(
+        rename = "mandatoryUpdateEffectiveDate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub mandatory_update_effective_date: Option<String>,
+
+    #[serde(flatten)]
+    pub other_fields: HashMap<String, Value>,
+}
+
+/// Основные данные листинга, остальные поля сохраняем как есть
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#base-listing-object`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BaseListing {
+    #[serde(rename = "releaseNotes", skip_serializing_if = "Option::is_none")]
+    pub release_notes: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<SubmissionListingImage>>,
+
+    #[serde(flatten)]
+    pub other_fields: HashMap<String, Value>,
+}
+
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#image-object`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SubmissionListingImage {
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+
+    #[serde(rename = "fileStatus")]
+    pub file_status: String,
+
+    #[serde(rename = "imageType")]
+    pub image_type: String,
+
+    #[serde(flatten)]
+    pub other_fields: HashMap<String, Value>,
+}
+
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/manage-app-submissions#listing-object`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SubmissionListing {
+    #[serde(rename = "baseListing")]
+    pub base_listing: BaseListing,
+
+    #[serde(flatten)]
+    pub other_fields: HashMap<String, Value>,
+}
+
 /// Данная структура представляет собой ответ после инициализации
 /// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/create-an-app-submission#response`
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -251,10 +381,25 @@ pub struct SubmissionCommonData {
     #[serde(rename = "targetPublishMode")]
     pub target_publish_mode: String,
 
+    // Дата публикации для режима SpecificDate
+    #[serde(rename = "targetPublishDate", skip_serializing_if = "Option::is_none")]
+    pub target_publish_date: Option<String>,
+
     // Пакеты
     #[serde(rename = "applicationPackages")]
     pub application_packages: Vec<AppPackage>,
 
+    // Описания приложения для разных языков
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listings: Option<HashMap<String, SubmissionListing>>,
+
+    // Постепенная раскатка и обязательное обновление
+    #[serde(
+        rename = "packageDeliveryOptions",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub package_delivery_options: Option<PackageDeliveryOptions>,
+
     // Все закомментированные поля выше просто размещаем внутри плоской структуры
     #[serde(flatten)]
     pub other_fields: serde_json::Value,
@@ -266,11 +411,6 @@ pub struct SubmissionCommonData {
 
     // pub visibility: String,
 
-    // #[serde(rename = "targetPublishDate")]
-    // pub target_date: String,
-
-    // pub listings: serde_json::Value,
-
     // #[serde(rename = "hardwarePreferences")]
     // pub hardware_preferences: serde_json::Value,
 
@@ -295,9 +435,6 @@ pub struct SubmissionCommonData {
     // #[serde(rename = "notesForCertification")]
     // pub notes_for_certification: Option<String>,
 
-    // #[serde(rename = "packageDeliveryOptions")]
-    // pub package_delivery_options: serde_json::Value,
-
     // #[serde(rename = "enterpriseLicensing")]
     // pub enterprise_licensing: String,
 
@@ -343,3 +480,31 @@ pub struct SubmissionStatusResponse {
     #[serde(rename = "statusDetails")]
     pub status_details: Option<SubmissionStatusDetails>,
 }
+
+impl Display for SubmissionStatusResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Status: {}", self.status)?;
+        if let Some(details) = self.status_details.as_ref() {
+            write!(f, "{}", details)?;
+        }
+        Ok(())
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApplicationSubmissionInfo {
+    pub id: String,
+}
+
+/// Информация о приложении
+/// Описание данных: `https://docs.microsoft.com/en-us/windows/uwp/monetize/get-an-app`
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApplicationInfoResponse {
+    #[serde(rename = "lastPublishedApplicationSubmission")]
+    pub last_published_application_submission: Option<ApplicationSubmissionInfo>,
+
+    #[serde(rename = "pendingApplicationSubmission")]
+    pub pending_application_submission: Option<ApplicationSubmissionInfo>,
+}
@@ -1,9 +1,82 @@
 use crate::{
     error::MicrosoftAzureError,
     request_builder::RequestBuilder,
-    responses::{DataOrErrorResponse, FlightSubmissionCommitResponse, SubmissionStatusResponse},
+    responses::{
+        ApplicationInfoResponse, DataOrErrorResponse, ErrorResponseValue,
+        FlightSubmissionCommitResponse, SubmissionStatusResponse,
+    },
 };
-use log::debug;
+use log::{debug, info};
+use serde_json_string_parse::ParseJson;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// Параметры ожидания прохождения сертификации
+#[derive(Debug, Clone)]
+pub struct CertificationWait {
+    /// Интервал между запросами статуса
+    pub poll_interval: Duration,
+    /// Общее максимальное время ожидания
+    pub timeout: Duration,
+}
+
+/// Что делать с уже существующей незавершенной сабмиссией
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingSubmissionPolicy {
+    /// Продолжаем работу с существующей сабмиссией
+    Reuse,
+    /// Удаляем существующую сабмиссию и создаем новую
+    Delete,
+    /// Завершаемся с ошибкой
+    Fail,
+}
+
+/// Получаем информацию о приложении
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/get-an-app`
+pub async fn get_application_info(
+    request_builder: &RequestBuilder,
+) -> Result<ApplicationInfoResponse, MicrosoftAzureError> {
+    let info = request_builder
+        .clone()
+        .method(reqwest::Method::GET)
+        .build()
+        .await?
+        .send()
+        .await?
+        .text()
+        .await?
+        .parse_json_with_data_err::<DataOrErrorResponse<ApplicationInfoResponse>>()?
+        .into_result()?;
+    debug!("Microsoft Azure: application info {:#?}", info);
+
+    Ok(info)
+}
+
+/// Удаляем сабмиссию, идентификатор которой уже есть в билдере запросов
+/// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/delete-an-app-submission`
+pub async fn delete_submission(
+    request_builder: &RequestBuilder,
+) -> Result<(), MicrosoftAzureError> {
+    let response = request_builder
+        .clone()
+        .method(reqwest::Method::DELETE)
+        .build()
+        .await?
+        .header(reqwest::header::CONTENT_LENGTH, "0")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let err = response
+            .text()
+            .await?
+            .parse_json_with_data_err::<ErrorResponseValue>()?;
+        return Err(MicrosoftAzureError::RestApiResponseError(err));
+    }
+    debug!("Microsoft Azure: submission delete success");
+
+    Ok(())
+}
 
 /// Данный метод занимается тем, что коммитит изменения на сервере
 /// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/commit-a-flight-submission`
@@ -33,51 +106,70 @@ pub async fn commit_changes(request_builder: &RequestBuilder) -> Result<(), Micr
     Ok(())
 }
 
-/// C помощью данного метода мы ждем завершения выполнения коммита
+/// Запрашиваем текущий статус сабмиссии
 /// Описание: `https://docs.microsoft.com/en-us/windows/uwp/monetize/get-status-for-a-flight-submission`
-pub async fn wait_commit_finished(request_builder: &RequestBuilder) -> Result<(), MicrosoftAzureError> {
+async fn request_submission_status(
+    request_builder: &RequestBuilder,
+) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
+    let status_response = request_builder
+        .clone()
+        .method(reqwest::Method::GET)
+        .submission_command("status".to_string())
+        .build()
+        .await?
+        .header(reqwest::header::CONTENT_LENGTH, "0")
+        .send()
+        .await?
+        // .error_for_status()?
+        .json::<DataOrErrorResponse<SubmissionStatusResponse>>()
+        .await?
+        .into_result()?;
+
+    debug!(
+        "Microsoft Azure: submission status response {:#?}",
+        status_response
+    );
+
+    Ok(status_response)
+}
+
+/// Является ли статус ошибочным
+fn is_failed_status(status: &str) -> bool {
+    matches!(
+        status,
+        "CommitFailed"
+            | "None"
+            | "Canceled"
+            | "PublishFailed"
+            | "PreProcessingFailed"
+            | "CertificationFailed"
+            | "ReleaseFailed"
+    )
+}
+
+/// C помощью данного метода мы ждем завершения выполнения коммита
+pub async fn wait_commit_finished(
+    request_builder: &RequestBuilder,
+) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
     debug!("Microsoft Azure: wait submission commit result");
 
     loop {
-        let status_response = request_builder
-            .clone()
-            .method(reqwest::Method::GET)
-            .submission_command("status".to_string())
-            .build()
-            .await?
-            .header(reqwest::header::CONTENT_LENGTH, "0")
-            .send()
-            .await?
-            // .error_for_status()?
-            .json::<DataOrErrorResponse<SubmissionStatusResponse>>()
-            .await?
-            .into_result()?;
-
-        debug!(
-            "Microsoft Azure: submission status response {:#?}",
-            status_response
-        );
+        let status_response = request_submission_status(request_builder).await?;
 
         match status_response.status.as_str() {
             // Нормальное состояние для ожидания
             "CommitStarted" => {
-                tokio::time::sleep(std::time::Duration::from_secs(15)).await;
+                sleep(Duration::from_secs(15)).await;
             }
 
             // Коммит прошел успешно, прерываем ожидание
             "PreProcessing" | "PendingPublication" | "Certification" | "Publishing"
             | "Published" | "Release" => {
-                break;
+                return Ok(status_response);
             }
 
             // Ошибочный статус - ошибка
-            "CommitFailed"
-            | "None"
-            | "Canceled"
-            | "PublishFailed"
-            | "PreProcessingFailed"
-            | "CertificationFailed"
-            | "ReleaseFailed" => {
+            status if is_failed_status(status) => {
                 return Err(MicrosoftAzureError::CommitFailed(status_response));
             }
 
@@ -89,6 +181,49 @@ pub async fn wait_commit_finished(request_builder: &RequestBuilder) -> Result<()
             }
         }
     }
+}
 
-    Ok(())
+/// Ждем, пока сабмиссия пройдет сертификацию и будет готова к публикации или опубликована
+pub async fn wait_certification_finished(
+    request_builder: &RequestBuilder,
+    wait: &CertificationWait,
+) -> Result<SubmissionStatusResponse, MicrosoftAzureError> {
+    debug!("Microsoft Azure: wait submission certification result");
+
+    let deadline = Instant::now() + wait.timeout;
+    loop {
+        let status_response = request_submission_status(request_builder).await?;
+
+        match status_response.status.as_str() {
+            // Сертификация пройдена
+            "PendingPublication" | "Published" => {
+                info!(
+                    "Microsoft Azure: certification finished with status {}",
+                    status_response.status
+                );
+                return Ok(status_response);
+            }
+
+            // Промежуточные состояния, продолжаем ждать
+            "CommitStarted" | "PreProcessing" | "Certification" | "Release" | "Publishing" => {}
+
+            // Ошибочный статус - ошибка
+            status if is_failed_status(status) => {
+                return Err(MicrosoftAzureError::CertificationFailed(status_response));
+            }
+
+            // Неизвестный статус - ошибка
+            _ => {
+                return Err(MicrosoftAzureError::InvalidCommitStatus(
+                    status_response.status,
+                ));
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Err(MicrosoftAzureError::CertificationTimeout(status_response));
+        }
+
+        sleep(wait.poll_interval).await;
+    }
 }
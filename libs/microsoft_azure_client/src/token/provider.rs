@@ -3,10 +3,10 @@ use crate::{
     error::MicrosoftAzureError,
     responses::{DataOrErrorResponse, TokenResponse},
 };
+use log::debug;
 use reqwest::{header::CONTENT_TYPE, Client};
 use std::{sync::Arc, time::Duration};
 use tokio::sync::Mutex;
-use log::debug;
 use url::Url;
 
 ////////////////////////////////////////////////////////////////
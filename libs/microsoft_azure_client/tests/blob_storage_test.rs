@@ -0,0 +1,143 @@
+use microsoft_azure_client::{BlobProperties, BlobStorageClient, BlobUploadOptions};
+use reqwest::Client;
+use std::{
+    env,
+    io::Write,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+
+// Для локальной проверки подойдет Azurite:
+// docker run -p 10000:10000 mcr.microsoft.com/azure-storage/azurite azurite-blob --blobHost 0.0.0.0
+// Затем создаем контейнер, генерируем для него SAS и запускаем:
+// AZURE_BLOB_SAS_URL="http://127.0.0.1:10000/devstoreaccount1/builds?<sas>" cargo test -- --ignored
+#[tokio::test]
+#[ignore]
+async fn blob_storage_integration_test() {
+    let sas_url = env::var("AZURE_BLOB_SAS_URL").expect("Missing env variable");
+
+    // Файлик на несколько блоков, последний неполный
+    let mut file = tempfile::NamedTempFile::new().expect("Temp file create failed");
+    let data: Vec<u8> = (0..(1024 * 1024 * 2 + 123))
+        .map(|i| (i % 251) as u8)
+        .collect();
+    file.write_all(&data).expect("Temp file write failed");
+
+    let client = BlobStorageClient::new(Client::new(), &sas_url)
+        .expect("Client create failed")
+        .with_upload_options(BlobUploadOptions {
+            block_size: 1024 * 1024,
+            concurrency: 2,
+        });
+
+    let properties = BlobProperties {
+        content_type: Some("application/octet-stream".to_owned()),
+        cache_control: Some("no-cache".to_owned()),
+        metadata: vec![("branch".to_owned(), "master".to_owned())],
+    };
+    let url = client
+        .upload_file(file.path(), "test/build.bin", &properties)
+        .await
+        .expect("Upload failed");
+
+    assert!(url.query().is_none());
+    assert!(url.path().ends_with("/test/build.bin"));
+
+    // Повторная выгрузка должна проходить так же успешно
+    client
+        .upload_file(file.path(), "test/build.bin", &properties)
+        .await
+        .expect("Second upload failed");
+}
+
+/// Простейший HTTP сервер: на запрос списка блоков отвечает 403, на остальные запросы 201.
+/// Возвращает адрес сервера и список принятых запросов
+async fn start_block_list_forbidden_server() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let requests: Arc<Mutex<Vec<String>>> = Default::default();
+
+    let server_requests = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let requests = server_requests.clone();
+            tokio::spawn(async move {
+                let mut reader = BufReader::new(stream);
+
+                // Стартовая строка и заголовки
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).await.unwrap();
+
+                let status =
+                    if request_line.contains("comp=blocklist") && request_line.starts_with("GET") {
+                        "403 Forbidden"
+                    } else {
+                        "201 Created"
+                    };
+                requests
+                    .lock()
+                    .unwrap()
+                    .push(request_line.trim_end().to_owned());
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                reader
+                    .get_mut()
+                    .write_all(response.as_bytes())
+                    .await
+                    .unwrap();
+            });
+        }
+    });
+
+    (format!("http://{}/builds?sig=test", address), requests)
+}
+
+#[tokio::test]
+async fn blob_storage_block_list_forbidden_test() {
+    let (sas_url, requests) = start_block_list_forbidden_server().await;
+
+    let mut file = tempfile::NamedTempFile::new().expect("Temp file create failed");
+    file.write_all(&[7u8; 1024 * 1024 + 10])
+        .expect("Temp file write failed");
+
+    let client = BlobStorageClient::new(Client::new(), &sas_url)
+        .expect("Client create failed")
+        .with_upload_options(BlobUploadOptions {
+            block_size: 1024 * 1024,
+            concurrency: 1,
+        });
+
+    // SAS без права на чтение не должен мешать выгрузке
+    client
+        .upload_file(file.path(), "build.bin", &BlobProperties::default())
+        .await
+        .expect("Upload failed");
+
+    let requests = requests.lock().unwrap();
+    let count = |pattern: &str| requests.iter().filter(|r| r.contains(pattern)).count();
+    assert_eq!(count("comp=block&"), 2);
+    assert_eq!(count("PUT /builds/build.bin?sig=test&comp=blocklist"), 1);
+}
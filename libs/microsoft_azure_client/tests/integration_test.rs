@@ -1,4 +1,4 @@
-use microsoft_azure_client::MicrosoftAzureClient;
+use microsoft_azure_client::{MicrosoftAzureClient, PendingSubmissionPolicy};
 use reqwest::Client;
 use std::{env, path::Path, sync::Once};
 
@@ -45,7 +45,14 @@ async fn library_integration_test() {
     //     let groups = vec!["1152921504607280735".to_owned()];
     //     let test_flight_name = "Flight name test".to_owned();
     //     client
-    //         .upload_flight_build(upload_file_path, groups, test_flight_name)
+    //         .upload_flight_build(
+    //             upload_file_path,
+    //             groups,
+    //             test_flight_name,
+    //             None,
+    //             PendingSubmissionPolicy::Reuse,
+    //             None,
+    //         )
     //         .await
     //         .expect("Upload failed");
     // }
@@ -54,7 +61,14 @@ async fn library_integration_test() {
     {
         let upload_name = "Production test".to_owned();
         client
-            .upload_production_build(upload_file_path, upload_name)
+            .upload_production_build(
+                upload_file_path,
+                upload_name,
+                &Default::default(),
+                &[],
+                PendingSubmissionPolicy::Fail,
+                None,
+            )
             .await
             .expect("Upload failed");
     }
@@ -0,0 +1,256 @@
+use super::{
+    client::RuStorePublishType, error::RuStoreError, request_builder::RuStoreRequestBuilder,
+    responses::*,
+};
+use futures::future::join_all;
+use log::debug;
+use reqwest::{
+    multipart::{Form, Part},
+    Body, Method,
+};
+use reqwest_inspect_json::InspectJson;
+use serde_json::json;
+use std::path::Path;
+use tokio::fs::File;
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+// Главная документация
+// https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app
+
+/// Размер страницы при запросе списка версий
+const VERSIONS_PAGE_SIZE: u32 = 20;
+
+///////////////////////////////////////////////////////
+
+/// Тип файла пакета, от него зависит путь выгрузки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageType {
+    Apk,
+    Aab,
+}
+impl PackageType {
+    pub fn from_path(file_path: &Path) -> Result<PackageType, RuStoreError> {
+        let extension = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase());
+        match extension.as_deref() {
+            Some("apk") => Ok(PackageType::Apk),
+            Some("aab") => Ok(PackageType::Aab),
+            _ => Err(RuStoreError::InvalidFileExtention(
+                "Only .apk and .aab files are supported",
+            )),
+        }
+    }
+}
+
+///////////////////////////////////////////////////////
+
+/// Полный список версий приложения со всех страниц
+pub async fn get_versions_list(
+    request_builder: &RuStoreRequestBuilder,
+) -> Result<Vec<VersionInfo>, RuStoreError> {
+    // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/get-version-list
+    let mut versions = Vec::new();
+    let mut page = 0;
+    loop {
+        let response = request_builder
+            .build_request(Method::GET, "version")
+            .await?
+            .query(&[("page", page), ("size", VERSIONS_PAGE_SIZE)])
+            .send()
+            .await?
+            .inspect_json::<ApiResponse<VersionsPageResponse>, RuStoreError>(|data| {
+                debug!("Versions list response: {}", data);
+            })
+            .await?
+            .into_body()?;
+
+        versions.extend(response.content);
+
+        page = response.page_number + 1;
+        if page >= response.total_pages {
+            return Ok(versions);
+        }
+    }
+}
+
+/// Удаление версии, удалить можно лишь черновик
+async fn delete_version(
+    request_builder: &RuStoreRequestBuilder,
+    info: &VersionInfo,
+) -> Result<i64, RuStoreError> {
+    // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/delete-draft
+    request_builder
+        .build_request(Method::DELETE, &format!("version/{}", info.version_id))
+        .await?
+        .send()
+        .await?
+        .inspect_json::<ApiResponse<serde_json::Value>, RuStoreError>(|data| {
+            debug!("Delete version response: {}", data);
+        })
+        .await?
+        .into_result()
+        .map_err(|err| RuStoreError::DraftDeleteFailed {
+            version_id: info.version_id,
+            info: err,
+        })?;
+
+    Ok(info.version_id)
+}
+
+///////////////////////////////////////////////////////
+
+/// Черновик версии приложения, в который выгружается пакет и который затем отправляется на модерацию
+pub struct AppDraft {
+    request_builder: RuStoreRequestBuilder,
+    version_id: i64,
+}
+impl AppDraft {
+    /// RuStore не дает создать новый черновик, пока есть старый, поэтому старые черновики удаляем
+    pub async fn remove_old_drafts(
+        request_builder: &RuStoreRequestBuilder,
+    ) -> Result<(), RuStoreError> {
+        let old_drafts: Vec<VersionInfo> = get_versions_list(request_builder)
+            .await?
+            .into_iter()
+            .filter(|info| info.is_draft())
+            .collect();
+
+        if old_drafts.is_empty() {
+            return Ok(());
+        }
+
+        debug!("Old drafts list: {:#?}", old_drafts);
+
+        // Итератор по футурам
+        let delete_futures_iter = old_drafts
+            .iter()
+            .map(|info| delete_version(request_builder, info));
+
+        // Ждем результатов
+        let results = join_all(delete_futures_iter).await;
+
+        // Проверяем ошибки в запросах
+        for result in results {
+            let deleted_id = result?;
+            debug!("Draft deleted: {}", deleted_id);
+        }
+
+        Ok(())
+    }
+
+    /// Создаем новый черновик версии
+    pub async fn new(
+        request_builder: RuStoreRequestBuilder,
+        whats_new: Option<&str>,
+        publish_type: RuStorePublishType,
+    ) -> Result<AppDraft, RuStoreError> {
+        // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/create-draft-version
+        let mut body = json!({
+            "publishType": publish_type.as_str()
+        });
+        if let Some(text) = whats_new {
+            body["whatsNew"] = json!(text);
+        }
+
+        let version_id = request_builder
+            .build_request(Method::POST, "version")
+            .await?
+            .json(&body)
+            .send()
+            .await?
+            .inspect_json::<ApiResponse<i64>, RuStoreError>(|data| {
+                debug!("Create draft response: {}", data);
+            })
+            .await?
+            .into_body()?;
+
+        Ok(AppDraft {
+            request_builder,
+            version_id,
+        })
+    }
+
+    pub fn get_version_id(&self) -> i64 {
+        self.version_id
+    }
+
+    /// Выгружаем файлик пакета в черновик
+    pub async fn upload_file(
+        &self,
+        file_path: &Path,
+        package_type: PackageType,
+    ) -> Result<(), RuStoreError> {
+        // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/apk-file-upload
+        // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/aab-file-upload
+        debug!("Uploading started");
+
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or(RuStoreError::WrongFilePath)?
+            .to_owned();
+
+        // Файлик в виде стрима
+        let file = File::open(file_path).await?;
+        let file_length = file.metadata().await?.len();
+        let reader = FramedRead::new(file, BytesCodec::new());
+        let file_part = Part::stream_with_length(Body::wrap_stream(reader), file_length)
+            .file_name(file_name)
+            .mime_str("application/octet-stream")?;
+        let form = Form::new().part("file", file_part);
+
+        let builder = match package_type {
+            // Основной APK без привязки к сервисам Huawei
+            PackageType::Apk => self
+                .request_builder
+                .build_request(Method::POST, &format!("version/{}/apk", self.version_id))
+                .await?
+                .query(&[("servicesType", "Unknown"), ("isMainApk", "true")]),
+            PackageType::Aab => {
+                self.request_builder
+                    .build_request(Method::POST, &format!("version/{}/aab", self.version_id))
+                    .await?
+            }
+        };
+
+        builder
+            .multipart(form)
+            .send()
+            .await?
+            .inspect_json::<ApiResponse<serde_json::Value>, RuStoreError>(|data| {
+                debug!("Upload file response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+
+    /// Текущее состояние версии черновика
+    pub async fn get_info(&self) -> Result<VersionInfo, RuStoreError> {
+        get_versions_list(&self.request_builder)
+            .await?
+            .into_iter()
+            .find(|info| info.version_id == self.version_id)
+            .ok_or(RuStoreError::VersionIsMissing(self.version_id))
+    }
+
+    /// Отправляем черновик на модерацию
+    pub async fn submit(&self) -> Result<(), RuStoreError> {
+        // https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/send-draft-for-moderation
+        self.request_builder
+            .build_request(Method::POST, &format!("version/{}/commit", self.version_id))
+            .await?
+            .send()
+            .await?
+            .inspect_json::<ApiResponse<serde_json::Value>, RuStoreError>(|data| {
+                debug!("Submit response: {}", data);
+            })
+            .await?
+            .into_result()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use super::{
+    app_draft::{AppDraft, PackageType},
+    error::RuStoreError,
+    request_builder::RuStoreRequestBuilder,
+    token::RuStoreTokenProvider,
+};
+use log::{debug, error, info};
+use reqwest::Client;
+use std::{path::Path, str::FromStr, time::Duration};
+use tap::TapFallible;
+use tokio::time::{sleep, Instant};
+
+// Доки
+// https://www.rustore.ru/help/work-with-rustore-api
+
+/// Пауза между запросами статуса версии после отправки на модерацию
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+/// Способ публикации версии после прохождения модерации
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuStorePublishType {
+    /// Версия публикуется сразу после модерации
+    Instantly,
+    /// Версию нужно опубликовать руками из консоли
+    Manual,
+}
+impl RuStorePublishType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuStorePublishType::Instantly => "INSTANTLY",
+            RuStorePublishType::Manual => "MANUAL",
+        }
+    }
+}
+impl FromStr for RuStorePublishType {
+    type Err = RuStoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "instantly" => Ok(RuStorePublishType::Instantly),
+            "manual" => Ok(RuStorePublishType::Manual),
+            _ => Err(RuStoreError::InvalidPublishType(s.to_owned())),
+        }
+    }
+}
+
+pub struct RuStoreUploadTask<'a> {
+    pub package_name: &'a str,
+    /// Файлик .apk или .aab
+    pub file_path: &'a Path,
+    /// Текст "Что нового" для версии
+    pub whats_new: Option<&'a str>,
+    pub publish_type: RuStorePublishType,
+    /// Отправлять версию на модерацию после выгрузки
+    pub submit: bool,
+    /// Сколько ждем принятия версии на модерацию
+    pub submit_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct RuStoreUploadResult {
+    pub version_id: i64,
+    pub version_name: Option<String>,
+    pub version_code: Option<i64>,
+    /// Статус версии после всех действий
+    pub version_status: String,
+    pub submitted: bool,
+}
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+pub struct RuStoreClient {
+    http_client: Client,
+    token_provider: RuStoreTokenProvider,
+}
+impl RuStoreClient {
+    pub fn new(http_client: Client, token_provider: RuStoreTokenProvider) -> RuStoreClient {
+        RuStoreClient {
+            http_client,
+            token_provider,
+        }
+    }
+
+    pub async fn upload(
+        &self,
+        task: RuStoreUploadTask<'_>,
+    ) -> Result<RuStoreUploadResult, RuStoreError> {
+        // Проверяем файлик до удаления старых черновиков
+        let package_type = PackageType::from_path(task.file_path)?;
+
+        let request_builder = RuStoreRequestBuilder::new(
+            self.http_client.clone(),
+            self.token_provider.clone(),
+            task.package_name,
+        )
+        .tap_err(|err| {
+            error!("Request builder create failed: {}", err);
+        })?;
+
+        AppDraft::remove_old_drafts(&request_builder)
+            .await
+            .tap_err(|err| {
+                error!("Old drafts remove failed: {}", err);
+            })?;
+
+        let draft = AppDraft::new(request_builder, task.whats_new, task.publish_type)
+            .await
+            .tap_err(|err| {
+                error!("Draft create failed: {}", err);
+            })?;
+        info!("RuStore draft created: {}", draft.get_version_id());
+
+        draft
+            .upload_file(task.file_path, package_type)
+            .await
+            .tap_err(|err| {
+                error!("Upload failed: {}", err);
+            })?;
+        info!("RuStore file uploaded");
+
+        if task.submit {
+            draft.submit().await.tap_err(|err| {
+                error!("Submit failed: {}", err);
+            })?;
+            info!("RuStore version submitted");
+
+            Self::wait_draft_submitted(&draft, task.submit_timeout).await?;
+        }
+
+        let version = draft.get_info().await?;
+        debug!("RuStore version info: {:?}", version);
+
+        Ok(RuStoreUploadResult {
+            version_id: version.version_id,
+            version_name: version.version_name,
+            version_code: version.version_code,
+            version_status: version.version_status,
+            submitted: task.submit,
+        })
+    }
+
+    /// Ждем, пока версия выйдет из статуса черновика, то есть будет принята на модерацию
+    async fn wait_draft_submitted(draft: &AppDraft, timeout: Duration) -> Result<(), RuStoreError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let info = draft.get_info().await?;
+            debug!("RuStore version status: {}", info.version_status);
+
+            if !info.is_draft() {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(RuStoreError::SubmitStatusTimeout(info.version_id));
+            }
+
+            sleep(STATUS_POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::responses::ApiErrorInfo;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use url::ParseError;
+
+#[derive(Debug)]
+pub enum RuStoreError {
+    URLError(ParseError),
+    NetErr(reqwest::Error),
+    JsonParseErr(serde_json::Error),
+    WrongFilePath,
+    InvalidFileExtention(&'static str),
+    FileError(io::Error),
+    InvalidPrivateKey(String),
+    TokenSignFailed,
+    InvalidTokenDuration(u64),
+    TokenIsExpired,
+    InvalidPublishType(String),
+    DraftDeleteFailed { version_id: i64, info: ApiErrorInfo },
+    VersionIsMissing(i64),
+    SubmitStatusTimeout(i64),
+    ApiError(ApiErrorInfo),
+    Custom(String),
+}
+
+impl From<ParseError> for RuStoreError {
+    fn from(err: ParseError) -> RuStoreError {
+        RuStoreError::URLError(err)
+    }
+}
+impl From<io::Error> for RuStoreError {
+    fn from(err: io::Error) -> RuStoreError {
+        RuStoreError::FileError(err)
+    }
+}
+impl From<reqwest::Error> for RuStoreError {
+    fn from(err: reqwest::Error) -> RuStoreError {
+        RuStoreError::NetErr(err)
+    }
+}
+impl From<serde_json::Error> for RuStoreError {
+    fn from(err: serde_json::Error) -> RuStoreError {
+        RuStoreError::JsonParseErr(err)
+    }
+}
+impl From<ApiErrorInfo> for RuStoreError {
+    fn from(err: ApiErrorInfo) -> RuStoreError {
+        RuStoreError::ApiError(err)
+    }
+}
+
+impl Display for RuStoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "RuStore error: {:#?}", self)
+    }
+}
+
+impl Error for RuStoreError {}
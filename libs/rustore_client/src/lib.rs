@@ -0,0 +1,12 @@
+mod app_draft;
+mod client;
+mod error;
+mod request_builder;
+mod responses;
+mod token;
+
+pub use self::{
+    client::{RuStoreClient, RuStorePublishType, RuStoreUploadResult, RuStoreUploadTask},
+    error::RuStoreError,
+    token::{request_token, RuStoreAccessToken, RuStoreKey, RuStoreTokenProvider},
+};
@@ -0,0 +1,50 @@
+use super::{error::RuStoreError, token::RuStoreTokenProvider};
+use log::debug;
+use reqwest::{Client, Method, RequestBuilder, Url};
+
+// https://www.rustore.ru/help/work-with-rustore-api
+
+//////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Clone)]
+pub struct RuStoreRequestBuilder {
+    http_client: Client,
+    api_url: Url,
+    token_provider: RuStoreTokenProvider,
+}
+impl RuStoreRequestBuilder {
+    /// Все запросы к приложению идут относительно урла с именем пакета
+    pub fn new(
+        http_client: Client,
+        token_provider: RuStoreTokenProvider,
+        package_name: &str,
+    ) -> Result<RuStoreRequestBuilder, RuStoreError> {
+        let api_url = Url::parse("https://public-api.rustore.ru/public/v1/application/")?
+            .join(&format!("{}/", package_name))?;
+        debug!("Api url: {}", api_url.as_str());
+
+        Ok(RuStoreRequestBuilder {
+            http_client,
+            api_url,
+            token_provider,
+        })
+    }
+
+    pub async fn build_request(
+        &self,
+        method: Method,
+        path: &str,
+    ) -> Result<RequestBuilder, RuStoreError> {
+        let full_url = self.api_url.join(path)?;
+
+        // Получаем токен с перезапросом если надо
+        let token = self.token_provider.get_access_token().await?;
+
+        let builder = self
+            .http_client
+            .request(method, full_url.as_str())
+            .header("Public-Token", token.as_str());
+
+        Ok(builder)
+    }
+}
@@ -0,0 +1,118 @@
+use serde::Deserialize;
+
+// RuStore все ответы заворачивает в общую обертку, успех определяется полем `code`
+
+/// Код и описание ошибки из ответа
+#[derive(Deserialize, Debug)]
+pub struct ApiErrorInfo {
+    pub code: String,
+    pub message: Option<String>,
+}
+
+/// Общая обертка над ответами API
+#[derive(Deserialize, Debug)]
+pub struct ApiResponse<D> {
+    pub code: String,
+    pub message: Option<String>,
+    pub body: Option<D>,
+}
+impl<D> ApiResponse<D> {
+    /// Проверяем лишь код ответа, тело для некоторых запросов отсутствует
+    pub fn into_result(self) -> Result<Option<D>, ApiErrorInfo> {
+        if self.code == "OK" {
+            Ok(self.body)
+        } else {
+            Err(ApiErrorInfo {
+                code: self.code,
+                message: self.message,
+            })
+        }
+    }
+
+    /// Тело ответа обязательно
+    pub fn into_body(self) -> Result<D, ApiErrorInfo> {
+        self.into_result()?.ok_or_else(|| ApiErrorInfo {
+            code: "EMPTY_BODY".to_owned(),
+            message: Some("Response body is missing".to_owned()),
+        })
+    }
+}
+
+//////////////////////////////////////////////////////////////////////
+
+// https://www.rustore.ru/help/work-with-rustore-api/api-authorization-token
+#[derive(Deserialize, Debug)]
+pub struct RuStoreTokenResponse {
+    pub jwe: String,
+    /// Время жизни токена в секундах
+    pub ttl: u64,
+}
+
+// https://www.rustore.ru/help/work-with-rustore-api/api-upload-publication-app/get-version-list
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionsPageResponse {
+    #[serde(default)]
+    pub content: Vec<VersionInfo>,
+    pub page_number: u32,
+    pub total_pages: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    pub version_id: i64,
+    pub version_name: Option<String>,
+    pub version_code: Option<i64>,
+    /// DRAFT, MODERATION, REJECTED, ACTIVE и тд
+    pub version_status: String,
+}
+impl VersionInfo {
+    pub fn is_draft(&self) -> bool {
+        self.version_status.eq("DRAFT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_page_parsing() {
+        let text = r#"{
+            "code": "OK",
+            "message": null,
+            "body": {
+                "content": [
+                    {
+                        "versionId": 123,
+                        "appName": "Test",
+                        "versionName": "1.2.3",
+                        "versionCode": 45,
+                        "versionStatus": "DRAFT",
+                        "publishType": "INSTANTLY"
+                    }
+                ],
+                "pageNumber": 0,
+                "pageSize": 20,
+                "totalElements": 1,
+                "totalPages": 1
+            },
+            "timestamp": "2023-08-14T12:34:56.789+03:00"
+        }"#;
+        let page = serde_json::from_str::<ApiResponse<VersionsPageResponse>>(text)
+            .unwrap()
+            .into_body()
+            .unwrap();
+        assert_eq!(page.content.len(), 1);
+        assert_eq!(page.content[0].version_id, 123);
+        assert!(page.content[0].is_draft());
+
+        let text = r#"{"code": "ERROR", "message": "Access denied", "body": null}"#;
+        let err = serde_json::from_str::<ApiResponse<VersionsPageResponse>>(text)
+            .unwrap()
+            .into_result()
+            .unwrap_err();
+        assert_eq!(err.code, "ERROR");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::error::RuStoreError;
+use ring::{
+    rand::SystemRandom,
+    signature::{RsaKeyPair, RSA_PKCS1_SHA512},
+};
+use std::fmt::{self, Debug, Formatter};
+
+// https://www.rustore.ru/help/work-with-rustore-api/api-authorization-token
+
+/// Ключ RuStore API, которым подписываются запросы токена
+pub struct RuStoreKey {
+    key_id: String,
+    key_pair: RsaKeyPair,
+}
+
+impl Debug for RuStoreKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Приватный ключ в логи не выводим
+        f.debug_struct("RuStoreKey")
+            .field("key_id", &self.key_id)
+            .finish()
+    }
+}
+
+impl RuStoreKey {
+    /// Консоль RuStore выдает приватный ключ PKCS8 в base64,
+    /// но на всякий случай принимаем его и в формате PEM
+    pub fn from_base64(key_id: String, private_key: &str) -> Result<RuStoreKey, RuStoreError> {
+        // Выкидываем заголовки PEM, оставляя только base64 тело ключа
+        let body: String = private_key
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with("-----"))
+            .collect();
+
+        let der = base64::decode(&body)
+            .map_err(|err| RuStoreError::InvalidPrivateKey(err.to_string()))?;
+
+        let key_pair = RsaKeyPair::from_pkcs8(&der)
+            .map_err(|err| RuStoreError::InvalidPrivateKey(err.to_string()))?;
+
+        Ok(RuStoreKey { key_id, key_pair })
+    }
+
+    pub fn get_key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Подпись SHA512withRSA от строки `keyId + timestamp` в base64
+    pub(crate) fn sign(&self, timestamp: &str) -> Result<String, RuStoreError> {
+        let message = format!("{}{}", self.key_id, timestamp);
+
+        let mut signature = vec![0; self.key_pair.public_modulus_len()];
+        self.key_pair
+            .sign(
+                &RSA_PKCS1_SHA512,
+                &SystemRandom::new(),
+                message.as_bytes(),
+                &mut signature,
+            )
+            .map_err(|_| RuStoreError::TokenSignFailed)?;
+
+        Ok(base64::encode(signature))
+    }
+}
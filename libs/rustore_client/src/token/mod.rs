@@ -0,0 +1,9 @@
+mod key;
+mod provider;
+mod token_struct;
+
+pub use self::{
+    key::RuStoreKey,
+    provider::RuStoreTokenProvider,
+    token_struct::{request_token, RuStoreAccessToken},
+};
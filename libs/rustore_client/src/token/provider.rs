@@ -0,0 +1,43 @@
+use super::{key::RuStoreKey, token_struct::request_token};
+use crate::error::RuStoreError;
+use reqwest::Client;
+use std::{sync::Arc, time::Duration};
+use token_provider::TokenProvider;
+
+////////////////////////////////////////////////////////////////
+
+/// Провайдер токенов RuStore, обновляет токен заранее перед его истечением
+#[derive(Debug, Clone)]
+pub struct RuStoreTokenProvider {
+    inner: TokenProvider<RuStoreError>,
+}
+
+impl RuStoreTokenProvider {
+    pub fn new(http_client: Client, key: RuStoreKey) -> RuStoreTokenProvider {
+        // Токен живет всего 15 минут, перезапрашиваем его за 3 минуты до истечения
+        Self::new_custom(http_client, key, Duration::from_secs(60 * 3))
+    }
+
+    pub fn new_custom(
+        http_client: Client,
+        key: RuStoreKey,
+        token_expire_pre_delay: Duration,
+    ) -> RuStoreTokenProvider {
+        let key = Arc::new(key);
+
+        let inner = TokenProvider::new("RuStore", token_expire_pre_delay, move || {
+            let http_client = http_client.clone();
+            let key = key.clone();
+            async move {
+                let token = request_token(&http_client, &key).await?;
+                Ok(token.into_fetched())
+            }
+        });
+
+        RuStoreTokenProvider { inner }
+    }
+
+    pub async fn get_access_token(&self) -> Result<Arc<String>, RuStoreError> {
+        self.inner.get_access_token().await
+    }
+}
@@ -0,0 +1,70 @@
+use super::key::RuStoreKey;
+use crate::{
+    error::RuStoreError,
+    responses::{ApiResponse, RuStoreTokenResponse},
+};
+use chrono::{Local, SecondsFormat};
+use log::debug;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use token_provider::FetchedToken;
+
+#[derive(Debug)]
+pub struct RuStoreAccessToken {
+    value: String,
+    expire_time: Instant,
+}
+impl RuStoreAccessToken {
+    pub fn new(value: String, expire_time: Instant) -> RuStoreAccessToken {
+        RuStoreAccessToken { value, expire_time }
+    }
+
+    pub fn as_str_checked(&self) -> Result<&str, RuStoreError> {
+        if Instant::now() < self.expire_time {
+            Ok(self.value.as_str())
+        } else {
+            Err(RuStoreError::TokenIsExpired)
+        }
+    }
+
+    /// Оставшееся время жизни токена для общего провайдера
+    pub(crate) fn into_fetched(self) -> FetchedToken {
+        FetchedToken {
+            lifetime: self.expire_time.saturating_duration_since(Instant::now()),
+            value: self.value,
+        }
+    }
+}
+
+pub async fn request_token(
+    http_client: &Client,
+    key: &RuStoreKey,
+) -> Result<RuStoreAccessToken, RuStoreError> {
+    // https://www.rustore.ru/help/work-with-rustore-api/api-authorization-token
+
+    // Время в формате `2023-08-14T12:34:56.789+03:00`
+    let timestamp = Local::now().to_rfc3339_opts(SecondsFormat::Millis, false);
+    let signature = key.sign(&timestamp)?;
+
+    let response = http_client
+        .post("https://public-api.rustore.ru/public/auth/")
+        .json(&json!({
+            "keyId": key.get_key_id(),
+            "timestamp": timestamp,
+            "signature": signature
+        }))
+        .send()
+        .await?
+        .json::<ApiResponse<RuStoreTokenResponse>>()
+        .await?
+        .into_body()?;
+
+    debug!("RuStore token expires in: {}", response.ttl);
+
+    let expire_time = Instant::now()
+        .checked_add(Duration::from_secs(response.ttl))
+        .ok_or(RuStoreError::InvalidTokenDuration(response.ttl))?;
+
+    Ok(RuStoreAccessToken::new(response.jwe, expire_time))
+}
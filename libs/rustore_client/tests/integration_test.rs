@@ -0,0 +1,59 @@
+use log::debug;
+use reqwest::Client;
+use rustore_client::{
+    RuStoreClient, RuStoreKey, RuStorePublishType, RuStoreTokenProvider, RuStoreUploadTask,
+};
+use std::{path::PathBuf, sync::Once, time::Duration};
+
+fn setup_logs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        if std::env::var("RUST_LOG").is_err() {
+            std::env::set_var("RUST_LOG", "debug");
+        }
+        env_logger::init();
+    })
+}
+
+// Версия не отправляется на модерацию, файлик лишь попадает в новый черновик:
+// RUSTORE_KEY_ID=... RUSTORE_PRIVATE_KEY=... RUSTORE_PACKAGE_NAME=... \
+// RUSTORE_TEST_FILE=app.apk cargo test -- --ignored
+#[tokio::test]
+#[ignore]
+async fn library_integration_test() {
+    setup_logs();
+
+    let key_id =
+        std::env::var("RUSTORE_KEY_ID").expect("RUSTORE_KEY_ID environment variable is missing");
+
+    let private_key = std::env::var("RUSTORE_PRIVATE_KEY")
+        .expect("RUSTORE_PRIVATE_KEY environment variable is missing");
+
+    let package_name = std::env::var("RUSTORE_PACKAGE_NAME")
+        .expect("RUSTORE_PACKAGE_NAME environment variable is missing");
+
+    let file_path = PathBuf::from(
+        std::env::var("RUSTORE_TEST_FILE")
+            .expect("RUSTORE_TEST_FILE environment variable is missing"),
+    );
+
+    let http_client = Client::new();
+
+    let key = RuStoreKey::from_base64(key_id, &private_key).expect("Invalid private key");
+
+    let token_provider = RuStoreTokenProvider::new(http_client.clone(), key);
+
+    let client = RuStoreClient::new(http_client, token_provider);
+
+    let task = RuStoreUploadTask {
+        package_name: &package_name,
+        file_path: &file_path,
+        whats_new: Some("Test upload"),
+        publish_type: RuStorePublishType::Manual,
+        submit: false,
+        submit_timeout: Duration::from_secs(60 * 5),
+    };
+    let result = client.upload(task).await.expect("Uploading failed");
+
+    debug!("Uploading result: {:#?}", result);
+}
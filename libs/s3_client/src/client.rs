@@ -0,0 +1,367 @@
+use crate::{
+    error::S3Error,
+    multipart::{abort_multipart_upload, complete_multipart_upload, create_multipart_upload},
+    signer::{canonical_query, sha256_hex, uri_encode, S3Credentials, Signer},
+};
+use chrono::Utc;
+use futures::{stream, StreamExt, TryStreamExt};
+use log::{debug, info, warn};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use std::{io::SeekFrom, path::Path, time::Duration};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use url::Url;
+
+/// Минимальный размер части multipart выгрузки, кроме последней
+const MIN_PART_SIZE: usize = 1024 * 1024 * 5;
+/// Максимальное количество частей в multipart выгрузке
+const MAX_PARTS_COUNT: u64 = 10000;
+/// Максимальное время жизни подписанного урла - 7 дней
+const MAX_PRESIGN_EXPIRATION: u64 = 60 * 60 * 24 * 7;
+
+/// Параметры подключения к хранилищу
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// Адрес вида `https://s3.eu-central-1.amazonaws.com` или `http://minio.local:9000`
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    /// Бакет в имени хоста вместо пути, MinIO по умолчанию работает с путем
+    pub virtual_hosted_style: bool,
+    /// Размер части multipart выгрузки, файлики не больше этого размера выгружаются одним запросом
+    pub part_size: usize,
+    /// Сколько частей выгружаем одновременно
+    pub concurrency: usize,
+}
+
+impl S3Config {
+    pub fn new(endpoint: String, region: String, bucket: String) -> S3Config {
+        S3Config {
+            endpoint,
+            region,
+            bucket,
+            virtual_hosted_style: false,
+            part_size: 1024 * 1024 * 16,
+            concurrency: 4,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct S3UploadTask<'a> {
+    pub file_path: &'a Path,
+    /// Ключ объекта, может содержать путь через `/`
+    pub key: &'a str,
+    pub content_type: Option<&'a str>,
+    /// Теги объекта
+    pub tags: &'a [(String, String)],
+}
+
+#[derive(Debug)]
+pub struct S3UploadResult {
+    pub key: String,
+    /// Урл объекта без подписи
+    pub url: Url,
+    pub etag: Option<String>,
+}
+
+/// Часть файлика для выгрузки
+struct PartTask {
+    number: u32,
+    offset: u64,
+    size: usize,
+}
+
+pub struct S3Client {
+    http_client: Client,
+    credentials: S3Credentials,
+    region: String,
+    bucket_url: Url,
+    part_size: usize,
+    concurrency: usize,
+}
+
+impl S3Client {
+    pub fn new(
+        http_client: Client,
+        credentials: S3Credentials,
+        config: S3Config,
+    ) -> Result<S3Client, S3Error> {
+        if config.part_size < MIN_PART_SIZE {
+            return Err(S3Error::InvalidPartSize(config.part_size));
+        }
+        if config.concurrency == 0 {
+            return Err(S3Error::Custom(
+                "Upload concurrency must be greater than zero".to_owned(),
+            ));
+        }
+
+        let mut bucket_url = Url::parse(&config.endpoint)?;
+        let endpoint_host = bucket_url
+            .host_str()
+            .ok_or_else(|| S3Error::InvalidEndpoint(config.endpoint.clone()))?
+            .to_owned();
+        if config.virtual_hosted_style {
+            bucket_url.set_host(Some(&format!("{}.{}", config.bucket, endpoint_host)))?;
+        } else {
+            bucket_url
+                .path_segments_mut()
+                .map_err(|_| S3Error::InvalidEndpoint(config.endpoint.clone()))?
+                .pop_if_empty()
+                .push(&config.bucket);
+        }
+        debug!("S3 bucket url: {}", bucket_url);
+
+        Ok(S3Client {
+            http_client,
+            credentials,
+            region: config.region,
+            bucket_url,
+            part_size: config.part_size,
+            concurrency: config.concurrency,
+        })
+    }
+
+    /// Урл объекта, каждый сегмент ключа кодируется так же, как при подписи
+    fn object_url(&self, key: &str) -> Url {
+        let encoded_key = key.split('/').map(uri_encode).collect::<Vec<_>>().join("/");
+        let mut url = self.bucket_url.clone();
+        let path = format!("{}/{}", url.path().trim_end_matches('/'), encoded_key);
+        url.set_path(&path);
+        url
+    }
+
+    fn signer(&self) -> Signer<'_> {
+        Signer {
+            credentials: &self.credentials,
+            region: &self.region,
+        }
+    }
+
+    /// Создаем подписанный запрос, параметры урла передаются отдельно для канонического вида
+    pub(crate) fn signed_request(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+        payload_hash: &str,
+    ) -> RequestBuilder {
+        let mut url = self.object_url(key);
+        if !query.is_empty() {
+            url.set_query(Some(&canonical_query(query)));
+        }
+
+        let signed_headers =
+            self.signer()
+                .sign_headers(&method, &url, headers, payload_hash, &Utc::now());
+
+        let mut request = self.http_client.request(method, url);
+        for (name, value) in headers.iter() {
+            request = request.header(*name, *value);
+        }
+        for (name, value) in signed_headers.into_iter() {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    /// Подписанный урл для скачивания объекта без ключей
+    pub fn presigned_get_url(&self, key: &str, expiration: Duration) -> Result<Url, S3Error> {
+        let seconds = expiration.as_secs();
+        if seconds == 0 || seconds > MAX_PRESIGN_EXPIRATION {
+            return Err(S3Error::InvalidPresignExpiration(seconds));
+        }
+        Ok(self
+            .signer()
+            .presign(&Method::GET, &self.object_url(key), seconds, &Utc::now()))
+    }
+
+    pub async fn upload_file(&self, task: S3UploadTask<'_>) -> Result<S3UploadResult, S3Error> {
+        let file_size = tokio::fs::metadata(task.file_path).await?.len();
+
+        // Общие заголовки объекта
+        let tagging = task
+            .tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        let tagging = canonical_query(&tagging);
+        let mut headers = Vec::new();
+        if let Some(content_type) = task.content_type {
+            headers.push(("content-type", content_type));
+        }
+        if !tagging.is_empty() {
+            headers.push(("x-amz-tagging", tagging.as_str()));
+        }
+
+        let etag = if file_size <= self.part_size as u64 {
+            self.put_object(&task, &headers).await?
+        } else {
+            self.multipart_upload(&task, &headers, file_size).await?
+        };
+        info!("S3 uploading finished: {}, etag: {:?}", task.key, etag);
+
+        Ok(S3UploadResult {
+            key: task.key.to_owned(),
+            url: self.object_url(task.key),
+            etag,
+        })
+    }
+
+    /// Небольшие файлики выгружаем одним запросом
+    async fn put_object(
+        &self,
+        task: &S3UploadTask<'_>,
+        headers: &[(&str, &str)],
+    ) -> Result<Option<String>, S3Error> {
+        // https://docs.aws.amazon.com/AmazonS3/latest/API/API_PutObject.html
+        let data = tokio::fs::read(task.file_path).await?;
+        let response = self
+            .signed_request(Method::PUT, task.key, &[], headers, &sha256_hex(&data))
+            .body(data)
+            .send()
+            .await?;
+        let response = check_response(response).await?;
+        Ok(get_etag(&response))
+    }
+
+    async fn multipart_upload(
+        &self,
+        task: &S3UploadTask<'_>,
+        headers: &[(&str, &str)],
+        file_size: u64,
+    ) -> Result<Option<String>, S3Error> {
+        let parts_count = file_size.div_ceil(self.part_size as u64);
+        if parts_count > MAX_PARTS_COUNT {
+            return Err(S3Error::InvalidPartSize(self.part_size));
+        }
+
+        let upload_id = create_multipart_upload(self, task.key, headers).await?;
+        debug!("S3 multipart upload id: {}", upload_id);
+
+        let result = async {
+            let parts = (0..parts_count).map(|index| {
+                let offset = index * self.part_size as u64;
+                PartTask {
+                    number: index as u32 + 1,
+                    offset,
+                    size: std::cmp::min(self.part_size as u64, file_size - offset) as usize,
+                }
+            });
+
+            // Выгружаем части параллельно, порядок восстанавливаем перед завершением
+            let mut etags: Vec<(u32, String)> = stream::iter(parts)
+                .map(|part| self.upload_part(task.file_path, task.key, &upload_id, part))
+                .buffer_unordered(self.concurrency)
+                .try_collect()
+                .await?;
+            etags.sort_by_key(|(number, _)| *number);
+
+            complete_multipart_upload(self, task.key, &upload_id, &etags).await
+        }
+        .await;
+
+        // Незавершенная выгрузка занимает место в бакете, поэтому удаляем ее
+        if result.is_err() {
+            if let Err(err) = abort_multipart_upload(self, task.key, &upload_id).await {
+                warn!("S3 multipart upload abort failed: {}", err);
+            }
+        }
+
+        result
+    }
+
+    async fn upload_part(
+        &self,
+        file_path: &Path,
+        key: &str,
+        upload_id: &str,
+        part: PartTask,
+    ) -> Result<(u32, String), S3Error> {
+        // https://docs.aws.amazon.com/AmazonS3/latest/API/API_UploadPart.html
+        let mut data = vec![0_u8; part.size];
+        let mut file = File::open(file_path).await?;
+        file.seek(SeekFrom::Start(part.offset)).await?;
+        file.read_exact(&mut data).await?;
+
+        let payload_hash = sha256_hex(&data);
+        let part_number = part.number.to_string();
+        let query = [
+            ("partNumber", part_number.as_str()),
+            ("uploadId", upload_id),
+        ];
+
+        let mut iter_count = 0;
+        loop {
+            let result = async {
+                let response = self
+                    .signed_request(Method::PUT, key, &query, &[], &payload_hash)
+                    .body(data.clone())
+                    .send()
+                    .await?;
+                let response = check_response(response).await?;
+                get_etag(&response).ok_or(S3Error::ETagIsMissing(part.number))
+            }
+            .await;
+
+            match result {
+                Ok(etag) => {
+                    debug!("S3 part {} uploaded, etag: {}", part.number, etag);
+                    return Ok((part.number, etag));
+                }
+                // Делаем несколько попыток с паузой, как и при выгрузке в Azure
+                Err(err) if iter_count < 3 => {
+                    iter_count += 1;
+                    warn!(
+                        "Retry S3 part {} uploading, iteration: {}, err: {}",
+                        part.number, iter_count, err
+                    );
+                    tokio::time::sleep(Duration::from_secs(3)).await;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+fn get_etag(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_owned())
+}
+
+/// Текст ошибки в XML вида `<Error><Code/><Message/></Error>`
+pub(crate) fn parse_error_xml(text: &str) -> Option<(Option<String>, Option<String>)> {
+    let document = roxmltree::Document::parse(text).ok()?;
+    let root = document.root_element();
+    if !root.has_tag_name("Error") {
+        return None;
+    }
+    let child_text = |name: &str| {
+        root.children()
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .map(|text| text.to_owned())
+    };
+    Some((child_text("Code"), child_text("Message")))
+}
+
+pub(crate) async fn check_response(response: Response) -> Result<Response, S3Error> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let text = response.text().await.unwrap_or_default();
+    let (code, message) = parse_error_xml(&text).unwrap_or((None, None));
+    Err(S3Error::ApiError {
+        status,
+        code,
+        message,
+    })
+}
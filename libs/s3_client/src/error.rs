@@ -0,0 +1,55 @@
+use reqwest::StatusCode;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use url::ParseError;
+
+#[derive(Debug)]
+pub enum S3Error {
+    URLError(ParseError),
+    NetErr(reqwest::Error),
+    FileError(io::Error),
+    XmlParseErr(roxmltree::Error),
+    InvalidEndpoint(String),
+    InvalidPartSize(usize),
+    InvalidPresignExpiration(u64),
+    ETagIsMissing(u32),
+    UploadIdIsMissing,
+    ApiError {
+        status: StatusCode,
+        code: Option<String>,
+        message: Option<String>,
+    },
+    Custom(String),
+}
+
+impl From<ParseError> for S3Error {
+    fn from(err: ParseError) -> S3Error {
+        S3Error::URLError(err)
+    }
+}
+impl From<io::Error> for S3Error {
+    fn from(err: io::Error) -> S3Error {
+        S3Error::FileError(err)
+    }
+}
+impl From<reqwest::Error> for S3Error {
+    fn from(err: reqwest::Error) -> S3Error {
+        S3Error::NetErr(err)
+    }
+}
+impl From<roxmltree::Error> for S3Error {
+    fn from(err: roxmltree::Error) -> S3Error {
+        S3Error::XmlParseErr(err)
+    }
+}
+
+impl Display for S3Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "S3 error: {:#?}", self)
+    }
+}
+
+impl Error for S3Error {}
@@ -0,0 +1,19 @@
+mod client;
+mod multipart;
+mod signer;
+mod error;
+
+pub use self::{
+    client::{
+        S3Client,
+        S3Config,
+        S3UploadTask,
+        S3UploadResult
+    },
+    signer::{
+        S3Credentials
+    },
+    error::{
+        S3Error
+    }
+};
@@ -0,0 +1,113 @@
+use crate::{
+    client::{check_response, parse_error_xml, S3Client},
+    error::S3Error,
+    signer::sha256_hex,
+};
+use log::debug;
+use reqwest::Method;
+
+// Описание multipart выгрузки:
+// https://docs.aws.amazon.com/AmazonS3/latest/userguide/mpuoverview.html
+
+/// Начинаем выгрузку, заголовки объекта вроде типа и тегов передаются именно здесь
+pub(crate) async fn create_multipart_upload(
+    client: &S3Client,
+    key: &str,
+    headers: &[(&str, &str)],
+) -> Result<String, S3Error> {
+    // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CreateMultipartUpload.html
+    let response = client
+        .signed_request(
+            Method::POST,
+            key,
+            &[("uploads", "")],
+            headers,
+            &sha256_hex(b""),
+        )
+        .send()
+        .await?;
+    let text = check_response(response).await?.text().await?;
+    debug!("S3 create multipart upload response: {}", text);
+
+    let document = roxmltree::Document::parse(&text)?;
+    document
+        .descendants()
+        .find(|node| node.has_tag_name("UploadId"))
+        .and_then(|node| node.text())
+        .map(|upload_id| upload_id.to_owned())
+        .ok_or(S3Error::UploadIdIsMissing)
+}
+
+/// Собираем объект из частей в порядке их номеров
+pub(crate) async fn complete_multipart_upload(
+    client: &S3Client,
+    key: &str,
+    upload_id: &str,
+    etags: &[(u32, String)],
+) -> Result<Option<String>, S3Error> {
+    // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html
+    let body = {
+        let mut body =
+            String::from(r#"<?xml version="1.0" encoding="UTF-8"?><CompleteMultipartUpload>"#);
+        for (number, etag) in etags.iter() {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                number,
+                etag.replace('&', "&amp;").replace('"', "&quot;")
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+        body
+    };
+
+    let response = client
+        .signed_request(
+            Method::POST,
+            key,
+            &[("uploadId", upload_id)],
+            &[],
+            &sha256_hex(body.as_bytes()),
+        )
+        .body(body)
+        .send()
+        .await?;
+    let status = response.status();
+    let text = check_response(response).await?.text().await?;
+    debug!("S3 complete multipart upload response: {}", text);
+
+    // Ошибка может прийти в теле ответа даже со статусом 200
+    if let Some((code, message)) = parse_error_xml(&text) {
+        return Err(S3Error::ApiError {
+            status,
+            code,
+            message,
+        });
+    }
+
+    let document = roxmltree::Document::parse(&text)?;
+    Ok(document
+        .descendants()
+        .find(|node| node.has_tag_name("ETag"))
+        .and_then(|node| node.text())
+        .map(|etag| etag.to_owned()))
+}
+
+pub(crate) async fn abort_multipart_upload(
+    client: &S3Client,
+    key: &str,
+    upload_id: &str,
+) -> Result<(), S3Error> {
+    // https://docs.aws.amazon.com/AmazonS3/latest/API/API_AbortMultipartUpload.html
+    let response = client
+        .signed_request(
+            Method::DELETE,
+            key,
+            &[("uploadId", upload_id)],
+            &[],
+            &sha256_hex(b""),
+        )
+        .send()
+        .await?;
+    check_response(response).await?;
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use log::debug;
+use std::{
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+////////////////////////////////////////////////////////////////
+
+/// Новый токен, полученный от сервера, вместе с его временем жизни
+#[derive(Debug)]
+pub struct FetchedToken {
+    pub value: String,
+    pub lifetime: Duration,
+}
+
+////////////////////////////////////////////////////////////////
+
+type FetchFuture<E> = Pin<Box<dyn Future<Output = Result<FetchedToken, E>> + Send>>;
+type FetchFn<E> = dyn Fn() -> FetchFuture<E> + Send + Sync;
+
+/// Активный токен и момент, начиная с которого его надо перезапросить
+struct ActiveToken {
+    value: Arc<String>,
+    refresh_time: Instant,
+}
+
+////////////////////////////////////////////////////////////////
+
+/// Общий провайдер короткоживущих токенов.
+/// Токен запрашивается через переданную функцию при первом обращении
+/// и перезапрашивается заранее, за время предзадержки до истечения.
+pub struct TokenProvider<E> {
+    name: &'static str,
+    fetch: Arc<FetchFn<E>>,
+    pre_delay: Duration,
+    active_token: Arc<Mutex<Option<ActiveToken>>>,
+}
+
+impl<E> Clone for TokenProvider<E> {
+    fn clone(&self) -> Self {
+        TokenProvider {
+            name: self.name,
+            fetch: self.fetch.clone(),
+            pre_delay: self.pre_delay,
+            active_token: self.active_token.clone(),
+        }
+    }
+}
+
+impl<E> Debug for TokenProvider<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenProvider")
+            .field("name", &self.name)
+            .field("pre_delay", &self.pre_delay)
+            .finish()
+    }
+}
+
+impl<E: 'static> TokenProvider<E> {
+    pub fn new<F, Fut>(name: &'static str, pre_delay: Duration, fetch: F) -> TokenProvider<E>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<FetchedToken, E>> + Send + 'static,
+    {
+        let fetch: Arc<FetchFn<E>> = Arc::new(move || Box::pin(fetch()) as FetchFuture<E>);
+
+        TokenProvider {
+            name,
+            fetch,
+            pre_delay,
+            active_token: Default::default(),
+        }
+    }
+
+    /// Возвращает текущий токен или запрашивает новый, если текущий скоро истечет
+    pub async fn get_access_token(&self) -> Result<Arc<String>, E> {
+        let mut token_guard = self.active_token.lock().await;
+
+        if let Some(token) = token_guard.as_ref() {
+            if Instant::now() < token.refresh_time {
+                return Ok(token.value.clone());
+            }
+        }
+
+        debug!(
+            "{} token will be expired soon or missing, refresh it",
+            self.name
+        );
+
+        let FetchedToken { value, lifetime } = (self.fetch)().await?;
+
+        // Если предзадержка не меньше времени жизни, то токен сразу считался бы устаревшим,
+        // поэтому в таком случае обновляем его на середине времени жизни
+        let pre_delay = if self.pre_delay < lifetime {
+            self.pre_delay
+        } else {
+            lifetime / 2
+        };
+
+        let token = ActiveToken {
+            value: Arc::new(value),
+            refresh_time: Instant::now() + (lifetime - pre_delay),
+        };
+        let value = token.value.clone();
+        token_guard.replace(token);
+
+        Ok(value)
+    }
+}
+
+////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    fn counting_provider(
+        lifetime: Duration,
+        pre_delay: Duration,
+    ) -> (TokenProvider<Infallible>, Arc<AtomicUsize>) {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let fetch_counter = counter.clone();
+        let provider = TokenProvider::new("Test", pre_delay, move || {
+            let index = fetch_counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok(FetchedToken {
+                    value: format!("token_{}", index),
+                    lifetime,
+                })
+            }
+        });
+        (provider, counter)
+    }
+
+    #[tokio::test]
+    async fn test_token_is_cached() {
+        let (provider, counter) =
+            counting_provider(Duration::from_secs(60 * 20), Duration::from_secs(60 * 3));
+
+        let first = provider.get_access_token().await.unwrap();
+        let second = provider.clone().get_access_token().await.unwrap();
+
+        assert_eq!(first.as_str(), "token_0");
+        assert_eq!(second.as_str(), "token_0");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_lifetime_shorter_than_pre_delay() {
+        let (provider, counter) =
+            counting_provider(Duration::from_secs(60), Duration::from_secs(60 * 3));
+
+        // Токен с временем жизни меньше предзадержки все равно выдается и кешируется
+        assert_eq!(
+            provider.get_access_token().await.unwrap().as_str(),
+            "token_0"
+        );
+        assert_eq!(
+            provider.get_access_token().await.unwrap().as_str(),
+            "token_0"
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+}
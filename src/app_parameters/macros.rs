@@ -22,6 +22,11 @@ macro_rules! params_data_type {
                     $($val_mult_opt:ident: $key_mult_opt:literal : $desc_mult_opt:literal), *
                 }
             )?
+            $(
+                Flag{
+                    $($val_flag:ident: $key_flag:literal : $desc_flag:literal), *
+                }
+            )?
         }
     ) 
     => 
@@ -32,6 +37,7 @@ macro_rules! params_data_type {
             $( $( pub $val_opt: Option<String>, )* )?
             $( $( pub $val_mult: Vec<String>, )* )?
             $( $( pub $val_mult_opt: Option<Vec<String>>, )* )?
+            $( $( pub $val_flag: bool, )* )?
         }
         impl crate::app_parameters::traits::AppParams for $type_id {
             fn get_args() -> Vec<clap::Arg<'static, 'static>>{
@@ -80,6 +86,14 @@ macro_rules! params_data_type {
                                 .takes_value(true),
                         )*
                     )?
+                    $(
+                        $(
+                            clap::Arg::with_name($key_flag)
+                                .long($key_flag)
+                                .help($desc_flag)
+                                .takes_value(false),
+                        )*
+                    )?
                 ]
             }
             fn parse(values: &clap::ArgMatches) -> Option<Self> {
@@ -92,7 +106,7 @@ macro_rules! params_data_type {
                                 .map(|v|{
                                     v.to_owned()
                                 })
-                                .collect::<Vec<String>>()
+                                .collect::<Vec<String>>(),
                     )* )?
                     $( $( $val_mult_opt: 
                             values
@@ -106,6 +120,7 @@ macro_rules! params_data_type {
                                     vector
                                 }), 
                     )* )?
+                    $( $( $val_flag: values.is_present($key_flag), )* )?
                 })
             }
         }
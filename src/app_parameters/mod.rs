@@ -24,11 +24,16 @@ pub use self::{
 #[derive(Debug)]
 pub struct AppParameters{
     pub amazon: Option<AmazonParams>,
+    pub app_gallery: Option<AppGalleryParams>,
+    pub rustore: Option<RuStoreParams>,
     pub app_center: Option<AppCenterParams>,
     pub goolge_drive: Option<GoogleDriveParams>,
     pub goolge_play: Option<GooglePlayParams>,
+    pub firebase_app_distribution: Option<FirebaseAppDistributionParams>,
     pub ios: Option<IOSParams>,
     pub windows: Option<WindowsStoreParams>,
+    pub azure_blob: Option<AzureBlobParams>,
+    pub s3: Option<S3Params>,
     pub facebook: Option<FacebookInstantParams>,
     pub ssh: Option<SSHParams>,
     pub slack: Option<SlackParams>,
@@ -41,11 +46,16 @@ impl AppParameters{
             .version("1.0.0")
             .setting(AppSettings::ColorAuto)
             .args(&AmazonParams::get_args())
+            .args(&AppGalleryParams::get_args())
+            .args(&RuStoreParams::get_args())
             .args(&AppCenterParams::get_args())
             .args(&GoogleDriveParams::get_args())
             .args(&GooglePlayParams::get_args())
+            .args(&FirebaseAppDistributionParams::get_args())
             .args(&IOSParams::get_args())
             .args(&WindowsStoreParams::get_args())
+            .args(&AzureBlobParams::get_args())
+            .args(&S3Params::get_args())
             .args(&FacebookInstantParams::get_args())
             .args(&SSHParams::get_args())
             .args(&SlackParams::get_args());
@@ -66,11 +76,16 @@ impl AppParameters{
     fn matches_to_struct(matches: ArgMatches) -> AppParameters {
         AppParameters {
             amazon: AmazonParams::parse(&matches),
+            app_gallery: AppGalleryParams::parse(&matches),
+            rustore: RuStoreParams::parse(&matches),
             app_center: AppCenterParams::parse(&matches),
             goolge_drive: GoogleDriveParams::parse(&matches),
             goolge_play: GooglePlayParams::parse(&matches),
+            firebase_app_distribution: FirebaseAppDistributionParams::parse(&matches),
             ios: IOSParams::parse(&matches),
             windows: WindowsStoreParams::parse(&matches),
+            azure_blob: AzureBlobParams::parse(&matches),
+            s3: S3Params::parse(&matches),
             facebook: FacebookInstantParams::parse(&matches),
             ssh: SSHParams::parse(&matches),
             slack: SlackParams::parse(&matches)
@@ -130,4 +145,27 @@ mod tests{
         ]));
         assert_eq!(app_center_params.build_description, Some(app_center_description.to_owned()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_app_parameters_flags(){
+        let test_parameters = [
+            "application",
+            "--amazon_input_file", "amazon.apk",
+            "--amazon_commit"
+        ];
+
+        let matches = AppParameters::get_params_app(None)
+            .get_matches_from(&test_parameters);
+
+        let result = AppParameters::matches_to_struct(matches);
+
+        let amazon_params = result
+            .amazon
+            .expect("Amazon values failed");
+
+        assert_eq!(amazon_params.file_paths, vec!["amazon.apk".to_owned()]);
+        assert!(amazon_params.commit);
+        assert!(!amazon_params.discard_existing_edit);
+        assert!(!amazon_params.replace_existing);
+    }
+}
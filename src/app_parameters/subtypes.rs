@@ -4,8 +4,54 @@
 
 params_data_type!(
     AmazonParams{
+        Mult{
+            file_paths : "amazon_input_file" : "Comma separated Amazon uploading APK files"
+        }
+        MultOpt{
+            whats_new: "amazon_whats_new" : "Comma separated localized what's new text files: <language>:<file path>, listing for the language must already exist",
+            device_targeting: "amazon_device_targeting" : "Comma separated APK targeting: <apk file name>:<device id>[;<device id>...]"
+        }
+        Flag{
+            commit : "amazon_commit" : "Validate and commit Amazon edit after uploading",
+            discard_existing_edit : "amazon_discard_existing_edit" : "Delete existing open Amazon edit instead of reusing it",
+            replace_existing : "amazon_replace_existing" : "Replace existing APKs in place keeping their targeting, matched by file name; unmatched ones are removed"
+        }
+    }
+);
+
+//////////////////////////////////////////////////////////////////////
+
+params_data_type!(
+    AppGalleryParams{
+        Req{
+            file_path : "app_gallery_input_file" : "Huawei AppGallery uploading APK or AAB file"
+        }
+        Opt{
+            phased_release_percent : "app_gallery_phased_release_percent" : "Release to this percent of users first, from 0.01 to 100",
+            phased_release_days : "app_gallery_phased_release_days" : "Phased release duration in days, 7 by default",
+            phased_release_description : "app_gallery_phased_release_description" : "Phased release description",
+            compile_timeout_minutes : "app_gallery_compile_timeout" : "Minutes to wait for AAB compilation, 30 by default"
+        }
+        Flag{
+            submit : "app_gallery_submit" : "Submit Huawei AppGallery version for review after uploading"
+        }
+    }
+);
+
+//////////////////////////////////////////////////////////////////////
+
+params_data_type!(
+    RuStoreParams{
         Req{
-            file_path : "amazon_input_file" : "Amazon uploading file"
+            file_path : "rustore_input_file" : "RuStore uploading APK or AAB file"
+        }
+        Opt{
+            whats_new_file : "rustore_whats_new_file" : "File with release notes text for RuStore version",
+            publish_type : "rustore_publish_type" : "Publish after moderation: instantly (default) or manual",
+            submit_timeout_minutes : "rustore_submit_timeout" : "Minutes to wait for moderation submit acceptance, 5 by default"
+        }
+        Flag{
+            submit : "rustore_submit" : "Submit RuStore version for moderation after uploading"
         }
     }
 );
@@ -37,12 +83,26 @@ params_data_type!(
             target_folder_id : "google_drive_target_folder_id" : "Google drive folder ID"
         }
         Opt{
-            target_subfolder_name : "google_drive_target_subfolder_name" : "Google drive subfolder name",
+            target_subfolder_name : "google_drive_target_subfolder_name" : "Google drive subfolder name or path like 'release/2026-10/build-123'",
             target_owner_email : "google_drive_target_owner_email" : "Google drive folder owner email",
-            target_domain: "google_drive_target_domain" : "Google drive shared domain"
+            target_domain: "google_drive_target_domain" : "Google drive shared domain",
+            on_conflict: "google_drive_on_conflict" : "What to do with existing file with the same name: replace, new-revision, skip, rename",
+            share_role: "google_drive_share_role" : "Role for shared access: reader, commenter, writer (default)",
+            share_expiration_days: "google_drive_share_expiration_days" : "Shared access expiration in days, only for users and groups",
+            share_notify: "google_drive_share_notify" : "Send notification email to users and groups: true (default) or false",
+            share_target: "google_drive_share_target" : "Apply sharing to each uploaded file (files, default) or to the target folder (folder)",
+            retention_max_age_days: "google_drive_retention_max_age_days" : "Remove builds older than this number of days from the builds folder",
+            retention_keep_newest: "google_drive_retention_keep_newest" : "Keep only this number of newest builds in the builds folder",
+            retention_mode: "google_drive_retention_mode" : "How to remove old builds: trash (default) or delete",
+            retention_dry_run: "google_drive_retention_dry_run" : "Only list old builds without removing: true or false (default)",
+            upload_concurrency: "google_drive_upload_concurrency" : "Max number of parallel file uploads, 4 by default"
         }
         Mult {
-            files : "google_drive_files" : "Comma separated files list"
+            files : "google_drive_files" : "Comma separated files or directories list, directories are uploaded recursively"
+        }
+        MultOpt {
+            share: "google_drive_share" : "Comma separated grantees: user:<email>, group:<email>, domain:<domain>, anyone",
+            retention_name_patterns: "google_drive_retention_name_patterns" : "Comma separated name patterns with * and ? for builds cleanup"
         }
     }
 );
@@ -63,11 +123,44 @@ params_data_type!(
 
 //////////////////////////////////////////////////////////////////////
 
+params_data_type!(
+    FirebaseAppDistributionParams{
+        Req{
+            app_id : "firebase_app_id" : "Firebase app id, like '1:1234567890:android:0a1b2c3d4e5f67890'",
+            file_path : "firebase_upload_file" : "File path for firebase app distribution uploading: .apk, .aab or .ipa"
+        }
+        Opt{
+            release_notes_file : "firebase_release_notes_file" : "Release notes text file",
+            processing_timeout_minutes : "firebase_processing_timeout" : "Minutes to wait for firebase build processing, 10 by default"
+        }
+        MultOpt{
+            groups : "firebase_groups" : "Comma separated firebase tester group aliases for the release",
+            testers : "firebase_testers" : "Comma separated firebase tester emails for the release"
+        }
+        Flag{
+            release_notes_from_git : "firebase_release_notes_from_git" : "Add git branch and commit to the release notes"
+        }
+    }
+);
+
+//////////////////////////////////////////////////////////////////////
+
 params_data_type!(
     IOSParams{
         Req{
             ipa_file_path : "ios_app_store_ipa" : "Ipa file for iOS App store"
         }
+        Opt{
+            processing_timeout_minutes : "ios_processing_timeout" : "Minutes to wait for App Store Connect build processing, 60 by default"
+        }
+        MultOpt{
+            testflight_what_to_test: "ios_testflight_what_to_test" : "Comma separated localized TestFlight what to test text files: <locale>:<file path>",
+            testflight_groups: "ios_testflight_groups" : "Comma separated TestFlight beta group names for the uploaded build",
+            testflight_testers: "ios_testflight_testers" : "Comma separated TestFlight individual tester emails for the uploaded build"
+        }
+        Flag{
+            testflight_submit_review: "ios_testflight_submit_for_review" : "Submit the uploaded build for TestFlight beta review"
+        }
     }
 );
 
@@ -80,19 +173,103 @@ params_data_type!(
         }
         Opt{
             // Production
-            production_zip_file_path : "windows_production_zip_file_path" : "ZIP file with .appx or .appxupload inside",
+            production_zip_file_path : "windows_production_zip_file_path" : "ZIP file with .appx or .appxupload inside, can't be used with windows_production_packages",
             production_submission_name : "windows_production_submission_name": "Submission name in admin console",
+            publish_mode : "windows_publish_mode" : "Production publish mode: manual (default), immediate, specific-date",
+            publish_date : "windows_publish_date" : "Production publish date in RFC 3339 format for specific-date publish mode",
+            rollout_percentage : "windows_rollout_percentage" : "Enable gradual production package rollout with this percentage of users",
+            mandatory_update_date : "windows_mandatory_update_date" : "Mandatory update effective date in RFC 3339 format",
+            rollout_command : "windows_rollout_command" : "Manage rollout of the published production submission before uploading: update:<percentage>, halt, finalize",
 
             // Test
-            test_flight_zip_file_path : "windows_test_flight_zip_file_path" : "ZIP file with .appx or .appxupload inside",
-            test_flight_name : "windows_test_flight_name": "Test flight name in admin console"
+            test_flight_zip_file_path : "windows_test_flight_zip_file_path" : "ZIP file with .appx or .appxupload inside, can't be used with windows_test_flight_packages",
+            test_flight_name : "windows_test_flight_name": "Test flight name in admin console, existing flight with the same name is reused when its groups and rank match",
+            test_flight_rank_higher_than : "windows_test_flight_rank_higher_than": "Friendly name of the flight to rank a new flight higher than",
+
+            // Незавершенные сабмиссии
+            pending : "windows_pending" : "What to do with existing pending submission: reuse, delete, fail. Production fails and flights reuse it by default",
+
+            // Проверка пакетов
+            package_version : "windows_package_version" : "Expected version of packages from AppxManifest, like 1.2.3.0",
+
+            // Выгрузка файлика
+            upload_block_size : "windows_upload_block_size" : "Upload block size in megabytes, 8 by default",
+            upload_concurrency : "windows_upload_concurrency" : "Max number of parallel block uploads, 8 by default",
+
+            // Ожидание сертификации
+            certification_poll_interval : "windows_certification_poll_interval" : "Seconds between certification status requests, 60 by default",
+            certification_timeout : "windows_certification_timeout" : "Minutes to wait for certification, 4320 (3 days) by default"
+        }
+        MultOpt {
+            test_flight_groups : "windows_test_flight_groups" : "Microsoft flight groups for tests",
+            whats_new : "windows_whats_new" : "Comma separated production what's new text files: <language>:<file path>",
+            description : "windows_description" : "Comma separated production description text files: <language>:<file path>",
+            features : "windows_features" : "Comma separated production features files with one feature per line: <language>:<file path>",
+            production_packages : "windows_production_packages" : "Comma separated production package files or directories with them (.msixupload, .appxupload, .msixbundle, ...), zipped automatically",
+            test_flight_packages : "windows_test_flight_packages" : "Comma separated test package files or directories with them (.msixupload, .appxupload, .msixbundle, ...), zipped automatically",
+            listing_images : "windows_listing_images" : "Comma separated production listing images added to the archive built from packages: <language>:<image type>:<file path>",
+            package_architectures : "windows_package_architectures" : "Comma separated architectures which packages must contain: x86, x64, arm, arm64, neutral"
+        }
+        Flag {
+            wait_certification : "windows_wait_certification" : "Wait until submission passes certification and becomes published or pending publication",
+            mandatory_update : "windows_mandatory_update" : "Mark production packages as mandatory update"
+        }
+    }
+);
+
+//////////////////////////////////////////////////////////////////////
+
+params_data_type!(
+    AzureBlobParams{
+        Req{
+            sas_url : "azure_blob_sas_url" : "Azure Blob Storage container URL with SAS token, write permission is required, read permission allows to resume interrupted uploads"
+        }
+        Opt{
+            path_prefix : "azure_blob_path_prefix" : "Path inside container for uploaded files, like 'builds/123'",
+            content_type : "azure_blob_content_type" : "Content type of uploaded blobs",
+            cache_control : "azure_blob_cache_control" : "Cache control of uploaded blobs",
+            block_size : "azure_blob_block_size" : "Upload block size in megabytes, 8 by default",
+            concurrency : "azure_blob_concurrency" : "Max number of parallel block uploads, 8 by default"
+        }
+        Mult {
+            files : "azure_blob_files" : "Comma separated files for uploading"
+        }
+        MultOpt {
+            metadata : "azure_blob_metadata" : "Comma separated blob metadata: <name>=<value>"
+        }
+    }
+);
+
+//////////////////////////////////////////////////////////////////////
+
+params_data_type!(
+    S3Params{
+        Req{
+            endpoint : "s3_endpoint" : "S3 compatible storage endpoint, like 'https://s3.eu-central-1.amazonaws.com'",
+            bucket : "s3_bucket" : "S3 bucket name"
+        }
+        Opt{
+            region : "s3_region" : "S3 region, us-east-1 by default",
+            prefix : "s3_prefix" : "Key prefix for uploaded files, like 'builds/123'",
+            content_type : "s3_content_type" : "Content type of uploaded objects",
+            presign_expiration : "s3_presign_expiration_hours" : "Generate presigned download urls valid for this number of hours, 168 max",
+            part_size : "s3_part_size" : "Multipart upload part size in megabytes, 16 by default",
+            concurrency : "s3_concurrency" : "Max number of parallel part uploads, 4 by default"
+        }
+        Mult {
+            files : "s3_files" : "Comma separated files for uploading"
         }
         MultOpt {
-            test_flight_groups : "windows_test_flight_groups" : "Microsoft flight groups for tests"
+            tags : "s3_tags" : "Comma separated object tags: <key>=<value>"
+        }
+        Flag {
+            virtual_hosted_style : "s3_virtual_hosted_style" : "Use bucket name in host instead of path"
         }
     }
 );
 
+//////////////////////////////////////////////////////////////////////
+
 params_data_type!(
     FacebookInstantParams{
         Req{
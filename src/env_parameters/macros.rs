@@ -18,7 +18,7 @@ macro_rules! env_params_type {
             )?
         }
     ) => {
-        #[derive(Debug)]
+        #[derive(Debug, Clone)]
         pub struct $type {
             $( $(pub $val_req: String,)* )?
             $( $(pub $val_opt: Option<String>,)* )?
@@ -46,12 +46,16 @@ macro_rules! describe_env_values {
 describe_env_values!(
     git: GitEnvironment,
     amazon: AmazonEnvironment,
+    app_gallery: AppGalleryEnvironment,
+    rustore: RuStoreEnvironment,
     app_center: AppCenterEnvironment,
     google_play: GooglePlayEnvironment,
     google_drive: GoogleDriveEnvironment,
+    firebase_app_distribution: FirebaseAppDistributionEnvironment,
     ios: IOSEnvironment,
     windows: WindowsStoreEnvironment,
     facebook: FacebookInstantEnvironment,
+    s3: S3Environment,
     ssh: SSHEnvironment,
     target_slack: TargetSlackEnvironment,
     result_slack: ResultSlackEnvironment
@@ -25,6 +25,30 @@ env_params_type!(
 
 /////////////////////////////////////////////////
 
+env_params_type!(
+    AppGalleryEnvironment{
+        Req{
+            client_id: "APP_GALLERY_CLIENT_ID",
+            client_secret: "APP_GALLERY_CLIENT_SECRET",
+            app_id: "APP_GALLERY_APP_ID"
+        }
+    }
+);
+
+/////////////////////////////////////////////////
+
+env_params_type!(
+    RuStoreEnvironment{
+        Req{
+            key_id: "RUSTORE_KEY_ID",
+            private_key: "RUSTORE_PRIVATE_KEY",
+            package_name: "RUSTORE_PACKAGE_NAME"
+        }
+    }
+);
+
+/////////////////////////////////////////////////
+
 env_params_type!(
     AppCenterEnvironment{
         Req{
@@ -39,11 +63,12 @@ env_params_type!(
 
 env_params_type!(
     GooglePlayEnvironment{
-        Req{
+        Opt{
             // email: "GOOGLE_PLAY_SERVICE_EMAIL",
             // key_id: "GOOGLE_PLAY_KEY_ID",
             // key: "GOOGLE_PLAY_KEY"
-            auth_file: "GOOGLE_PLAY_AUTH_JSON_FILE"
+            auth_file: "GOOGLE_PLAY_AUTH_JSON_FILE",
+            auth_json: "GOOGLE_PLAY_AUTH_JSON"
         }
     }
 );
@@ -52,11 +77,24 @@ env_params_type!(
 
 env_params_type!(
     GoogleDriveEnvironment{
-        Req{
+        Opt{
             // email: "GOOGLE_DRIVE_SERVICE_EMAIL",
             // key_id: "GOOGLE_DRIVE_KEY_ID",
             // key: "GOOGLE_DRIVE_KEY"
-            auth_file: "GOOGLE_DRIVE_AUTH_JSON_FILE"
+            auth_file: "GOOGLE_DRIVE_AUTH_JSON_FILE",
+            auth_json: "GOOGLE_DRIVE_AUTH_JSON",
+            subject: "GOOGLE_DRIVE_SUBJECT"
+        }
+    }
+);
+
+/////////////////////////////////////////////////
+
+env_params_type!(
+    FirebaseAppDistributionEnvironment{
+        Opt{
+            auth_file: "FIREBASE_AUTH_JSON_FILE",
+            auth_json: "FIREBASE_AUTH_JSON"
         }
     }
 );
@@ -65,9 +103,13 @@ env_params_type!(
 
 env_params_type!(
     IOSEnvironment{
-        Req{
+        Opt{
             user: "IOS_USER",
-            pass: "IOS_PASS"
+            pass: "IOS_PASS",
+            api_issuer_id: "IOS_API_ISSUER_ID",
+            api_key_id: "IOS_API_KEY_ID",
+            api_key_file: "IOS_API_KEY_FILE",
+            api_key: "IOS_API_KEY"
         }
     }
 );
@@ -97,6 +139,20 @@ env_params_type!(
 
 /////////////////////////////////////////////////
 
+env_params_type!(
+    S3Environment{
+        Req{
+            access_key_id: "S3_ACCESS_KEY_ID",
+            secret_access_key: "S3_SECRET_ACCESS_KEY"
+        }
+        Opt{
+            session_token: "S3_SESSION_TOKEN"
+        }
+    }
+);
+
+/////////////////////////////////////////////////
+
 env_params_type!(
     SSHEnvironment{
         Req{
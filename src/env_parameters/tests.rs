@@ -76,10 +76,14 @@ fn test_env_environment(){
     test_types! (
         GitEnvironment,
         AmazonEnvironment,
+        AppGalleryEnvironment,
+        RuStoreEnvironment,
         AppCenterEnvironment,
         GooglePlayEnvironment,
         GoogleDriveEnvironment,
+        FirebaseAppDistributionEnvironment,
         IOSEnvironment,
+        S3Environment,
         SSHEnvironment,
         TargetSlackEnvironment,
         ResultSlackEnvironment
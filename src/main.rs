@@ -11,9 +11,10 @@ use self::{
     env_parameters::{AppEnvValues, ResultSlackEnvironment},
     result_senders::{ResultSender, SlackResultSender, TerminalSender},
     uploaders::{
-        upload_by_ssh, upload_in_amazon, upload_in_app_center, upload_in_facebook_instant,
-        upload_in_google_drive, upload_in_google_play, upload_in_ios, upload_in_windows_store,
-        UploadResult,
+        upload_by_ssh, upload_in_amazon, upload_in_app_center, upload_in_app_gallery,
+        upload_in_azure_blob, upload_in_facebook_instant, upload_in_firebase_app_distribution,
+        upload_in_google_drive, upload_in_google_play, upload_in_ios, upload_in_rustore,
+        upload_in_s3, upload_in_windows_store, UploadResult,
     },
 };
 use futures::future::{join_all, select_all, Future, FutureExt};
@@ -97,7 +98,7 @@ fn build_uploaders(
             http_client.clone(),
             app_center_env_params,
             app_center_app_params,
-            env_params.git,
+            env_params.git.clone(),
         )
         .boxed();
         info!("App center uploading task created");
@@ -122,6 +123,22 @@ fn build_uploaders(
         active_workers.push(fut);
     }
 
+    // Создаем задачу выгрузки в Firebase App Distribution
+    if let (Some(env_params_firebase), Some(app_params)) = (
+        env_params.firebase_app_distribution,
+        app_parameters.firebase_app_distribution,
+    ) {
+        let fut = upload_in_firebase_app_distribution(
+            http_client.clone(),
+            env_params_firebase,
+            app_params,
+            env_params.git.clone(),
+        )
+        .boxed();
+        info!("Firebase app distribution uploading task created");
+        active_workers.push(fut);
+    }
+
     // Создаем задачу выгрузки в Amazon
     if let (Some(env_params), Some(app_params)) = (env_params.amazon, app_parameters.amazon) {
         let fut = upload_in_amazon(http_client.clone(), env_params, app_params).boxed();
@@ -129,9 +146,25 @@ fn build_uploaders(
         active_workers.push(fut);
     }
 
+    // Создаем задачу выгрузки в Huawei AppGallery
+    if let (Some(env_params), Some(app_params)) =
+        (env_params.app_gallery, app_parameters.app_gallery)
+    {
+        let fut = upload_in_app_gallery(http_client.clone(), env_params, app_params).boxed();
+        info!("Huawei AppGallery uploading task created");
+        active_workers.push(fut);
+    }
+
+    // Создаем задачу выгрузки в RuStore
+    if let (Some(env_params), Some(app_params)) = (env_params.rustore, app_parameters.rustore) {
+        let fut = upload_in_rustore(http_client.clone(), env_params, app_params).boxed();
+        info!("RuStore uploading task created");
+        active_workers.push(fut);
+    }
+
     // Создаем задачу выгрузки в IOS
     if let (Some(env_params), Some(app_params)) = (env_params.ios, app_parameters.ios) {
-        let fut = upload_in_ios(env_params, app_params).boxed();
+        let fut = upload_in_ios(http_client.clone(), env_params, app_params).boxed();
         info!("IOS uploading task created");
         active_workers.push(fut);
     }
@@ -143,6 +176,26 @@ fn build_uploaders(
         active_workers.push(fut);
     }
 
+    // Создаем задачу выгрузки в Azure blob storage, SAS урл передается параметром
+    if let Some(app_params) = app_parameters.azure_blob {
+        let fut = upload_in_azure_blob(http_client.clone(), app_params).boxed();
+        info!("Azure blob uploading task created");
+        active_workers.push(fut);
+    }
+
+    // Создаем задачу выгрузки в S3 совместимое хранилище, git информация идет в теги объектов
+    if let (Some(s3_env_params), Some(app_params)) = (env_params.s3, app_parameters.s3) {
+        let fut = upload_in_s3(
+            http_client.clone(),
+            s3_env_params,
+            app_params,
+            env_params.git,
+        )
+        .boxed();
+        info!("S3 uploading task created");
+        active_workers.push(fut);
+    }
+
     // Создаем задачу выгрузки в Windows store
     if let (Some(env_params), Some(app_params)) = (env_params.facebook, app_parameters.facebook) {
         let fut = upload_in_facebook_instant(http_client, env_params, app_params).boxed();
@@ -0,0 +1,253 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    error,
+    fmt::{self, Display, Formatter},
+};
+
+/*
+Пример вывода `xcrun altool --output-format json` с ошибкой:
+{
+  "os-version" : "13.4.0",
+  "product-errors" : [
+    {
+      "code" : -19232,
+      "message" : "The provided entity includes an attribute with a value that has already been used",
+      "userInfo" : {
+        "NSLocalizedDescription" : "...",
+        "NSLocalizedFailureReason" : "The bundle version must be higher than the previously uploaded version: '42'."
+      }
+    }
+  ],
+  "tool-version" : "5.0.1"
+}
+*/
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Deserialize, Debug)]
+struct AltoolProductError {
+    code: i64,
+    message: String,
+    #[serde(rename = "userInfo", default)]
+    user_info: HashMap<String, Value>,
+}
+
+impl AltoolProductError {
+    /// Причина ошибки обычно подробнее основного сообщения
+    fn failure_reason(&self) -> Option<&str> {
+        self.user_info
+            .get("NSLocalizedFailureReason")
+            .and_then(|value| value.as_str())
+            .filter(|reason| !reason.trim().is_empty())
+    }
+
+    /// Весь текст ошибки, по которому определяем ее тип
+    fn full_text(&self) -> String {
+        let mut text = self.message.clone();
+        for value in self.user_info.values() {
+            if let Some(value) = value.as_str() {
+                text.push('\n');
+                text.push_str(value);
+            }
+        }
+        text.to_lowercase()
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AltoolOutput {
+    #[serde(rename = "success-message")]
+    success_message: Option<String>,
+    #[serde(rename = "product-errors", default)]
+    product_errors: Vec<AltoolProductError>,
+}
+
+/// Вывод altool может содержать мусор перед JSON, поэтому ищем начало объекта
+pub fn parse_altool_output(text: &str) -> Option<AltoolOutput> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&text[start..=end]).ok()
+}
+
+impl AltoolOutput {
+    /// В случае успеха возвращаем сообщение altool
+    pub fn into_result(self) -> Result<Option<String>, AltoolError> {
+        if self.product_errors.is_empty() {
+            return Ok(self.success_message);
+        }
+
+        let mut kinds = Vec::with_capacity(self.product_errors.len());
+        let mut issues = Vec::with_capacity(self.product_errors.len());
+        for err in self.product_errors {
+            kinds.push(IssueKind::detect(&err.full_text(), err.code));
+            issues.push(AltoolIssue {
+                code: err.code,
+                message: match err.failure_reason() {
+                    Some(reason) => format!("{} {}", err.message.trim(), reason.trim()),
+                    None => err.message.trim().to_owned(),
+                },
+            });
+        }
+
+        // Постоянные ошибки приоритетнее временных
+        let err = if kinds.contains(&IssueKind::DuplicateBuild) {
+            AltoolError::DuplicateBuild(issues)
+        } else if kinds.contains(&IssueKind::InvalidCredentials) {
+            AltoolError::InvalidCredentials(issues)
+        } else if kinds.iter().all(|kind| *kind == IssueKind::Transient) {
+            AltoolError::Transient(issues)
+        } else {
+            AltoolError::Validation(issues)
+        };
+        Err(err)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug, PartialEq)]
+enum IssueKind {
+    DuplicateBuild,
+    InvalidCredentials,
+    Transient,
+    Validation,
+}
+
+impl IssueKind {
+    fn detect(text: &str, code: i64) -> IssueKind {
+        const DUPLICATE_CODE: i64 = -19232;
+        const DUPLICATE_MARKERS: &[&str] = &[
+            "already been used",
+            "already been uploaded",
+            "must be higher than the previously uploaded",
+            "entity_error.attribute.invalid.duplicate",
+        ];
+        const CREDENTIALS_MARKERS: &[&str] = &[
+            "unable to authenticate",
+            "authentication failed",
+            "invalid username and password",
+            "app-specific password",
+            "not_authorized",
+        ];
+        const TRANSIENT_MARKERS: &[&str] = &[
+            "nsurlerrordomain",
+            "timed out",
+            "network connection was lost",
+            "internet connection appears to be offline",
+            "internal server error",
+            "service unavailable",
+            "try again later",
+        ];
+
+        let contains_any = |markers: &[&str]| markers.iter().any(|marker| text.contains(marker));
+
+        if code == DUPLICATE_CODE || contains_any(DUPLICATE_MARKERS) {
+            IssueKind::DuplicateBuild
+        } else if contains_any(CREDENTIALS_MARKERS) {
+            IssueKind::InvalidCredentials
+        } else if contains_any(TRANSIENT_MARKERS) {
+            IssueKind::Transient
+        } else {
+            IssueKind::Validation
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AltoolIssue {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Ошибки altool, текст которых пригоден для отправки в Slack
+#[derive(Debug)]
+pub enum AltoolError {
+    DuplicateBuild(Vec<AltoolIssue>),
+    InvalidCredentials(Vec<AltoolIssue>),
+    Transient(Vec<AltoolIssue>),
+    Validation(Vec<AltoolIssue>),
+}
+
+impl AltoolError {
+    /// Имеет ли смысл повторять выгрузку
+    pub fn is_transient(&self) -> bool {
+        matches!(self, AltoolError::Transient(_))
+    }
+}
+
+impl Display for AltoolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (title, issues) = match self {
+            AltoolError::DuplicateBuild(issues) => {
+                ("build with this version is already uploaded", issues)
+            }
+            AltoolError::InvalidCredentials(issues) => {
+                ("invalid App Store Connect credentials", issues)
+            }
+            AltoolError::Transient(issues) => {
+                ("App Store Connect is temporarily unavailable", issues)
+            }
+            AltoolError::Validation(issues) => ("App Store Connect validation failed", issues),
+        };
+        write!(f, "iOS uploading failed, {}:", title)?;
+        for issue in issues.iter() {
+            write!(f, "\n- [{}] {}", issue.code, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for AltoolError {}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> AltoolError {
+        parse_altool_output(text)
+            .expect("Output must be parsed")
+            .into_result()
+            .expect_err("Output must contain errors")
+    }
+
+    #[test]
+    fn test_altool_output_classification() {
+        let success =
+            r#"{"success-message": "No errors uploading 'app.ipa'", "tool-version": "5.0.1"}"#;
+        let message = parse_altool_output(success).unwrap().into_result().unwrap();
+        assert_eq!(message.as_deref(), Some("No errors uploading 'app.ipa'"));
+
+        let duplicate = r#"2023-01-01 altool[1:2] *** Error
+            {"product-errors": [{"code": -19232, "message": "The provided entity includes an attribute with a value that has already been used",
+            "userInfo": {"NSLocalizedFailureReason": "The bundle version must be higher than the previously uploaded version: '42'."}}]}"#;
+        let err = parse_error(duplicate);
+        assert!(matches!(err, AltoolError::DuplicateBuild(_)));
+        assert!(err.to_string().contains("'42'"));
+
+        let credentials = r#"{"product-errors": [{"code": -22938, "message": "Unable to authenticate.", "userInfo": {}}]}"#;
+        assert!(matches!(
+            parse_error(credentials),
+            AltoolError::InvalidCredentials(_)
+        ));
+
+        let transient = r#"{"product-errors": [{"code": -1001, "message": "The request timed out.",
+            "userInfo": {"NSUnderlyingError": "Error Domain=NSURLErrorDomain Code=-1001"}}]}"#;
+        assert!(parse_error(transient).is_transient());
+
+        let validation = r#"{"product-errors": [
+            {"code": -19208, "message": "Invalid Bundle. The bundle does not support the minimum OS Version."},
+            {"code": -1001, "message": "The request timed out."}]}"#;
+        let err = parse_error(validation);
+        assert!(matches!(err, AltoolError::Validation(ref issues) if issues.len() == 2));
+        assert!(!err.is_transient());
+
+        assert!(parse_altool_output("xcrun: error: unable to find utility \"altool\"").is_none());
+    }
+}
@@ -3,54 +3,153 @@ use crate::{
     env_parameters::AmazonEnvironment,
     uploaders::{UploadResult, UploadResultData},
 };
-use amazon_client::{request_token, AmazonAccessToken, AmazonClient, AmazonUploadTask};
+use amazon_client::{
+    AmazonApkUpload, AmazonClient, AmazonListingUpdate, AmazonTokenProvider, AmazonUploadTask,
+};
 use log::{debug, error};
-use std::path::Path;
+use std::{collections::HashMap, error::Error, path::Path};
 use tap::TapFallible;
 
+/// Читаем тексты "What's new" из файлов вида `<язык>:<путь к файлу>`
+async fn read_listing_updates(
+    app_params: &AmazonParams,
+) -> Result<Vec<AmazonListingUpdate>, Box<dyn Error + Send + Sync>> {
+    let mut updates = Vec::new();
+    for value in app_params.whats_new.iter().flatten() {
+        let (language, file_path) = value.split_once(':').ok_or_else(|| {
+            format!(
+                "Amazon: invalid what's new value '{}', expected <language>:<file path>",
+                value
+            )
+        })?;
+
+        let text = tokio::fs::read_to_string(file_path).await.tap_err(|err| {
+            error!("What's new file read failed: {}, err: {}", file_path, err);
+        })?;
+
+        updates.push(AmazonListingUpdate {
+            language: language.trim().to_owned(),
+            recent_changes: Some(text.trim().to_owned()),
+            ..Default::default()
+        });
+    }
+    Ok(updates)
+}
+
+/// Разбираем таргетинг вида `<имя файла APK>:<устройство>[;<устройство>...]`
+fn parse_device_targeting(
+    app_params: &AmazonParams,
+) -> Result<HashMap<String, Vec<String>>, Box<dyn Error + Send + Sync>> {
+    let mut targeting = HashMap::new();
+    for value in app_params.device_targeting.iter().flatten() {
+        let (file_name, devices) = value.split_once(':').ok_or_else(|| {
+            format!(
+                "Amazon: invalid device targeting value '{}', expected <apk file name>:<device id>",
+                value
+            )
+        })?;
+
+        let file_name = file_name.trim();
+        let is_known_file = app_params.file_paths.iter().any(|path| {
+            Path::new(path).file_name().and_then(|name| name.to_str()) == Some(file_name)
+        });
+        if !is_known_file {
+            return Err(format!(
+                "Amazon: device targeting for unknown APK file '{}'",
+                file_name
+            )
+            .into());
+        }
+
+        let device_ids: Vec<String> = devices
+            .split(';')
+            .map(|device| device.trim())
+            .filter(|device| !device.is_empty())
+            .map(|device| device.to_owned())
+            .collect();
+
+        targeting.insert(file_name.to_owned(), device_ids);
+    }
+    Ok(targeting)
+}
+
 pub async fn upload_in_amazon(
     http_client: reqwest::Client,
     env_params: AmazonEnvironment,
     app_params: AmazonParams,
 ) -> UploadResult {
-    let token: AmazonAccessToken = request_token(
-        &http_client,
-        &env_params.client_id,
-        &env_params.client_secret,
-    )
-    .await
-    .tap_err(|err| {
-        error!("Access token request failed: {}", err);
-    })?;
-
+    // Токен обновляется провайдером сам, но первый запрашиваем сразу, чтобы быстрее увидеть ошибку
+    let token_provider = AmazonTokenProvider::new(
+        http_client.clone(),
+        env_params.client_id,
+        env_params.client_secret,
+    );
     {
-        let token_str = token.as_str_checked().tap_err(|err| {
-            error!("Invalid token: {}", err);
+        let token_str = token_provider.get_access_token().await.tap_err(|err| {
+            error!("Access token request failed: {}", err);
         })?;
         debug!("Amazon token: {}", token_str);
     }
 
-    let file_path = Path::new(&app_params.file_path);
+    // Заранее читаем тексты и таргетинг, чтобы не начинать редактирование с некорректными данными
+    let listings = read_listing_updates(&app_params).await?;
+    let targeting = parse_device_targeting(&app_params)?;
+
+    let apks: Vec<AmazonApkUpload> = app_params
+        .file_paths
+        .iter()
+        .map(|path| {
+            let file_path = Path::new(path);
+            let device_ids = file_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| targeting.get(name))
+                .map(|ids| ids.as_slice())
+                .unwrap_or(&[]);
+            AmazonApkUpload {
+                file_path,
+                device_ids,
+            }
+        })
+        .collect();
 
     // Грузим
-    let client = AmazonClient::new(http_client, token);
+    let client = AmazonClient::new(http_client, token_provider);
     let task = AmazonUploadTask {
         application_id: &env_params.app_id,
-        file_path,
+        apks: &apks,
+        replace_existing: app_params.replace_existing,
+        commit: app_params.commit,
+        discard_existing_edit: app_params.discard_existing_edit,
+        listings: &listings,
     };
-    client.upload(task).await.tap_err(|err| {
+    let result = client.upload(task).await.tap_err(|err| {
         error!("Amazon uploading error: {}", err);
     })?;
+    debug!("Amazon uploading result: {:?}", result);
 
-    // Имя файла
-    let file_name = file_path
-        .file_name()
-        .ok_or("Amazon: invalid file name")?
-        .to_str()
-        .ok_or("Amazon: Invalid file name")?;
+    // Список файлов
+    let files_list = result
+        .apks
+        .iter()
+        .map(|apk| {
+            if apk.replaced {
+                format!("- {} (replaced)", apk.file_name)
+            } else {
+                format!("- {}", apk.file_name)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
 
     // Финальное сообщение
-    let message = format!("Amazon uploading finished:\n- {}", file_name);
+    let message = match result.submission_status {
+        Some(status) => format!(
+            "Amazon uploading finished:\n{}\n\nEdit committed, submission status: {}",
+            files_list, status
+        ),
+        None => format!("Amazon uploading finished:\n{}", files_list),
+    };
 
     Ok(UploadResultData {
         target: "Amazon",
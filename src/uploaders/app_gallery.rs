@@ -0,0 +1,101 @@
+use crate::{
+    app_parameters::AppGalleryParams,
+    env_parameters::AppGalleryEnvironment,
+    uploaders::{UploadResult, UploadResultData},
+};
+use app_gallery_client::{
+    AppGalleryClient, AppGalleryPhasedRelease, AppGalleryTokenProvider, AppGalleryUploadTask,
+};
+use log::{debug, error};
+use std::{error::Error, path::Path, time::Duration};
+use tap::TapFallible;
+
+/// Поэтапный выпуск задается процентом пользователей, остальные параметры опциональны
+fn get_phased_release(
+    app_params: &AppGalleryParams,
+) -> Result<Option<AppGalleryPhasedRelease>, Box<dyn Error + Send + Sync>> {
+    let percent = match app_params.phased_release_percent.as_deref() {
+        Some(percent) => percent.parse::<f32>()?,
+        None => {
+            if app_params.phased_release_days.is_some()
+                || app_params.phased_release_description.is_some()
+            {
+                return Err("App gallery: phased release percent is missing".into());
+            }
+            return Ok(None);
+        }
+    };
+
+    let days = match app_params.phased_release_days.as_deref() {
+        Some(days) => days.parse::<i64>()?,
+        None => 7,
+    };
+
+    let start_time = chrono::Utc::now();
+    Ok(Some(AppGalleryPhasedRelease {
+        start_time,
+        end_time: start_time + chrono::Duration::days(days),
+        percent,
+        description: app_params
+            .phased_release_description
+            .clone()
+            .unwrap_or_else(|| "Phased release".to_owned()),
+    }))
+}
+
+pub async fn upload_in_app_gallery(
+    http_client: reqwest::Client,
+    env_params: AppGalleryEnvironment,
+    app_params: AppGalleryParams,
+) -> UploadResult {
+    // Заранее проверяем параметры, чтобы не выгружать файл впустую
+    let phased_release = get_phased_release(&app_params)?;
+    let compile_timeout = match app_params.compile_timeout_minutes.as_deref() {
+        Some(minutes) => Duration::from_secs(minutes.parse::<u64>()? * 60),
+        None => Duration::from_secs(30 * 60),
+    };
+
+    // Токен обновляется провайдером сам, но первый запрашиваем сразу, чтобы быстрее увидеть ошибку
+    let token_provider = AppGalleryTokenProvider::new(
+        http_client.clone(),
+        env_params.client_id,
+        env_params.client_secret,
+    );
+    token_provider.get_access_token().await.tap_err(|err| {
+        error!("Access token request failed: {}", err);
+    })?;
+    debug!("App gallery token received");
+
+    // Грузим
+    let client = AppGalleryClient::new(http_client, token_provider);
+    let task = AppGalleryUploadTask {
+        application_id: &env_params.app_id,
+        file_path: Path::new(&app_params.file_path),
+        submit: app_params.submit,
+        phased_release: phased_release.as_ref(),
+        compile_timeout,
+    };
+    let result = client.upload(task).await.tap_err(|err| {
+        error!("App gallery uploading error: {}", err);
+    })?;
+    debug!("App gallery uploading result: {:?}", result);
+
+    // Финальное сообщение
+    let message = format!("Huawei AppGallery uploading finished: {}", result.file_name);
+    let message = match (result.submitted, phased_release) {
+        (true, Some(phased)) => format!(
+            "{}\n\nSubmitted for review with phased release to {:.2}% of users until {}",
+            message,
+            phased.percent,
+            phased.end_time.format("%Y-%m-%d")
+        ),
+        (true, None) => format!("{}\n\nSubmitted for review", message),
+        (false, _) => message,
+    };
+
+    Ok(UploadResultData {
+        target: "Huawei AppGallery",
+        message: Some(message),
+        install_url: None,
+    })
+}
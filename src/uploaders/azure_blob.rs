@@ -0,0 +1,99 @@
+use super::upload_result::{UploadResult, UploadResultData};
+use crate::app_parameters::AzureBlobParams;
+use log::{error, info};
+use microsoft_azure_client::{BlobProperties, BlobStorageClient, BlobUploadOptions};
+use std::{error::Error, path::Path};
+use tap::TapFallible;
+
+fn get_file_name(path: &Path) -> Result<&str, &str> {
+    let file_name = path
+        .file_name()
+        .ok_or("Azure blob: invalid file name")?
+        .to_str()
+        .ok_or("Azure blob: Invalid file name")?;
+    Ok(file_name)
+}
+
+/// Метаданные из параметров вида `<имя>=<значение>`
+fn parse_metadata(
+    values: Option<&Vec<String>>,
+) -> Result<Vec<(String, String)>, Box<dyn Error + Send + Sync>> {
+    values
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            let (name, value) = value.split_once('=').ok_or_else(|| {
+                format!(
+                    "Azure blob: invalid metadata '{}', expected <name>=<value>",
+                    value
+                )
+            })?;
+            Ok((name.trim().to_owned(), value.trim().to_owned()))
+        })
+        .collect()
+}
+
+fn get_upload_options(
+    app_params: &AzureBlobParams,
+) -> Result<BlobUploadOptions, Box<dyn Error + Send + Sync>> {
+    let mut options = BlobUploadOptions::default();
+    if let Some(megabytes) = app_params.block_size.as_deref() {
+        options.block_size = megabytes.parse::<usize>()? * 1024 * 1024;
+    }
+    if let Some(concurrency) = app_params.concurrency.as_deref() {
+        options.concurrency = concurrency.parse::<usize>()?;
+    }
+    Ok(options)
+}
+
+pub async fn upload_in_azure_blob(
+    http_client: reqwest::Client,
+    app_params: AzureBlobParams,
+) -> UploadResult {
+    info!("Start azure blob uploading");
+
+    let properties = BlobProperties {
+        content_type: app_params.content_type.clone(),
+        cache_control: app_params.cache_control.clone(),
+        metadata: parse_metadata(app_params.metadata.as_ref())?,
+    };
+
+    let client = BlobStorageClient::new(http_client, &app_params.sas_url)
+        .tap_err(|err| {
+            error!("Azure blob client create failed with error: {}", err);
+        })?
+        .with_upload_options(get_upload_options(&app_params)?);
+
+    let prefix = app_params
+        .path_prefix
+        .as_deref()
+        .map(|prefix| prefix.trim_matches('/'))
+        .filter(|prefix| !prefix.is_empty());
+
+    let mut urls = Vec::with_capacity(app_params.files.len());
+    for file in app_params.files.iter() {
+        let path = Path::new(file);
+        let file_name = get_file_name(path)?;
+        let blob_name = match prefix {
+            Some(prefix) => format!("{}/{}", prefix, file_name),
+            None => file_name.to_owned(),
+        };
+
+        let url = client
+            .upload_file(path, &blob_name, &properties)
+            .await
+            .tap_err(|err| {
+                error!("Azure blob uploading failed for {}: {}", file, err);
+            })?;
+        info!("Azure blob uploading finished: {}", url);
+        urls.push(url.to_string());
+    }
+
+    let message = format!("Azure blob uploading finished:\n- {}", urls.join("\n- "));
+
+    Ok(UploadResultData {
+        target: "Azure blob",
+        message: Some(message),
+        install_url: urls.into_iter().next(),
+    })
+}
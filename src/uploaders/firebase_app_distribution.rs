@@ -0,0 +1,117 @@
+use super::{
+    google_auth::{GoogleAuth, FIREBASE_APP_DISTRIBUTION_SCOPES},
+    upload_result::{UploadResult, UploadResultData},
+};
+use crate::{
+    app_parameters::FirebaseAppDistributionParams,
+    env_parameters::{FirebaseAppDistributionEnvironment, GitEnvironment},
+};
+use firebase_app_distribution_client::{FirebaseAppDistributionClient, FirebaseUploadTask};
+use log::{debug, error, info};
+use std::{error::Error, path::Path, time::Duration};
+use tap::TapFallible;
+
+/// Описание релиза из файлика и git информации, если она нужна
+async fn build_release_notes(
+    app_params: &FirebaseAppDistributionParams,
+    git_info: Option<&GitEnvironment>,
+) -> Result<Option<String>, Box<dyn Error + Send + Sync>> {
+    let file_text = match app_params.release_notes_file.as_deref() {
+        Some(path) => {
+            let text = tokio::fs::read_to_string(path).await.tap_err(|err| {
+                error!("Release notes file read failed: {}, error: {}", path, err);
+            })?;
+            Some(text.trim().to_owned())
+        }
+        None => None,
+    };
+
+    let git_text = match (app_params.release_notes_from_git, git_info) {
+        (true, Some(git_info)) => Some(format!(
+            "Branch: {}\nCommit: {}",
+            git_info.git_branch, git_info.git_commit
+        )),
+        (true, None) => {
+            return Err("Firebase: git info for release notes is missing".into());
+        }
+        (false, _) => None,
+    };
+
+    let notes = match (file_text, git_text) {
+        (Some(file_text), Some(git_text)) => Some(format!("{}\n\n{}", file_text, git_text)),
+        (file_text, git_text) => file_text.or(git_text),
+    };
+    Ok(notes)
+}
+
+pub async fn upload_in_firebase_app_distribution(
+    http_client: reqwest::Client,
+    env_params: FirebaseAppDistributionEnvironment,
+    app_params: FirebaseAppDistributionParams,
+    git_info: Option<GitEnvironment>,
+) -> UploadResult {
+    info!("Start firebase app distribution uploading");
+
+    let release_notes = build_release_notes(&app_params, git_info.as_ref()).await?;
+
+    let processing_timeout = match app_params.processing_timeout_minutes.as_deref() {
+        Some(minutes) => Duration::from_secs(minutes.parse::<u64>()? * 60),
+        None => Duration::from_secs(10 * 60),
+    };
+
+    // Аутентификация через сервисный аккаунт, как и для остальных выгрузок в Google
+    let auth = GoogleAuth::new(
+        env_params.auth_file.as_deref(),
+        env_params.auth_json.as_deref(),
+        None,
+        FIREBASE_APP_DISTRIBUTION_SCOPES,
+    )
+    .await
+    .tap_err(|err| {
+        error!("Firebase auth failed: {}", err);
+    })?;
+
+    let token = auth.token().await.tap_err(|err| {
+        error!("Token receive failed: {}", err);
+    })?;
+    info!("Firebase token received");
+
+    let client = FirebaseAppDistributionClient::new(http_client, token, app_params.app_id)
+        .tap_err(|err| {
+            error!("Firebase client create failed: {}", err);
+        })?;
+
+    let task = FirebaseUploadTask {
+        file_path: Path::new(&app_params.file_path),
+        release_notes: release_notes.as_deref(),
+        tester_emails: app_params.testers.as_deref().unwrap_or_default(),
+        group_aliases: app_params.groups.as_deref().unwrap_or_default(),
+        processing_timeout,
+    };
+    let result = client.upload(task).await.tap_err(|err| {
+        error!("Firebase app distribution uploading failed: {}", err);
+    })?;
+    debug!("Firebase app distribution uploading result: {:?}", result);
+
+    let release = result.release;
+    let version = match (&release.display_version, &release.build_version) {
+        (Some(display), Some(build)) => format!("{} ({})", display, build),
+        (Some(version), None) | (None, Some(version)) => version.clone(),
+        (None, None) => release.name.clone(),
+    };
+    let message = format!("Firebase release {}: {}", result.action, version);
+    let message = match release.firebase_console_uri.as_deref() {
+        Some(url) => format!("{}\n- Console: {}", message, url),
+        None => message,
+    };
+    let message = match release.testing_uri.as_deref() {
+        Some(url) => format!("{}\n- Testers: {}", message, url),
+        None => message,
+    };
+
+    Ok(UploadResultData {
+        target: "Firebase app distribution",
+        message: Some(message),
+        install_url: release.testing_uri.or(release.firebase_console_uri),
+    })
+}
@@ -0,0 +1,146 @@
+use log::{debug, info};
+use std::{
+    error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+use yup_oauth2::{
+    authenticator::{Authenticator, DefaultHyperClient, HyperClientBuilder},
+    parse_service_account_key, read_service_account_key, AccessToken, ServiceAccountAuthenticator,
+    ServiceAccountKey,
+};
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub const GOOGLE_DRIVE_SCOPES: &[&str] = &["https://www.googleapis.com/auth/drive"];
+pub const GOOGLE_PLAY_SCOPES: &[&str] = &["https://www.googleapis.com/auth/androidpublisher"];
+pub const FIREBASE_APP_DISTRIBUTION_SCOPES: &[&str] =
+    &["https://www.googleapis.com/auth/cloud-platform"];
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[derive(Debug)]
+pub enum GoogleAuthError {
+    KeyIsMissing,
+    KeyReadFailed(io::Error),
+    KeyDecodeFailed(base64::DecodeError),
+    AuthenticatorBuildFailed(io::Error),
+    TokenReceiveFailed(yup_oauth2::Error),
+}
+impl Display for GoogleAuthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GoogleAuthError::KeyIsMissing => {
+                write!(f, "Google service account key is not specified")
+            }
+            GoogleAuthError::KeyReadFailed(err) => {
+                write!(f, "Google service account key read failed: {}", err)
+            }
+            GoogleAuthError::KeyDecodeFailed(err) => {
+                write!(
+                    f,
+                    "Google service account key base64 decode failed: {}",
+                    err
+                )
+            }
+            GoogleAuthError::AuthenticatorBuildFailed(err) => {
+                write!(f, "Google authenticator create failed: {}", err)
+            }
+            GoogleAuthError::TokenReceiveFailed(err) => {
+                write!(f, "Google token receive failed: {}", err)
+            }
+        }
+    }
+}
+impl error::Error for GoogleAuthError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GoogleAuthError::KeyIsMissing => None,
+            GoogleAuthError::KeyReadFailed(err) => Some(err),
+            GoogleAuthError::KeyDecodeFailed(err) => Some(err),
+            GoogleAuthError::AuthenticatorBuildFailed(err) => Some(err),
+            GoogleAuthError::TokenReceiveFailed(err) => Some(err),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Получаем ключ сервисного аккаунта Google.
+/// Содержимое ключа в переменной окружения имеет приоритет над путем к файлу,
+/// так как CI умеет пробрасывать секреты лишь значениями.
+/// Содержимое может быть как обычным JSON, так и закодированным в base64.
+async fn read_google_service_key(
+    auth_file: Option<&str>,
+    auth_json: Option<&str>,
+) -> Result<ServiceAccountKey, GoogleAuthError> {
+    match (auth_json, auth_file) {
+        (Some(json), _) => {
+            let json = json.trim();
+            if json.starts_with('{') {
+                debug!("Google service key is passed as JSON");
+                parse_service_account_key(json).map_err(GoogleAuthError::KeyReadFailed)
+            } else {
+                debug!("Google service key is passed as base64");
+                let decoded = base64::decode(json).map_err(GoogleAuthError::KeyDecodeFailed)?;
+                parse_service_account_key(decoded).map_err(GoogleAuthError::KeyReadFailed)
+            }
+        }
+        (None, Some(file)) => {
+            debug!("Google service key is read from file: {}", file);
+            read_service_account_key(file)
+                .await
+                .map_err(GoogleAuthError::KeyReadFailed)
+        }
+        (None, None) => Err(GoogleAuthError::KeyIsMissing),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+type GoogleConnector = <DefaultHyperClient as HyperClientBuilder>::Connector;
+
+/// Аутентификация сервисного аккаунта, общая для всех выгрузок в Google
+pub struct GoogleAuth {
+    authenticator: Authenticator<GoogleConnector>,
+    scopes: &'static [&'static str],
+}
+impl GoogleAuth {
+    /// При делегировании на уровне домена `subject` - пользователь, от имени которого действуем
+    pub async fn new(
+        auth_file: Option<&str>,
+        auth_json: Option<&str>,
+        subject: Option<String>,
+        scopes: &'static [&'static str],
+    ) -> Result<GoogleAuth, GoogleAuthError> {
+        let key = read_google_service_key(auth_file, auth_json).await?;
+        info!("Google service key read success");
+
+        let builder = ServiceAccountAuthenticator::builder(key);
+        let builder = match subject {
+            Some(subject) => {
+                info!("Google service account impersonates user: {}", subject);
+                builder.subject(subject)
+            }
+            None => builder,
+        };
+        let authenticator = builder
+            .build()
+            .await
+            .map_err(GoogleAuthError::AuthenticatorBuildFailed)?;
+        info!("Google auth success");
+
+        Ok(GoogleAuth {
+            authenticator,
+            scopes,
+        })
+    }
+
+    /// Токен кешируется внутри аутентификатора, новый запрашивается лишь после истечения старого
+    pub async fn token(&self) -> Result<AccessToken, GoogleAuthError> {
+        self.authenticator
+            .token(self.scopes)
+            .await
+            .map_err(GoogleAuthError::TokenReceiveFailed)
+    }
+}
@@ -1,10 +1,253 @@
-use super::upload_result::{UploadResult, UploadResultData};
+use super::{
+    google_auth::{GoogleAuth, GOOGLE_DRIVE_SCOPES},
+    upload_result::{UploadResult, UploadResultData},
+};
 use crate::{app_parameters::GoogleDriveParams, env_parameters::GoogleDriveEnvironment};
-use google_drive_client::{GoogleDriveClient, GoogleDriveUploadTask};
-use log::{debug, error, info};
-use std::{path::PathBuf, time::Duration};
+use futures::{
+    stream::{self, StreamExt},
+    TryStreamExt,
+};
+use google_drive_client::{
+    GoogleDriveClient, GoogleDriveConflictPolicy, GoogleDriveError, GoogleDriveFolder,
+    GoogleDriveGrantee, GoogleDrivePermission, GoogleDriveRetentionPolicy,
+    GoogleDriveRetentionResult, GoogleDriveRole, GoogleDriveUploadAction, GoogleDriveUploadResult,
+    GoogleDriveUploadTask,
+};
+use log::{debug, error, info, warn};
+use std::{collections::VecDeque, error::Error, path::PathBuf, time::Duration};
 use tap::TapFallible;
-use yup_oauth2::{read_service_account_key, ServiceAccountAuthenticator};
+
+/// Куда применяются разрешения
+enum ShareTarget {
+    Files,
+    Folder,
+}
+
+/// Собираем список разрешений из параметров приложения
+fn build_permissions(
+    app_params: &GoogleDriveParams,
+) -> Result<Vec<GoogleDrivePermission>, Box<dyn Error + Send + Sync>> {
+    let role = match app_params.share_role.as_deref() {
+        Some(role) => role.parse::<GoogleDriveRole>()?,
+        None => GoogleDriveRole::Writer,
+    };
+
+    let expiration_time = match app_params.share_expiration_days.as_deref() {
+        Some(days) => {
+            let days = days.parse::<i64>()?;
+            let time = chrono::Utc::now() + chrono::Duration::days(days);
+            Some(time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        }
+        None => None,
+    };
+
+    // Старые параметры владельца остаются рабочими
+    let legacy_grantees = app_params
+        .target_owner_email
+        .iter()
+        .map(|email| GoogleDriveGrantee::User(email.clone()))
+        .chain(
+            app_params
+                .target_domain
+                .iter()
+                .map(|domain| GoogleDriveGrantee::Domain(domain.clone())),
+        );
+
+    let mut grantees: Vec<GoogleDriveGrantee> = legacy_grantees.collect();
+    for grantee in app_params.share.iter().flatten() {
+        grantees.push(grantee.parse()?);
+    }
+
+    let permissions = grantees
+        .into_iter()
+        .map(|grantee| {
+            // Время окончания доступа поддерживается лишь для пользователей и групп
+            let expiration_time = match grantee {
+                GoogleDriveGrantee::User(_) | GoogleDriveGrantee::Group(_) => {
+                    expiration_time.clone()
+                }
+                _ => {
+                    if expiration_time.is_some() {
+                        warn!("Expiration time is ignored for grantee: {:?}", grantee);
+                    }
+                    None
+                }
+            };
+            GoogleDrivePermission {
+                grantee,
+                role,
+                expiration_time,
+            }
+        })
+        .collect();
+
+    Ok(permissions)
+}
+
+/// Политика очистки старых сборок, если она вообще задана
+fn build_retention_policy(
+    app_params: &GoogleDriveParams,
+) -> Result<Option<GoogleDriveRetentionPolicy>, Box<dyn Error + Send + Sync>> {
+    if app_params.retention_max_age_days.is_none() && app_params.retention_keep_newest.is_none() {
+        return Ok(None);
+    }
+
+    let delete_permanently = match app_params.retention_mode.as_deref() {
+        Some("trash") | None => false,
+        Some("delete") => true,
+        Some(other) => {
+            return Err(format!("Invalid google drive retention mode: {}", other).into());
+        }
+    };
+
+    let dry_run = match app_params.retention_dry_run.as_deref() {
+        Some("false") | None => false,
+        Some("true") => true,
+        Some(other) => {
+            return Err(format!(
+                "Invalid google drive retention dry run value, expected true or false: {}",
+                other
+            )
+            .into());
+        }
+    };
+
+    Ok(Some(GoogleDriveRetentionPolicy {
+        max_age_days: app_params
+            .retention_max_age_days
+            .as_deref()
+            .map(str::parse::<i64>)
+            .transpose()?,
+        keep_newest: app_params
+            .retention_keep_newest
+            .as_deref()
+            .map(str::parse::<usize>)
+            .transpose()?,
+        name_patterns: app_params
+            .retention_name_patterns
+            .clone()
+            .unwrap_or_default(),
+        delete_permanently,
+        dry_run,
+    }))
+}
+
+/// Файл для выгрузки вместе с индексом папки назначения
+struct UploadJob {
+    file_path: PathBuf,
+    /// Папка из зеркала директорий, если нет - целевая папка
+    folder_index: Option<usize>,
+    /// Папка верхнего уровня выгружаемой директории
+    top_dir_index: Option<usize>,
+}
+
+/// Создает в Google Drive структуру папок как у локальной директории и собирает список файлов,
+/// возвращает индекс папки верхнего уровня
+async fn mirror_directory(
+    target_folder: &GoogleDriveFolder,
+    dir_path: PathBuf,
+    folders: &mut Vec<GoogleDriveFolder>,
+    jobs: &mut Vec<UploadJob>,
+) -> Result<usize, Box<dyn Error + Send + Sync>> {
+    let top_index = folders.len();
+
+    // Для путей вроде `.` или `..` имя директории есть лишь у полного пути
+    let dir_path = tokio::fs::canonicalize(&dir_path).await?;
+
+    // Обходим в ширину, чтобы не городить рекурсию в async
+    let mut queue = VecDeque::from([(dir_path, None)]);
+    while let Some((dir_path, parent_index)) = queue.pop_front() {
+        let dir_name = dir_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or("Google drive: invalid directory name")?;
+
+        let parent_folder: &GoogleDriveFolder = match parent_index {
+            Some(index) => &folders[index],
+            None => target_folder,
+        };
+        let new_folder = parent_folder.create_subfolder_if_needed(dir_name).await?;
+        folders.push(new_folder);
+        let folder_index = folders.len() - 1;
+
+        let mut entries = tokio::fs::read_dir(&dir_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let file_type = tokio::fs::symlink_metadata(&path).await?.file_type();
+            if file_type.is_dir() {
+                queue.push_back((path, Some(folder_index)));
+            } else if file_type.is_symlink() && tokio::fs::metadata(&path).await?.is_dir() {
+                // Ссылки на директории пропускаем, иначе можно зациклиться
+                warn!(
+                    "Google drive: directory symlink skipped: {}",
+                    path.display()
+                );
+            } else {
+                jobs.push(UploadJob {
+                    file_path: path,
+                    folder_index: Some(folder_index),
+                    top_dir_index: Some(top_index),
+                });
+            }
+        }
+    }
+
+    Ok(top_index)
+}
+
+/// Делаем 3 попытки повторной выгрузки файлика с паузой в 20 секунд
+async fn upload_with_retries(
+    client: &GoogleDriveClient,
+    task: &GoogleDriveUploadTask<'_>,
+) -> Result<GoogleDriveUploadResult, GoogleDriveError> {
+    let mut current_retry_count = 0;
+    loop {
+        match client.upload(task).await {
+            Ok(result) => {
+                return Ok(result);
+            }
+            Err(err) => {
+                error!("Upload failed: {}", err);
+                if current_retry_count < 3 {
+                    current_retry_count += 1;
+                    tokio::time::sleep(Duration::from_secs(20)).await;
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+    }
+}
+
+/// Размер в человекочитаемом виде
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Текст о результатах очистки старых сборок
+fn format_retention_result(result: &GoogleDriveRetentionResult) -> String {
+    let header = if result.dry_run {
+        format!(
+            "Old builds cleanup (dry run), would reclaim {}:",
+            format_size(result.reclaimed_bytes)
+        )
+    } else {
+        format!(
+            "Old builds cleanup, reclaimed {}:",
+            format_size(result.reclaimed_bytes)
+        )
+    };
+    result.removed.iter().fold(header, |prev, item| {
+        format!("{}\n- {} ({})", prev, item.name, format_size(item.size))
+    })
+}
 
 pub async fn upload_in_google_drive(
     client: reqwest::Client,
@@ -13,99 +256,241 @@ pub async fn upload_in_google_drive(
 ) -> UploadResult {
     info!("Start google drive uploading");
 
-    // Содержимое Json файлика ключа
-    let key = read_service_account_key(env_params.auth_file)
-        .await
+    // Политика для уже существующих файлов
+    let on_conflict = app_params
+        .on_conflict
+        .as_deref()
+        .map(str::parse::<GoogleDriveConflictPolicy>)
+        .transpose()
         .tap_err(|err| {
-            error!("Credentials read failed: {}", err);
+            error!("Invalid conflict policy: {}", err);
         })?;
-    info!("Google drive key read success");
 
-    // Аутентификация на основе прочитанного файлика
-    let auth = ServiceAccountAuthenticator::builder(key)
-        .build()
-        .await
-        .tap_err(|err| {
-            error!("Service account build failed: {}", err);
-        })?;
-    info!("Google drive auth success");
+    // Разрешения на доступ
+    let permissions = build_permissions(&app_params).tap_err(|err| {
+        error!("Invalid sharing parameters: {}", err);
+    })?;
+    let send_notification_email = match app_params.share_notify.as_deref() {
+        Some("true") | None => true,
+        Some("false") => false,
+        Some(other) => {
+            return Err(format!(
+                "Invalid google drive share notify value, expected true or false: {}",
+                other
+            )
+            .into());
+        }
+    };
+    let share_target = match app_params.share_target.as_deref() {
+        Some("files") | None => ShareTarget::Files,
+        Some("folder") => ShareTarget::Folder,
+        Some(other) => {
+            return Err(format!("Invalid google drive share target: {}", other).into());
+        }
+    };
 
-    // Add scopes to the secret and get the token.
-    let token = auth
-        .token(&["https://www.googleapis.com/auth/drive"])
-        .await
-        .tap_err(|err| {
-            error!("Token receive failed: {}", err);
-        })?;
+    // Количество одновременных выгрузок
+    let concurrency = match app_params.upload_concurrency.as_deref() {
+        Some(count) => count.parse::<usize>()?.max(1),
+        None => 4,
+    };
+
+    // Очистка старых сборок
+    let retention_policy = build_retention_policy(&app_params).tap_err(|err| {
+        error!("Invalid retention parameters: {}", err);
+    })?;
+
+    // Аутентификация, при делегировании на уровне домена действуем от имени указанного пользователя
+    let auth = GoogleAuth::new(
+        env_params.auth_file.as_deref(),
+        env_params.auth_json.as_deref(),
+        env_params.subject,
+        GOOGLE_DRIVE_SCOPES,
+    )
+    .await
+    .tap_err(|err| {
+        error!("Google drive auth failed: {}", err);
+    })?;
+
+    let token = auth.token().await.tap_err(|err| {
+        error!("Token receive failed: {}", err);
+    })?;
     info!("Google drive token received");
 
     // Клиент
     let client = GoogleDriveClient::new(client, token);
 
-    // Целевая папка
-    let folder = {
-        let folder = client
+    // Целевая папка, а так же папка, в которой лежат все сборки, если она отличается
+    let (builds_folder, folder) = {
+        let root_folder = client
             .get_folder_for_id(&app_params.target_folder_id)
             .await?
             .ok_or("Target google drive folder is not found")
             .tap_err(|err| {
                 error!("Folder find failed: {}", err);
             })?;
-        if let Some(sub_folder_name) = app_params.target_subfolder_name {
-            folder
-                .create_subfolder_if_needed(&sub_folder_name)
-                .await
-                .tap_err(|err| {
-                    error!("Subfolder create failed: {}", err);
-                })?
-        } else {
-            folder
+
+        let sub_folder_names: Vec<&str> = app_params
+            .target_subfolder_name
+            .as_deref()
+            .unwrap_or_default()
+            .split('/')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        match sub_folder_names.split_last() {
+            Some((last_name, parent_names)) => {
+                let parent_folder = if parent_names.is_empty() {
+                    root_folder
+                } else {
+                    root_folder
+                        .create_subfolders_path_if_needed(&parent_names.join("/"))
+                        .await
+                        .tap_err(|err| {
+                            error!("Subfolder create failed: {}", err);
+                        })?
+                };
+                let folder = parent_folder
+                    .create_subfolder_if_needed(last_name)
+                    .await
+                    .tap_err(|err| {
+                        error!("Subfolder create failed: {}", err);
+                    })?;
+                (Some(parent_folder), folder)
+            }
+            None => (None, root_folder),
         }
     };
     debug!("Target folder received: {}", folder.get_info().id);
 
-    // Грузим файлы
-    let mut results = Vec::with_capacity(app_params.files.len());
-    for file_path_str in app_params.files {
-        let task = GoogleDriveUploadTask {
-            file_path: PathBuf::from(file_path_str),
-            owner_domain: app_params.target_owner_email.as_deref(),
-            owner_email: app_params.target_owner_email.as_deref(),
-            parent_folder: &folder,
-        };
-
-        // Делаем 3 попытки повторной выгрузки файлика с паузой в 20 секунд
-        let mut current_retry_count = 0;
-        let result = loop {
-            match client.upload(&task).await {
-                Ok(result) => {
-                    break result;
-                }
-                Err(err) => {
-                    error!("Upload failed: {}", err);
-                    if current_retry_count < 3 {
-                        current_retry_count += 1;
-                        tokio::time::sleep(Duration::from_secs(20)).await;
-                    } else {
-                        return UploadResult::Err(Box::new(err));
-                    }
-                }
+    // Доступ выдается сразу на папку, файлы его наследуют
+    let file_permissions: &[GoogleDrivePermission] = match share_target {
+        ShareTarget::Files => &permissions,
+        ShareTarget::Folder => {
+            for permission in permissions.iter() {
+                folder
+                    .add_permission(permission, send_notification_email)
+                    .await
+                    .tap_err(|err| {
+                        error!("Folder sharing failed: {}", err);
+                    })?;
             }
-        };
+            &[]
+        }
+    };
 
-        debug!("Google drive uploading result: {:?}", result);
-        results.push(result);
+    // Папки повторяют структуру локальных директорий
+    let mut jobs = Vec::new();
+    let mut folders = Vec::new();
+    let mut top_dirs = Vec::new();
+    for path_str in app_params.files {
+        let path = PathBuf::from(path_str);
+        if path.is_dir() {
+            let top_index = mirror_directory(&folder, path, &mut folders, &mut jobs)
+                .await
+                .tap_err(|err| {
+                    error!("Directory mirroring failed: {}", err);
+                })?;
+            top_dirs.push(top_index);
+        } else {
+            jobs.push(UploadJob {
+                file_path: path,
+                folder_index: None,
+                top_dir_index: None,
+            });
+        }
     }
 
+    // Грузим файлы параллельно с ограничением количества одновременных выгрузок
+    let results: Vec<(Option<usize>, GoogleDriveUploadResult)> = stream::iter(jobs)
+        .map(|job| {
+            let top_dir_index = job.top_dir_index;
+            let task = GoogleDriveUploadTask {
+                file_path: job.file_path,
+                parent_folder: match job.folder_index {
+                    Some(index) => &folders[index],
+                    None => &folder,
+                },
+                permissions: file_permissions,
+                send_notification_email,
+                on_conflict,
+            };
+            let client = &client;
+            async move {
+                let result = upload_with_retries(client, &task).await?;
+                debug!("Google drive uploading result: {:?}", result);
+                Ok::<_, GoogleDriveError>((top_dir_index, result))
+            }
+        })
+        .buffered(concurrency)
+        .try_collect()
+        .await?;
+
+    // Чистим старые сборки рядом с текущей
+    let retention_result = match retention_policy {
+        Some(policy) => {
+            let (target, keep_ids): (_, Vec<&str>) = match builds_folder.as_ref() {
+                Some(builds_folder) => (builds_folder, vec![folder.get_info().id.as_str()]),
+                None => (
+                    &folder,
+                    results
+                        .iter()
+                        .filter(|(top_dir, _)| top_dir.is_none())
+                        .map(|(_, res)| res.file_id.as_str())
+                        .chain(
+                            top_dirs
+                                .iter()
+                                .map(|index| folders[*index].get_info().id.as_str()),
+                        )
+                        .collect(),
+                ),
+            };
+            let result = target
+                .remove_old_items(&policy, &keep_ids)
+                .await
+                .tap_err(|err| {
+                    error!("Old builds cleanup failed: {}", err);
+                })?;
+            debug!("Google drive retention result: {:?}", result);
+            Some(result)
+        }
+        None => None,
+    };
+
     // Финальное сообщение
     let message_begin = format!(
         "Google drive folder:\n- {}\n  => {}\n\nFiles:",
         folder.get_info().name,
         folder.get_info().web_view_link
     );
-    let message = results.into_iter().fold(message_begin, |prev, res| {
-        format!("{}\n- {}\n  => {}", prev, res.file_name, res.web_view_link)
+    let message = results
+        .iter()
+        .filter(|(top_dir, _)| top_dir.is_none())
+        .fold(message_begin, |prev, (_, res)| match &res.action {
+            GoogleDriveUploadAction::Uploaded => {
+                format!("{}\n- {}\n  => {}", prev, res.file_name, res.web_view_link)
+            }
+            action => format!(
+                "{}\n- {} ({})\n  => {}",
+                prev, res.file_name, action, res.web_view_link
+            ),
+        });
+    let message = top_dirs.iter().fold(message, |prev, index| {
+        let files_count = results
+            .iter()
+            .filter(|(top_dir, _)| top_dir == &Some(*index))
+            .count();
+        let info = folders[*index].get_info();
+        format!(
+            "{}\n- {}/ ({} files)\n  => {}",
+            prev, info.name, files_count, info.web_view_link
+        )
     });
+    let message = match retention_result {
+        Some(result) => format!("{}\n\n{}", message, format_retention_result(&result)),
+        None => message,
+    };
 
     Ok(UploadResultData {
         target: "Google drive",
@@ -1,10 +1,12 @@
-use super::upload_result::{UploadResult, UploadResultData};
+use super::{
+    google_auth::{GoogleAuth, GOOGLE_PLAY_SCOPES},
+    upload_result::{UploadResult, UploadResultData},
+};
 use crate::{app_parameters::GooglePlayParams, env_parameters::GooglePlayEnvironment};
 use google_play_client::{GooglePlayClient, GooglePlayUploadTask};
 use log::{debug, error, info};
 use std::path::Path;
 use tap::TapFallible;
-use yup_oauth2::{read_service_account_key, ServiceAccountAuthenticator};
 
 pub async fn upload_in_google_play(
     client: reqwest::Client,
@@ -13,24 +15,21 @@ pub async fn upload_in_google_play(
 ) -> UploadResult {
     info!("Start google play uploading");
 
-    // Содержимое Json файлика ключа
-    let key = read_service_account_key(env_params.auth_file)
-        .await
-        .expect("Google play auth file parsing failed");
-
-    // Аутентификация на основе прочитанного файлика
-    let auth = ServiceAccountAuthenticator::builder(key)
-        .build()
-        .await
-        .expect("Failed to create google play authenticator");
+    // Аутентификация, ошибки отдаем наверх, чтобы не ронять остальные выгрузки
+    let auth = GoogleAuth::new(
+        env_params.auth_file.as_deref(),
+        env_params.auth_json.as_deref(),
+        None,
+        GOOGLE_PLAY_SCOPES,
+    )
+    .await
+    .tap_err(|err| {
+        error!("Google play auth failed: {}", err);
+    })?;
 
-    // Add the scopes to the secret and get the token.
-    let token = auth
-        .token(&["https://www.googleapis.com/auth/androidpublisher"])
-        .await
-        .tap_err(|err| {
-            error!("Token receive failed: {}", err);
-        })?;
+    let token = auth.token().await.tap_err(|err| {
+        error!("Token receive failed: {}", err);
+    })?;
     debug!("Token received: {:?}", token);
 
     // Клиент
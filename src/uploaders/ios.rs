@@ -1,9 +1,14 @@
+use super::altool_output::parse_altool_output;
 use crate::{
     app_parameters::IOSParams,
     env_parameters::IOSEnvironment,
     uploaders::{UploadResult, UploadResultData},
 };
-use log::{debug, error};
+use app_store_connect_client::{
+    AppStoreConnectClient, AppStoreConnectKey, AppStoreConnectTestFlightTask,
+    AppStoreConnectTokenProvider, AppStoreConnectUploadTask, TestFlightLocalization,
+};
+use log::{debug, error, info, warn};
 use std::{
     error::{self},
     fmt::{self, Display, Formatter},
@@ -33,6 +38,12 @@ fi
 #[derive(Debug)]
 enum IOSError {
     FileDoesNotExist(String),
+    CredentialsAreMissing,
+    TestFlightRequiresApiKey,
+    InvalidWhatToTest(String),
+    ApiKeyReadFailed(io::Error),
+    ApiKeyDecodeFailed(base64::DecodeError),
+    ApiKeyIsNotUtf8(FromUtf8Error),
     SpawnFailed(io::Error),
     InvalidSpawn(String),
     ErrorParseFailed(FromUtf8Error),
@@ -47,7 +58,11 @@ impl error::Error for IOSError {}
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
-pub async fn upload_in_ios(env_params: IOSEnvironment, app_params: IOSParams) -> UploadResult {
+pub async fn upload_in_ios(
+    http_client: reqwest::Client,
+    env_params: IOSEnvironment,
+    app_params: IOSParams,
+) -> UploadResult {
     // Проверка наличия файлика
     let path = Path::new(&app_params.ipa_file_path);
     if !path.exists() {
@@ -56,6 +71,169 @@ pub async fn upload_in_ios(env_params: IOSEnvironment, app_params: IOSParams) ->
         )));
     }
 
+    // Ключ App Store Connect API приоритетнее, так как не требует Xcode
+    let has_api_key = env_params.api_key.is_some() || env_params.api_key_file.is_some();
+    if let (Some(issuer_id), Some(key_id), true) = (
+        &env_params.api_issuer_id,
+        &env_params.api_key_id,
+        has_api_key,
+    ) {
+        return upload_with_api(
+            http_client,
+            issuer_id.clone(),
+            key_id.clone(),
+            &env_params,
+            &app_params,
+        )
+        .await;
+    }
+
+    // Раздача в TestFlight работает только через App Store Connect API
+    if is_testflight_requested(&app_params) {
+        return Err(Box::new(IOSError::TestFlightRequiresApiKey));
+    }
+
+    match (&env_params.user, &env_params.pass) {
+        (Some(user), Some(pass)) => upload_with_altool(user, pass, &app_params).await,
+        _ => Err(Box::new(IOSError::CredentialsAreMissing)),
+    }
+}
+
+fn is_testflight_requested(app_params: &IOSParams) -> bool {
+    app_params.testflight_what_to_test.is_some()
+        || app_params.testflight_groups.is_some()
+        || app_params.testflight_testers.is_some()
+        || app_params.testflight_submit_review
+}
+
+/// Читаем тексты "What to Test" из параметров вида `<локаль>:<путь к файлу>`
+async fn read_what_to_test(
+    app_params: &IOSParams,
+) -> Result<Vec<TestFlightLocalization>, Box<dyn error::Error + Send + Sync>> {
+    let mut localizations = Vec::new();
+    for value in app_params.testflight_what_to_test.iter().flatten() {
+        let (locale, file_path) = value
+            .split_once(':')
+            .ok_or_else(|| IOSError::InvalidWhatToTest(value.clone()))?;
+
+        let text = tokio::fs::read_to_string(file_path).await.tap_err(|err| {
+            error!("What to test file read failed: {}, err: {}", file_path, err);
+        })?;
+
+        localizations.push(TestFlightLocalization {
+            locale: locale.trim().to_owned(),
+            what_to_test: text.trim().to_owned(),
+        });
+    }
+    Ok(localizations)
+}
+
+/// Содержимое .p8 ключа: значение из переменной окружения приоритетнее файла,
+/// в переменной может быть как сам PEM, так и он же в base64
+async fn read_api_key(env_params: &IOSEnvironment) -> Result<String, IOSError> {
+    match (&env_params.api_key, &env_params.api_key_file) {
+        (Some(key), _) if key.trim_start().starts_with("-----BEGIN") => Ok(key.clone()),
+        (Some(key), _) => {
+            let data = base64::decode(key.trim()).map_err(IOSError::ApiKeyDecodeFailed)?;
+            String::from_utf8(data).map_err(IOSError::ApiKeyIsNotUtf8)
+        }
+        (None, Some(file)) => tokio::fs::read_to_string(file)
+            .await
+            .map_err(IOSError::ApiKeyReadFailed),
+        (None, None) => Err(IOSError::CredentialsAreMissing),
+    }
+}
+
+async fn upload_with_api(
+    http_client: reqwest::Client,
+    issuer_id: String,
+    key_id: String,
+    env_params: &IOSEnvironment,
+    app_params: &IOSParams,
+) -> UploadResult {
+    let path = Path::new(&app_params.ipa_file_path);
+
+    // Имя файла
+    let file_name = path
+        .file_name()
+        .ok_or("iOS: invalid file name")?
+        .to_str()
+        .ok_or("iOS: Invalid file name")?;
+
+    let pem = read_api_key(env_params).await.tap_err(|err| {
+        error!("App Store Connect key read failed: {}", err);
+    })?;
+    let key = AppStoreConnectKey::from_pem(issuer_id, key_id, &pem).tap_err(|err| {
+        error!("App Store Connect key parse failed: {}", err);
+    })?;
+
+    let processing_timeout = match app_params.processing_timeout_minutes.as_deref() {
+        Some(minutes) => Duration::from_secs(minutes.parse::<u64>()? * 60),
+        None => Duration::from_secs(60 * 60),
+    };
+
+    let client = AppStoreConnectClient::new(http_client, AppStoreConnectTokenProvider::new(key));
+    let task = AppStoreConnectUploadTask {
+        ipa_path: path,
+        processing_timeout,
+    };
+    let result = client.upload(task).await.tap_err(|err| {
+        error!("App Store Connect uploading failed: {}", err);
+    })?;
+    info!("App Store Connect uploading result: {:?}", result);
+
+    // Финальное сообщение
+    let mut message = format!(
+        "IOS uploading finished:\n- {}\n\nVersion: {} ({})",
+        file_name, result.ipa_info.version, result.ipa_info.build_number
+    );
+
+    if !is_testflight_requested(app_params) {
+        return Ok(UploadResultData {
+            target: "iOS",
+            message: Some(message),
+            install_url: None,
+        });
+    }
+
+    let what_to_test = read_what_to_test(app_params).await?;
+    let empty = Vec::new();
+    let testflight_task = AppStoreConnectTestFlightTask {
+        what_to_test: &what_to_test,
+        beta_groups: app_params.testflight_groups.as_ref().unwrap_or(&empty),
+        testers: app_params.testflight_testers.as_ref().unwrap_or(&empty),
+        submit_for_review: app_params.testflight_submit_review,
+        processing_timeout,
+    };
+    let testflight = client
+        .distribute_to_testflight(&result, testflight_task)
+        .await
+        .tap_err(|err| {
+            error!("TestFlight distribution failed: {}", err);
+        })?;
+    info!("TestFlight distribution result: {:?}", testflight);
+
+    message.push_str(&format!("\n\nTestFlight: {}", testflight.build_url));
+    if let Some(groups) = app_params.testflight_groups.as_ref() {
+        message.push_str(&format!("\nGroups: {}", groups.join(", ")));
+    }
+    if let Some(testers) = app_params.testflight_testers.as_ref() {
+        message.push_str(&format!("\nTesters: {}", testers.join(", ")));
+    }
+    if let Some(state) = testflight.beta_review_state.as_ref() {
+        message.push_str(&format!("\nBeta review: {}", state));
+    }
+
+    Ok(UploadResultData {
+        target: "iOS",
+        message: Some(message),
+        install_url: testflight.public_links.into_iter().next(),
+    })
+}
+
+async fn upload_with_altool(user: &str, pass: &str, app_params: &IOSParams) -> UploadResult {
+    let path = Path::new(&app_params.ipa_file_path);
+
     // Имя файла
     let file_name = path
         .file_name()
@@ -72,19 +250,20 @@ pub async fn upload_in_ios(env_params: IOSEnvironment, app_params: IOSParams) ->
                 "altool", "--upload-app",
                 "-f", &app_params.ipa_file_path, 
                 "-t", "ios",
-                "-u", &env_params.user,
-                "-p", &env_params.pass
+                "-u", user,
+                "-p", pass,
+                "--output-format", "json"
             ])
             .stderr(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::inherit())
+            .stdout(std::process::Stdio::piped())
             .stdin(std::process::Stdio::null());
         command
     };
 
-    // Делаем 3 попытки с паузой
+    // Делаем 3 попытки с паузой, но только для временных ошибок
     const ITER_COUNT: u8 = 3;
     let mut i = 0;
-    let output = loop {
+    loop {
         i += 1;
 
         // Запуск altool
@@ -97,34 +276,54 @@ pub async fn upload_in_ios(env_params: IOSEnvironment, app_params: IOSParams) ->
                 error!("xcrun start failed: {}", err);
             })?;
 
-        // Проверим ошибку
-        if output.status.code() != Some(0) {
-            let err = String::from_utf8(output.stderr)
-                .map_err(|err| Box::new(IOSError::ErrorParseFailed(err)))?;
-            let error_text = format!(
-                "Spawn failed with code {:?}, stderr: '{err}'",
-                output.status.code()
-            );
-
-            // Было ли превышено количество итераций?
-            if i > ITER_COUNT {
-                return UploadResult::Err(Box::new(IOSError::InvalidSpawn(error_text)));
-            } else {
-                // Пока что выводим ошибку как есть
-                error!("{}", error_text);
+        // Получим вывод приложения
+        let text = String::from_utf8(output.stdout)
+            .map_err(|err| Box::new(IOSError::OutputParseFailed(err)))?;
+        debug!("Uploading util output: {}", text);
 
-                // Подождем 10 секунд, может быть следующая итерация будет успешной
-                sleep(Duration::from_secs(10)).await;
+        // JSON с результатом altool пишет в stdout, даже если выгрузка не удалась
+        match parse_altool_output(&text).map(|output| output.into_result()) {
+            Some(Ok(success_message)) if output.status.success() => {
+                info!("Altool uploading success: {:?}", success_message);
+                break;
+            }
+            Some(Err(err)) if err.is_transient() && i <= ITER_COUNT => {
+                warn!("Altool temporary error, attempt {}: {}", i, err);
+            }
+            Some(Err(err)) => {
+                error!("{}", err);
+                return Err(Box::new(err));
+            }
+            None if output.status.success() => {
+                break;
+            }
+            parsed => {
+                // Ненулевой код выхода даже с сообщением об успехе считаем временной ошибкой
+                let err = String::from_utf8(output.stderr)
+                    .map_err(|err| Box::new(IOSError::ErrorParseFailed(err)))?;
+                let error_text = match parsed {
+                    Some(Ok(success_message)) => format!(
+                        "Spawn failed with code {:?}, message: '{}', stderr: '{err}'",
+                        output.status.code(),
+                        success_message.unwrap_or_default()
+                    ),
+                    _ => format!(
+                        "Spawn failed with code {:?}, stderr: '{err}'",
+                        output.status.code()
+                    ),
+                };
+
+                // Было ли превышено количество итераций?
+                if i > ITER_COUNT {
+                    return UploadResult::Err(Box::new(IOSError::InvalidSpawn(error_text)));
+                }
+                error!("{}", error_text);
             }
-        } else {
-            break output;
         }
-    };
 
-    // Получим вывод приложения
-    let text = String::from_utf8(output.stdout)
-        .map_err(|err| Box::new(IOSError::OutputParseFailed(err)))?;
-    debug!("Uploading util output: {}", text);
+        // Подождем 10 секунд, может быть следующая итерация будет успешной
+        sleep(Duration::from_secs(10)).await;
+    }
 
     // Финальное сообщение
     let message = format!("IOS uploading finished:\n- {}", file_name);
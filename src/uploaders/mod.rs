@@ -1,8 +1,15 @@
+mod altool_output;
 mod amazon;
+mod app_gallery;
+mod azure_blob;
 mod app_center;
+mod firebase_app_distribution;
+mod google_auth;
 mod google_drive;
 mod google_play;
 mod ios;
+mod rustore;
+mod s3;
 mod upload_result;
 mod windows_store;
 mod facebook_instant;
@@ -17,10 +24,15 @@ mod ssh;
 
 pub use self::{
     amazon::upload_in_amazon,
+    app_gallery::upload_in_app_gallery,
+    azure_blob::upload_in_azure_blob,
     app_center::upload_in_app_center,
+    firebase_app_distribution::upload_in_firebase_app_distribution,
     google_drive::upload_in_google_drive,
     google_play::upload_in_google_play,
     ios::upload_in_ios,
+    rustore::upload_in_rustore,
+    s3::upload_in_s3,
     ssh::upload_by_ssh,
     windows_store::upload_in_windows_store,
     facebook_instant::upload_in_facebook_instant,
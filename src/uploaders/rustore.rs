@@ -0,0 +1,89 @@
+use crate::{
+    app_parameters::RuStoreParams,
+    env_parameters::RuStoreEnvironment,
+    uploaders::{UploadResult, UploadResultData},
+};
+use log::{debug, error};
+use rustore_client::{
+    RuStoreClient, RuStoreKey, RuStorePublishType, RuStoreTokenProvider, RuStoreUploadTask,
+};
+use std::{path::Path, time::Duration};
+use tap::TapFallible;
+
+pub async fn upload_in_rustore(
+    http_client: reqwest::Client,
+    env_params: RuStoreEnvironment,
+    app_params: RuStoreParams,
+) -> UploadResult {
+    // Заранее проверяем параметры, чтобы не удалять черновики впустую
+    let publish_type = match app_params.publish_type.as_deref() {
+        Some(publish_type) => publish_type.parse::<RuStorePublishType>()?,
+        None => RuStorePublishType::Instantly,
+    };
+    let submit_timeout = match app_params.submit_timeout_minutes.as_deref() {
+        Some(minutes) => Duration::from_secs(minutes.parse::<u64>()? * 60),
+        None => Duration::from_secs(5 * 60),
+    };
+    let whats_new = match app_params.whats_new_file.as_deref() {
+        Some(path) => {
+            let text = tokio::fs::read_to_string(path).await.tap_err(|err| {
+                error!(
+                    "RuStore whats new file read failed: {}, error: {}",
+                    path, err
+                );
+            })?;
+            Some(text.trim().to_owned())
+        }
+        None => None,
+    };
+
+    let key =
+        RuStoreKey::from_base64(env_params.key_id, &env_params.private_key).tap_err(|err| {
+            error!("RuStore private key parsing failed: {}", err);
+        })?;
+
+    // Токен обновляется провайдером сам, но первый запрашиваем сразу, чтобы быстрее увидеть ошибку
+    let token_provider = RuStoreTokenProvider::new(http_client.clone(), key);
+    token_provider.get_access_token().await.tap_err(|err| {
+        error!("Access token request failed: {}", err);
+    })?;
+    debug!("RuStore token received");
+
+    // Грузим
+    let client = RuStoreClient::new(http_client, token_provider);
+    let task = RuStoreUploadTask {
+        package_name: &env_params.package_name,
+        file_path: Path::new(&app_params.file_path),
+        whats_new: whats_new.as_deref(),
+        publish_type,
+        submit: app_params.submit,
+        submit_timeout,
+    };
+    let result = client.upload(task).await.tap_err(|err| {
+        error!("RuStore uploading error: {}", err);
+    })?;
+    debug!("RuStore uploading result: {:?}", result);
+
+    // Финальное сообщение
+    let version = match (&result.version_name, &result.version_code) {
+        (Some(name), Some(code)) => format!("{} ({})", name, code),
+        (Some(name), None) => name.clone(),
+        (None, Some(code)) => code.to_string(),
+        (None, None) => result.version_id.to_string(),
+    };
+    let message = format!("RuStore uploading finished: {}", version);
+    let message = if result.submitted {
+        format!(
+            "{}\n\nSubmitted for moderation, status: {}",
+            message, result.version_status
+        )
+    } else {
+        message
+    };
+
+    Ok(UploadResultData {
+        target: "RuStore",
+        message: Some(message),
+        install_url: None,
+    })
+}
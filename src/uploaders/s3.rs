@@ -0,0 +1,142 @@
+use super::upload_result::{UploadResult, UploadResultData};
+use crate::{
+    app_parameters::S3Params,
+    env_parameters::{GitEnvironment, S3Environment},
+};
+use log::{error, info};
+use s3_client::{S3Client, S3Config, S3Credentials, S3UploadTask};
+use std::{error::Error, path::Path, time::Duration};
+use tap::TapFallible;
+
+/// Максимальное время жизни подписанного урла в часах
+const MAX_PRESIGN_EXPIRATION_HOURS: u64 = 24 * 7;
+
+fn get_file_name(path: &Path) -> Result<&str, &str> {
+    let file_name = path
+        .file_name()
+        .ok_or("S3: invalid file name")?
+        .to_str()
+        .ok_or("S3: Invalid file name")?;
+    Ok(file_name)
+}
+
+/// Теги из параметров вида `<ключ>=<значение>`, в начало добавляем ветку и коммит
+fn get_tags(
+    values: Option<&Vec<String>>,
+    git_info: Option<&GitEnvironment>,
+) -> Result<Vec<(String, String)>, Box<dyn Error + Send + Sync>> {
+    let mut tags = Vec::new();
+    if let Some(git_info) = git_info {
+        tags.push(("git_branch".to_owned(), git_info.git_branch.clone()));
+        tags.push(("git_commit".to_owned(), git_info.git_commit.clone()));
+    }
+    for value in values.into_iter().flatten() {
+        let (key, value) = value
+            .split_once('=')
+            .ok_or_else(|| format!("S3: invalid tag '{}', expected <key>=<value>", value))?;
+        tags.push((key.trim().to_owned(), value.trim().to_owned()));
+    }
+    Ok(tags)
+}
+
+fn get_config(app_params: &S3Params) -> Result<S3Config, Box<dyn Error + Send + Sync>> {
+    let mut config = S3Config::new(
+        app_params.endpoint.clone(),
+        app_params
+            .region
+            .clone()
+            .unwrap_or_else(|| "us-east-1".to_owned()),
+        app_params.bucket.clone(),
+    );
+    config.virtual_hosted_style = app_params.virtual_hosted_style;
+    if let Some(megabytes) = app_params.part_size.as_deref() {
+        config.part_size = megabytes.parse::<usize>()? * 1024 * 1024;
+    }
+    if let Some(concurrency) = app_params.concurrency.as_deref() {
+        config.concurrency = concurrency.parse::<usize>()?;
+    }
+    Ok(config)
+}
+
+fn get_presign_expiration(
+    app_params: &S3Params,
+) -> Result<Option<Duration>, Box<dyn Error + Send + Sync>> {
+    match app_params.presign_expiration.as_deref() {
+        Some(hours) => {
+            let hours = hours.parse::<u64>()?;
+            if hours == 0 || hours > MAX_PRESIGN_EXPIRATION_HOURS {
+                return Err(format!(
+                    "S3: presign expiration must be from 1 to {} hours",
+                    MAX_PRESIGN_EXPIRATION_HOURS
+                )
+                .into());
+            }
+            Ok(Some(Duration::from_secs(hours * 60 * 60)))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn upload_in_s3(
+    http_client: reqwest::Client,
+    env_params: S3Environment,
+    app_params: S3Params,
+    git_info: Option<GitEnvironment>,
+) -> UploadResult {
+    info!("Start S3 uploading");
+
+    let tags = get_tags(app_params.tags.as_ref(), git_info.as_ref())?;
+    let presign_expiration = get_presign_expiration(&app_params)?;
+
+    let credentials = S3Credentials {
+        access_key_id: env_params.access_key_id,
+        secret_access_key: env_params.secret_access_key,
+        session_token: env_params.session_token,
+    };
+    let client =
+        S3Client::new(http_client, credentials, get_config(&app_params)?).tap_err(|err| {
+            error!("S3 client create failed with error: {}", err);
+        })?;
+
+    let prefix = app_params
+        .prefix
+        .as_deref()
+        .map(|prefix| prefix.trim_matches('/'))
+        .filter(|prefix| !prefix.is_empty());
+
+    let mut urls = Vec::with_capacity(app_params.files.len());
+    for file in app_params.files.iter() {
+        let path = Path::new(file);
+        let file_name = get_file_name(path)?;
+        let key = match prefix {
+            Some(prefix) => format!("{}/{}", prefix, file_name),
+            None => file_name.to_owned(),
+        };
+
+        let task = S3UploadTask {
+            file_path: path,
+            key: &key,
+            content_type: app_params.content_type.as_deref(),
+            tags: &tags,
+        };
+        let result = client.upload_file(task).await.tap_err(|err| {
+            error!("S3 uploading failed for {}: {}", file, err);
+        })?;
+
+        // Без подписи урл доступен только для публичных бакетов
+        let url = match presign_expiration {
+            Some(expiration) => client.presigned_get_url(&result.key, expiration)?,
+            None => result.url,
+        };
+        info!("S3 uploading finished: {}", url);
+        urls.push(url.to_string());
+    }
+
+    let message = format!("S3 uploading finished:\n- {}", urls.join("\n- "));
+
+    Ok(UploadResultData {
+        target: "S3",
+        message: Some(message),
+        install_url: urls.into_iter().next(),
+    })
+}
@@ -1,9 +1,19 @@
 use super::upload_result::{UploadResult, UploadResultData};
 use crate::{app_parameters::WindowsStoreParams, env_parameters::WindowsStoreEnvironment};
-use microsoft_azure_client::MicrosoftAzureClient;
-use std::path::Path;
-use tap::TapFallible;
 use log::{error, info};
+use microsoft_azure_client::{
+    build_upload_archive, BlobUploadOptions, CertificationWait, ListingImage, ListingUpdate,
+    MandatoryUpdate, MicrosoftAzureClient, PackageRequirements, PackageRolloutAction,
+    PendingSubmissionPolicy, ProductionPublishOptions, PublishMode, SubmissionStatusResponse,
+    UploadArchive,
+};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tap::TapFallible;
 
 fn get_file_name(path: &Path) -> Result<&str, &str> {
     let file_name = path
@@ -14,6 +24,304 @@ fn get_file_name(path: &Path) -> Result<&str, &str> {
     Ok(file_name)
 }
 
+/// Файлик выгрузки: готовый .zip или собранный из пакетов архив
+enum UploadSource {
+    Zip(PathBuf),
+    Archive(UploadArchive),
+}
+
+impl UploadSource {
+    fn path(&self) -> &Path {
+        match self {
+            UploadSource::Zip(path) => path,
+            UploadSource::Archive(archive) => archive.path(),
+        }
+    }
+
+    /// Список файликов для финального сообщения
+    fn description(&self) -> Result<String, Box<dyn Error + Send + Sync>> {
+        match self {
+            UploadSource::Zip(path) => Ok(get_file_name(path)?.to_owned()),
+            UploadSource::Archive(archive) => Ok(archive
+                .packages
+                .iter()
+                .map(|package| {
+                    format!(
+                        "{} ({}, {})",
+                        package.file_name,
+                        package.version,
+                        package.architectures.join("/")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n- ")),
+        }
+    }
+}
+
+/// Готовый .zip используем как есть, а из пакетов собираем архив во временной директории
+async fn get_upload_source(
+    zip_file_path: Option<String>,
+    packages: Option<Vec<String>>,
+    listing_images: Vec<PathBuf>,
+    requirements: &PackageRequirements,
+) -> Result<Option<UploadSource>, Box<dyn Error + Send + Sync>> {
+    match (zip_file_path, packages) {
+        (Some(_), Some(_)) => {
+            Err("Microsoft Azure: ZIP file and packages can't be used together".into())
+        }
+        (Some(zip_file_path), None) => Ok(Some(UploadSource::Zip(PathBuf::from(zip_file_path)))),
+        (None, Some(packages)) => {
+            let archive = build_upload_archive(
+                packages.into_iter().map(PathBuf::from).collect(),
+                listing_images,
+                requirements.clone(),
+            )
+            .await
+            .tap_err(|err| {
+                error!("Microsoft Azure upload archive build failed: {}", err);
+            })?;
+            Ok(Some(UploadSource::Archive(archive)))
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+/// Параметры ожидания сертификации, если оно было запрошено
+fn get_certification_wait(
+    app_params: &WindowsStoreParams,
+) -> Result<Option<CertificationWait>, Box<dyn Error + Send + Sync>> {
+    if !app_params.wait_certification {
+        return Ok(None);
+    }
+
+    let poll_interval = match app_params.certification_poll_interval.as_deref() {
+        Some(seconds) => Duration::from_secs(seconds.parse::<u64>()?),
+        None => Duration::from_secs(60),
+    };
+    let timeout = match app_params.certification_timeout.as_deref() {
+        Some(minutes) => Duration::from_secs(minutes.parse::<u64>()? * 60),
+        None => Duration::from_secs(3 * 24 * 60 * 60),
+    };
+
+    Ok(Some(CertificationWait {
+        poll_interval,
+        timeout,
+    }))
+}
+
+/// Параметры выгрузки файлика блоками
+fn get_blob_upload_options(
+    app_params: &WindowsStoreParams,
+) -> Result<BlobUploadOptions, Box<dyn Error + Send + Sync>> {
+    let mut options = BlobUploadOptions::default();
+    if let Some(megabytes) = app_params.upload_block_size.as_deref() {
+        options.block_size = megabytes.parse::<usize>()? * 1024 * 1024;
+    }
+    if let Some(concurrency) = app_params.upload_concurrency.as_deref() {
+        options.concurrency = concurrency.parse::<usize>()?;
+    }
+    Ok(options)
+}
+
+/// Политика для незавершенных сабмиссий, без параметра сохраняем прежнее поведение
+fn get_pending_policies(
+    app_params: &WindowsStoreParams,
+) -> Result<(PendingSubmissionPolicy, PendingSubmissionPolicy), Box<dyn Error + Send + Sync>> {
+    let policy = match app_params.pending.as_deref() {
+        None => {
+            return Ok((
+                PendingSubmissionPolicy::Fail,
+                PendingSubmissionPolicy::Reuse,
+            ));
+        }
+        Some("reuse") => PendingSubmissionPolicy::Reuse,
+        Some("delete") => PendingSubmissionPolicy::Delete,
+        Some("fail") => PendingSubmissionPolicy::Fail,
+        Some(other) => {
+            return Err(format!("Microsoft Azure: unknown pending policy '{}'", other).into());
+        }
+    };
+    Ok((policy, policy))
+}
+
+/// Проверяем дату и приводим ее к формату, который понимает API
+fn parse_date(value: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let date = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|err| format!("Microsoft Azure: invalid date '{}': {}", value, err))?;
+    Ok(date
+        .with_timezone(&chrono::Utc)
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string())
+}
+
+fn parse_percentage(value: &str) -> Result<f32, Box<dyn Error + Send + Sync>> {
+    let percentage = value.trim().parse::<f32>()?;
+    if !(0.0..=100.0).contains(&percentage) {
+        return Err(format!("Microsoft Azure: invalid rollout percentage {}", value).into());
+    }
+    Ok(percentage)
+}
+
+/// Параметры публикации продакшен сабмиссии
+fn get_publish_options(
+    app_params: &WindowsStoreParams,
+) -> Result<ProductionPublishOptions, Box<dyn Error + Send + Sync>> {
+    let publish_mode = match app_params.publish_mode.as_deref() {
+        None | Some("manual") => PublishMode::Manual,
+        Some("immediate") => PublishMode::Immediate,
+        Some("specific-date") => {
+            let date = app_params
+                .publish_date
+                .as_deref()
+                .ok_or("Microsoft Azure: publish date is required for specific-date mode")?;
+            PublishMode::SpecificDate(parse_date(date)?)
+        }
+        Some(other) => {
+            return Err(format!("Microsoft Azure: unknown publish mode '{}'", other).into());
+        }
+    };
+
+    let rollout_percentage = match app_params.rollout_percentage.as_deref() {
+        Some(value) => Some(parse_percentage(value)?),
+        None => None,
+    };
+
+    let mandatory_update = match (
+        app_params.mandatory_update,
+        app_params.mandatory_update_date.as_deref(),
+    ) {
+        (true, date) => Some(MandatoryUpdate {
+            effective_date: date.map(parse_date).transpose()?,
+        }),
+        (false, Some(_)) => {
+            return Err(
+                "Microsoft Azure: mandatory update date requires mandatory update flag".into(),
+            );
+        }
+        (false, None) => None,
+    };
+
+    Ok(ProductionPublishOptions {
+        publish_mode,
+        rollout_percentage,
+        mandatory_update,
+    })
+}
+
+/// Команда управления раскаткой вида `update:<процент>`, `halt` или `finalize`
+fn parse_rollout_command(
+    value: &str,
+) -> Result<PackageRolloutAction, Box<dyn Error + Send + Sync>> {
+    match value.trim().split_once(':') {
+        Some(("update", percentage)) => {
+            Ok(PackageRolloutAction::Update(parse_percentage(percentage)?))
+        }
+        None if value.trim() == "halt" => Ok(PackageRolloutAction::Halt),
+        None if value.trim() == "finalize" => Ok(PackageRolloutAction::Finalize),
+        _ => Err(format!("Microsoft Azure: unknown rollout command '{}'", value).into()),
+    }
+}
+
+/// Читаем файлы из параметров вида `<язык>:<путь к файлу>`
+async fn read_language_files(
+    values: Option<&Vec<String>>,
+) -> Result<Vec<(String, String)>, Box<dyn Error + Send + Sync>> {
+    let mut result = Vec::new();
+    for value in values.into_iter().flatten() {
+        let (language, file_path) = value.split_once(':').ok_or_else(|| {
+            format!(
+                "Microsoft Azure: invalid listing value '{}', expected <language>:<file path>",
+                value
+            )
+        })?;
+
+        let text = tokio::fs::read_to_string(file_path).await.tap_err(|err| {
+            error!("Listing file read failed: {}, err: {}", file_path, err);
+        })?;
+
+        result.push((language.trim().to_lowercase(), text.trim().to_owned()));
+    }
+    Ok(result)
+}
+
+/// Картинка листинга в формате `<language>:<image type>:<file path>`
+fn parse_listing_image(
+    value: &str,
+) -> Result<(String, ListingImage), Box<dyn Error + Send + Sync>> {
+    let mut parts = value.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(language), Some(image_type), Some(file_path))
+            if !language.trim().is_empty() && !image_type.trim().is_empty() =>
+        {
+            Ok((
+                language.trim().to_lowercase(),
+                ListingImage {
+                    image_type: image_type.trim().to_owned(),
+                    file_path: PathBuf::from(file_path),
+                },
+            ))
+        }
+        _ => Err(format!(
+            "Microsoft Azure: invalid listing image '{}', expected <language>:<image type>:<file path>",
+            value
+        )
+        .into()),
+    }
+}
+
+/// Собираем изменения листингов по языкам
+async fn read_listing_updates(
+    app_params: &WindowsStoreParams,
+) -> Result<Vec<ListingUpdate>, Box<dyn Error + Send + Sync>> {
+    fn get_update(
+        updates: &mut BTreeMap<String, ListingUpdate>,
+        language: String,
+    ) -> &mut ListingUpdate {
+        updates
+            .entry(language.clone())
+            .or_insert_with(|| ListingUpdate {
+                language,
+                ..Default::default()
+            })
+    }
+
+    let mut updates = BTreeMap::new();
+
+    for (language, text) in read_language_files(app_params.whats_new.as_ref()).await? {
+        get_update(&mut updates, language).release_notes = Some(text);
+    }
+    for (language, text) in read_language_files(app_params.description.as_ref()).await? {
+        get_update(&mut updates, language).description = Some(text);
+    }
+    for (language, text) in read_language_files(app_params.features.as_ref()).await? {
+        let features = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_owned())
+            .collect();
+        get_update(&mut updates, language).features = Some(features);
+    }
+    for value in app_params.listing_images.iter().flatten() {
+        let (language, image) = parse_listing_image(value)?;
+        get_update(&mut updates, language).images.push(image);
+    }
+
+    Ok(updates.into_values().collect())
+}
+
+/// Статус сабмиссии пишем в сообщение только если ждали сертификацию
+fn format_status(
+    certification_wait: &Option<CertificationWait>,
+    status: &SubmissionStatusResponse,
+) -> String {
+    match certification_wait {
+        Some(_) => format!("\n{}", status),
+        None => String::new(),
+    }
+}
+
 pub async fn upload_in_windows_store(
     http_client: reqwest::Client,
     env_params: WindowsStoreEnvironment,
@@ -21,6 +329,47 @@ pub async fn upload_in_windows_store(
 ) -> UploadResult {
     info!("Start windows store uploading");
 
+    // Параметры проверяем заранее, до начала выгрузки
+    let certification_wait = get_certification_wait(&app_params)?;
+    let publish_options = get_publish_options(&app_params)?;
+    let rollout_action = app_params
+        .rollout_command
+        .as_deref()
+        .map(parse_rollout_command)
+        .transpose()?;
+    let listings = read_listing_updates(&app_params).await?;
+    let (production_pending_policy, flight_pending_policy) = get_pending_policies(&app_params)?;
+    let blob_upload_options = get_blob_upload_options(&app_params)?;
+
+    // Архивы из пакетов собираем и проверяем тоже заранее
+    let package_requirements = PackageRequirements {
+        version: app_params.package_version.clone(),
+        architectures: app_params.package_architectures.clone(),
+    };
+    if app_params.listing_images.is_some() && app_params.production_packages.is_none() {
+        return Err("Microsoft Azure: listing images require production packages".into());
+    }
+    // Картинки листингов кладем в архив рядом с пакетами
+    let listing_images = listings
+        .iter()
+        .flat_map(|update| update.images.iter())
+        .map(|image| image.file_path.clone())
+        .collect();
+    let production_source = get_upload_source(
+        app_params.production_zip_file_path,
+        app_params.production_packages,
+        listing_images,
+        &package_requirements,
+    )
+    .await?;
+    let test_flight_source = get_upload_source(
+        app_params.test_flight_zip_file_path,
+        app_params.test_flight_packages,
+        Vec::new(),
+        &package_requirements,
+    )
+    .await?;
+
     // Создаем клиента
     let client = MicrosoftAzureClient::new(
         http_client,
@@ -31,15 +380,33 @@ pub async fn upload_in_windows_store(
     )
     .tap_err(|err| {
         error!("Microsoft Azure client create failed with error: {}", err);
-    })?;
+    })?
+    .with_blob_upload_options(blob_upload_options);
 
     let mut messages = Vec::new();
 
-    // Продакшен выгрузка
-    if let Some(production_zip) = app_params.production_zip_file_path {
-        // Файлик выгрузки
-        let upload_file_path = Path::new(&production_zip);
+    // Раскатку текущей версии меняем до выгрузки, так как новая сабмиссия
+    // не может быть создана, пока раскатка не завершена или не остановлена
+    if let Some(action) = rollout_action {
+        let rollout = client
+            .manage_published_rollout(&action)
+            .await
+            .tap_err(|err| {
+                error!("Microsoft Azure rollout command failed with error: {}", err);
+            })?;
+
+        messages.push(format!(
+            "Windows store rollout updated:\n- {}%, status: {}",
+            rollout.package_rollout_percentage,
+            rollout
+                .package_rollout_status
+                .as_deref()
+                .unwrap_or("unknown")
+        ));
+    }
 
+    // Продакшен выгрузка
+    if let Some(production_source) = production_source {
         // Генерация имени выгрузки
         let submission_name = match app_params.production_submission_name {
             Some(name) => name,
@@ -50,8 +417,15 @@ pub async fn upload_in_windows_store(
         };
 
         // Делавем попытку выгрузки
-        client
-            .upload_production_build(upload_file_path, submission_name)
+        let status = client
+            .upload_production_build(
+                production_source.path(),
+                submission_name,
+                &publish_options,
+                &listings,
+                production_pending_policy,
+                certification_wait.as_ref(),
+            )
             .await
             .tap_err(|err| {
                 error!(
@@ -62,19 +436,16 @@ pub async fn upload_in_windows_store(
 
         // Финальное сообщение
         messages.push(format!(
-            "Windows store production uploading finished:\n- {}",
-            get_file_name(upload_file_path)?
+            "Windows store production uploading finished:\n- {}{}",
+            production_source.description()?,
+            format_status(&certification_wait, &status)
         ));
     }
 
     // Тестовая выгрузка
-    if let (Some(test_zip), Some(groups)) = (
-        app_params.test_flight_zip_file_path,
-        app_params.test_flight_groups,
-    ) {
-        // Файлик выгрузки
-        let upload_file_path = Path::new(&test_zip);
-
+    if let (Some(test_flight_source), Some(groups)) =
+        (test_flight_source, app_params.test_flight_groups)
+    {
         // Генерация имени выгрузки
         let flight_name = match app_params.test_flight_name {
             Some(name) => name,
@@ -85,16 +456,24 @@ pub async fn upload_in_windows_store(
         };
 
         // Делавем попытку выгрузки
-        client
-            .upload_flight_build(upload_file_path, groups, flight_name)
+        let status = client
+            .upload_flight_build(
+                test_flight_source.path(),
+                groups,
+                flight_name,
+                app_params.test_flight_rank_higher_than,
+                flight_pending_policy,
+                certification_wait.as_ref(),
+            )
             .await
             .tap_err(|err| {
                 error!("Microsoft Azure test uploading failed with error: {}", err);
             })?;
 
         messages.push(format!(
-            "Windows store test uploading finished:\n- {}",
-            get_file_name(upload_file_path)?
+            "Windows store test uploading finished:\n- {}{}",
+            test_flight_source.description()?,
+            format_status(&certification_wait, &status)
         ));
     }
 